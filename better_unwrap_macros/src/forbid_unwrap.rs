@@ -0,0 +1,58 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::spanned::Spanned;
+use syn::visit::{self, Visit};
+use syn::{ExprMethodCall, Item};
+
+pub(crate) fn expand(attr: TokenStream, item: Item) -> syn::Result<TokenStream> {
+    if !attr.is_empty() {
+        return Err(syn::Error::new(
+            attr.span(),
+            "`forbid_unwrap` does not take arguments",
+        ));
+    }
+    match &item {
+        Item::Fn(_) | Item::Mod(_) | Item::Impl(_) => {}
+        _ => {
+            return Err(syn::Error::new(
+                item.span(),
+                "`forbid_unwrap` can only be applied to functions, inline modules, and impl blocks",
+            ));
+        }
+    }
+
+    let mut finder = UnwrapFinder { errors: None };
+    finder.visit_item(&item);
+    // The item is kept alongside the errors so that its uses do not report spurious errors too.
+    let errors = finder.errors.map(syn::Error::into_compile_error);
+    Ok(quote!(#errors #item))
+}
+
+/// Collects an error for every `.unwrap()` and `.expect(..)` call it visits.
+struct UnwrapFinder {
+    errors: Option<syn::Error>,
+}
+
+impl<'ast> Visit<'ast> for UnwrapFinder {
+    fn visit_expr_method_call(&mut self, call: &'ast ExprMethodCall) {
+        let replacement = match call.method.to_string().as_str() {
+            "unwrap" => Some("`.or_panic()`"),
+            "expect" => Some("`.panic_with(msg)`"),
+            _ => None,
+        };
+        if let Some(replacement) = replacement {
+            let error = syn::Error::new(
+                call.method.span(),
+                format!(
+                    "`.{}()` is forbidden by `#[forbid_unwrap]`; use {replacement} from `better_unwrap` instead",
+                    call.method
+                ),
+            );
+            match &mut self.errors {
+                Some(errors) => errors.combine(error),
+                None => self.errors = Some(error),
+            }
+        }
+        visit::visit_expr_method_call(self, call);
+    }
+}
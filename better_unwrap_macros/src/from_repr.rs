@@ -0,0 +1,88 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Data, DeriveInput, Fields, Ident};
+
+const INTEGER_REPRS: &[&str] = &[
+    "u8", "u16", "u32", "u64", "u128", "usize", "i8", "i16", "i32", "i64", "i128", "isize",
+];
+
+pub(crate) fn expand(input: DeriveInput) -> syn::Result<TokenStream> {
+    let name = &input.ident;
+    let Data::Enum(data) = &input.data else {
+        return Err(syn::Error::new_spanned(
+            name,
+            "`FromReprOrPanic` can only be derived for enums",
+        ));
+    };
+    if let Some(variant) = data
+        .variants
+        .iter()
+        .find(|variant| !matches!(variant.fields, Fields::Unit))
+    {
+        return Err(syn::Error::new_spanned(
+            variant,
+            "`FromReprOrPanic` requires every variant to be fieldless",
+        ));
+    }
+    let repr = integer_repr(&input)?;
+    let variants: Vec<&Ident> = data.variants.iter().map(|variant| &variant.ident).collect();
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics #name #ty_generics #where_clause {
+            /// Converts a discriminant into the matching variant.
+            ///
+            /// # Panics
+            ///
+            /// Panics if no variant has the discriminant `value`.
+            #[track_caller]
+            pub fn from_repr_or_panic(value: #repr) -> Self {
+                #(
+                    if value == Self::#variants as #repr {
+                        return Self::#variants;
+                    }
+                )*
+                ::better_unwrap::__private::fail(::core::format_args!(
+                    "invalid discriminant {} for enum `{}`",
+                    value,
+                    ::core::stringify!(#name),
+                ))
+            }
+
+            /// Converts a discriminant into the matching variant, or returns `default` if no variant
+            /// has the discriminant `value`.
+            pub fn from_repr_or(value: #repr, default: Self) -> Self {
+                #(
+                    if value == Self::#variants as #repr {
+                        return Self::#variants;
+                    }
+                )*
+                default
+            }
+        }
+    })
+}
+
+fn integer_repr(input: &DeriveInput) -> syn::Result<Ident> {
+    let mut repr = None;
+    for attr in input
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("repr"))
+    {
+        attr.parse_nested_meta(|meta| {
+            if let Some(ident) = meta.path.get_ident()
+                && INTEGER_REPRS.contains(&ident.to_string().as_str())
+            {
+                repr = Some(ident.clone());
+            }
+            Ok(())
+        })?;
+    }
+    repr.ok_or_else(|| {
+        syn::Error::new_spanned(
+            &input.ident,
+            "`FromReprOrPanic` requires an integer representation such as `#[repr(u8)]`",
+        )
+    })
+}
@@ -0,0 +1,65 @@
+//! Procedural macros for `better_unwrap`.
+//!
+//! These are re-exported from `better_unwrap` when its `macros` feature is enabled and should be used
+//! from there.
+
+use proc_macro::TokenStream;
+use syn::{DeriveInput, Ident, Item, ItemFn, parse_macro_input};
+
+mod forbid_unwrap;
+mod from_repr;
+mod panic_message;
+mod panic_policy;
+
+/// Runs the body of a function under a scoped `better_unwrap` policy.
+///
+/// Accepts `panic`, `log_and_default`, or `abort`. Under `log_and_default` the function returns
+/// `Default::default()` when a failure occurs in its body, so its return type must implement `Default`.
+///
+/// Failures in the body also include the function's module path in their message.
+#[proc_macro_attribute]
+pub fn panic_policy(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let policy = parse_macro_input!(attr as Ident);
+    let function = parse_macro_input!(item as ItemFn);
+    panic_policy::expand(policy, function)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Rejects `.unwrap()` and `.expect(..)` calls in a function or inline module at compile time.
+///
+/// Each call is reported with an error pointing at the `better_unwrap` equivalent. Calls inside macro
+/// invocations are not inspected, since their arguments are not parsed until the macro expands.
+#[proc_macro_attribute]
+pub fn forbid_unwrap(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let item = parse_macro_input!(item as Item);
+    forbid_unwrap::expand(attr.into(), item)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Derives `from_repr_or_panic(value)` and `from_repr_or(value, default)` for a fieldless enum with an
+/// integer representation such as `#[repr(u8)]`.
+///
+/// `from_repr_or_panic` panics with the invalid discriminant and the enum name.
+#[proc_macro_derive(FromReprOrPanic)]
+pub fn derive_from_repr_or_panic(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    from_repr::expand(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Derives `better_unwrap::PanicMessage`, which controls how a value is rendered by
+/// `or_panic_message()`.
+///
+/// Each enum variant, or the struct itself, may carry a `#[panic_message("...")]` format string that
+/// refers to named fields by name and to tuple fields by position, as in `{0}`. Variants without one
+/// are rendered using `Debug`.
+#[proc_macro_derive(PanicMessage, attributes(panic_message))]
+pub fn derive_panic_message(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    panic_message::expand(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
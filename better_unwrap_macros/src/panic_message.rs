@@ -0,0 +1,86 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{Attribute, Data, DeriveInput, Fields, Ident, LitStr};
+
+pub(crate) fn expand(input: DeriveInput) -> syn::Result<TokenStream> {
+    let name = &input.ident;
+    let arms = match &input.data {
+        Data::Enum(data) => data
+            .variants
+            .iter()
+            .map(|variant| {
+                let ident = &variant.ident;
+                arm(quote!(Self::#ident), &variant.fields, &variant.attrs)
+            })
+            .collect::<syn::Result<Vec<_>>>()?,
+        Data::Struct(data) => vec![arm(quote!(Self), &data.fields, &input.attrs)?],
+        Data::Union(_) => {
+            return Err(syn::Error::new_spanned(
+                name,
+                "`PanicMessage` can only be derived for enums and structs",
+            ));
+        }
+    };
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics ::better_unwrap::PanicMessage for #name #ty_generics #where_clause {
+            fn fmt_panic_message(
+                &self,
+                f: &mut ::core::fmt::Formatter<'_>,
+            ) -> ::core::fmt::Result {
+                #[allow(unused_variables)]
+                match self {
+                    #(#arms)*
+                }
+            }
+        }
+    })
+}
+
+/// Builds the match arm rendering one variant, or the struct, with its `#[panic_message("...")]`
+/// format string, falling back to `Debug` without one.
+fn arm(path: TokenStream, fields: &Fields, attrs: &[Attribute]) -> syn::Result<TokenStream> {
+    let Some(format) = format_string(attrs)? else {
+        return Ok(quote!(#path { .. } => ::core::fmt::Debug::fmt(self, f),));
+    };
+    match fields {
+        Fields::Named(fields) => {
+            let names = fields.named.iter().map(|field| &field.ident);
+            Ok(quote!(#path { #(#names),* } => ::core::write!(f, #format),))
+        }
+        Fields::Unnamed(fields) => {
+            let names: Vec<Ident> = (0..fields.unnamed.len())
+                .map(|index| format_ident!("__field{}", index))
+                .collect();
+            let format = LitStr::new(&name_positions(&format.value()), format.span());
+            Ok(quote!(#path(#(#names),*) => ::core::write!(f, #format),))
+        }
+        Fields::Unit => Ok(quote!(#path => ::core::write!(f, #format),)),
+    }
+}
+
+/// Returns the format string of the `#[panic_message("...")]` attribute, if any.
+fn format_string(attrs: &[Attribute]) -> syn::Result<Option<LitStr>> {
+    attrs
+        .iter()
+        .find(|attr| attr.path().is_ident("panic_message"))
+        .map(|attr| attr.parse_args::<LitStr>())
+        .transpose()
+}
+
+/// Rewrites positional references such as `{0}` and `{1:?}` into the names the tuple fields are bound
+/// to, leaving escaped braces untouched.
+fn name_positions(format: &str) -> String {
+    let mut output = String::with_capacity(format.len());
+    let mut chars = format.chars().peekable();
+    while let Some(c) = chars.next() {
+        output.push(c);
+        match c {
+            '{' if chars.peek() == Some(&'{') => output.extend(chars.next()),
+            '{' if chars.peek().is_some_and(char::is_ascii_digit) => output.push_str("__field"),
+            _ => {}
+        }
+    }
+    output
+}
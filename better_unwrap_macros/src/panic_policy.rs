@@ -0,0 +1,40 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Ident, ItemFn, ReturnType, Type, parse_quote};
+
+pub(crate) fn expand(policy: Ident, mut function: ItemFn) -> syn::Result<TokenStream> {
+    let (variant, scope) = match policy.to_string().as_str() {
+        "panic" => (quote!(Panic), quote!(with)),
+        "log_and_default" => (quote!(LogAndDefault), quote!(with_default)),
+        "abort" => (quote!(Abort), quote!(with)),
+        _ => {
+            return Err(syn::Error::new(
+                policy.span(),
+                "expected `panic`, `log_and_default`, or `abort`",
+            ));
+        }
+    };
+    if let Some(asyncness) = function.sig.asyncness {
+        return Err(syn::Error::new(
+            asyncness.span,
+            "`panic_policy` does not support async functions",
+        ));
+    }
+
+    let output = match &function.sig.output {
+        ReturnType::Type(_, ty) if !matches!(**ty, Type::ImplTrait(_)) => quote!(-> #ty),
+        ReturnType::Type(..) => quote!(),
+        ReturnType::Default => quote!(-> ()),
+    };
+    let body = &function.block;
+    function.block = parse_quote!({
+        ::better_unwrap::report::with_module_path(::core::module_path!(), move || {
+            ::better_unwrap::policy::#scope(
+                ::better_unwrap::policy::Policy::#variant,
+                move || #output #body,
+            )
+        })
+    });
+
+    Ok(quote!(#function))
+}
@@ -58,4 +58,3 @@ fn main() {
     // let none_option: Option<u32> = None;
     // none_option.panic_with("Expected a value here"); // This will panic with custom message
 }
-
@@ -0,0 +1,29 @@
+//! Instantiates every method annotated under the `no-panic` feature, so that a release build of
+//! this example fails to link if a panic path is introduced into any of them:
+//!
+//! ```sh
+//! cargo build --release --example no_panic --features no-panic
+//! ```
+
+use better_unwrap::prelude::*;
+
+fn main() {
+    // Derived from the arguments, so the optimizer cannot fold away the fallback branches.
+    let count = std::env::args().count();
+    let option = (count > 1).then_some(count);
+    let result = option.ok_or(count);
+
+    let values = [
+        option.panic_or(0),
+        option.panic_or_else(|| 1),
+        option.panic_or_default(),
+        result.panic_or(0),
+        result.panic_or_else(|error| error + 1),
+        result.panic_or_default(),
+        option.debug_panic_or(0),
+        option.debug_panic_or_else(|| 1),
+        result.debug_panic_or(0),
+        result.debug_panic_or_else(|error| error + 1),
+    ];
+    println!("{values:?}");
+}
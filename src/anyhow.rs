@@ -0,0 +1,141 @@
+//! Panicking with [`anyhow`](https://docs.rs/anyhow) context.
+//!
+//! Available with the `anyhow` feature. `or_panic()` on an `anyhow::Result` already renders the error
+//! with anyhow's `Debug` format: the outermost context, a `Caused by:` list of every underlying error,
+//! and the backtrace captured when the error was created, if backtraces are enabled. [`BUAnyhow`]
+//! adds one more layer of context at the failing call, in place of `.context("...").unwrap()`.
+
+use std::fmt::Display;
+
+use ::anyhow::Context;
+
+use crate::message::Truncated;
+use crate::policy;
+
+/// Trait that provides alternatives to `.context(...).unwrap()` for results whose error anyhow can
+/// wrap.
+pub trait BUAnyhow<T> {
+    /// Unwraps the result, attaching `context` to the error before panicking.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is an `Err`, with a panic message including `context`, the chain of errors
+    /// it wraps, and the backtrace anyhow captured, if any.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use better_unwrap::anyhow::BUAnyhow;
+    ///
+    /// let x: anyhow::Result<u32> = Ok(42);
+    /// assert_eq!(x.context_or_panic("reading the port"), 42);
+    /// ```
+    ///
+    /// ```should_panic
+    /// use std::fs;
+    /// use better_unwrap::anyhow::BUAnyhow;
+    ///
+    /// let config = fs::read_to_string("/nonexistent/app.toml");
+    /// config.context_or_panic("loading the configuration"); // panics with `"called `context_or_panic()` on an `Err` value: loading the configuration\n\nCaused by:\n    No such file or directory (os error 2)"`
+    /// ```
+    #[track_caller]
+    fn context_or_panic<C>(self, context: C) -> T
+    where
+        C: Display + Send + Sync + 'static;
+
+    /// Unwraps the result, attaching the context returned by `f` to the error before panicking.
+    ///
+    /// `f` is only called if the value is an `Err`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is an `Err`, with a panic message including the context, the chain of
+    /// errors it wraps, and the backtrace anyhow captured, if any.
+    ///
+    /// # Examples
+    ///
+    /// ```should_panic
+    /// use std::fs;
+    /// use better_unwrap::anyhow::BUAnyhow;
+    ///
+    /// let path = "/nonexistent/app.toml";
+    /// fs::read_to_string(path).with_context_or_panic(|| format!("loading {path}"));
+    /// ```
+    #[track_caller]
+    fn with_context_or_panic<C, F>(self, f: F) -> T
+    where
+        C: Display + Send + Sync + 'static,
+        F: FnOnce() -> C;
+}
+
+impl<T, E> BUAnyhow<T> for Result<T, E>
+where
+    Result<T, E>: Context<T, E>,
+{
+    #[track_caller]
+    fn context_or_panic<C>(self, context: C) -> T
+    where
+        C: Display + Send + Sync + 'static,
+    {
+        match self.context(context) {
+            Ok(value) => value,
+            Err(error) => fail_context("context_or_panic", &error),
+        }
+    }
+
+    #[track_caller]
+    fn with_context_or_panic<C, F>(self, f: F) -> T
+    where
+        C: Display + Send + Sync + 'static,
+        F: FnOnce() -> C,
+    {
+        match self.with_context(f) {
+            Ok(value) => value,
+            Err(error) => fail_context("with_context_or_panic", &error),
+        }
+    }
+}
+
+/// Fails with an error that `method` attached context to, rendered with anyhow's `Debug` format.
+#[track_caller]
+fn fail_context(method: &str, error: &::anyhow::Error) -> ! {
+    policy::fail(format_args!(
+        "called `{method}()` on an `Err` value: {error:?}",
+        error = Truncated(error)
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    // Glob-imported next to `anyhow::Context`, whose `context()` must stay unambiguous.
+    use crate::prelude::*;
+    use std::io;
+
+    #[test]
+    #[should_panic(
+        expected = "called `or_panic()` on an `Err` value: reading the port\n\nCaused by:\n    refused"
+    )]
+    fn test_or_panic_renders_anyhow_chain() {
+        let result: Result<u16, io::Error> = Err(io::Error::other("refused"));
+        result.context("reading the port").or_panic();
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "called `context_or_panic()` on an `Err` value: connecting to the broker\n\nCaused by:\n    0: reading the port\n    1: refused"
+    )]
+    fn test_context_or_panic_adds_context() {
+        let result: Result<u16, io::Error> = Err(io::Error::other("refused"));
+        result
+            .context("reading the port")
+            .context_or_panic("connecting to the broker");
+    }
+
+    #[test]
+    fn test_with_context_or_panic_is_lazy_on_ok() {
+        let result: Result<u16, io::Error> = Ok(8080);
+        let port = result.with_context_or_panic(|| -> &str { unreachable!() });
+        assert_eq!(port, 8080);
+    }
+}
@@ -0,0 +1,114 @@
+use std::fmt::Debug;
+
+use crate::message::Truncated;
+use crate::policy;
+
+/// Trait that unwraps every slot of a fixed-size array of `Option`s or `Result`s at once.
+///
+/// Useful where every position must be present, such as fixed lanes or board squares; the panic
+/// message lists every failing position rather than just the first.
+pub trait BUArray<T, const N: usize> {
+    /// Unwraps every element, yielding an array of the contained values.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any element is `None` or `Err`, with a panic message listing the failing positions
+    /// (and, for `Result`, their errors formatted using `Debug`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use better_unwrap::BUArray;
+    ///
+    /// let lanes = [Some(1), Some(2), Some(3)];
+    /// assert_eq!(lanes.unwrap_all_or_panic(), [1, 2, 3]);
+    /// ```
+    ///
+    /// ```should_panic
+    /// use better_unwrap::BUArray;
+    ///
+    /// let lanes = [Some(1), None, None];
+    /// lanes.unwrap_all_or_panic(); // panics with `"called `unwrap_all_or_panic()` with `None` at positions [1, 2]"`
+    /// ```
+    #[track_caller]
+    fn unwrap_all_or_panic(self) -> [T; N];
+}
+
+impl<T, const N: usize> BUArray<T, N> for [Option<T>; N] {
+    #[track_caller]
+    fn unwrap_all_or_panic(self) -> [T; N] {
+        let mut missing = Vec::new();
+        let values: Vec<T> = self
+            .into_iter()
+            .enumerate()
+            .filter_map(|(index, item)| {
+                if item.is_none() {
+                    missing.push(index);
+                }
+                item
+            })
+            .collect();
+        match values.try_into() {
+            Ok(values) => values,
+            Err(_) => policy::fail(format_args!(
+                "called `unwrap_all_or_panic()` with `None` at positions {missing:?}"
+            )),
+        }
+    }
+}
+
+impl<T, E: Debug, const N: usize> BUArray<T, N> for [Result<T, E>; N] {
+    #[track_caller]
+    fn unwrap_all_or_panic(self) -> [T; N] {
+        let mut errors = Vec::new();
+        let values: Vec<T> = self
+            .into_iter()
+            .enumerate()
+            .filter_map(|(index, item)| match item {
+                Ok(value) => Some(value),
+                Err(error) => {
+                    errors.push(format!("{index}: {:?}", Truncated(&error)));
+                    None
+                }
+            })
+            .collect();
+        match values.try_into() {
+            Ok(values) => values,
+            Err(_) => policy::fail(format_args!(
+                "called `unwrap_all_or_panic()` with `Err` at positions [{}]",
+                errors.join(", ")
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unwrap_all_or_panic_with_all_some() {
+        assert_eq!([Some('a'), Some('b')].unwrap_all_or_panic(), ['a', 'b']);
+    }
+
+    #[test]
+    #[should_panic(expected = "called `unwrap_all_or_panic()` with `None` at positions [0, 2]")]
+    fn test_unwrap_all_or_panic_lists_none_positions() {
+        let _ = [None, Some(1), None].unwrap_all_or_panic();
+    }
+
+    #[test]
+    fn test_unwrap_all_or_panic_with_all_ok() {
+        let squares: [Result<u8, &str>; 2] = [Ok(1), Ok(4)];
+        assert_eq!(squares.unwrap_all_or_panic(), [1, 4]);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "called `unwrap_all_or_panic()` with `Err` at positions [1: \"blocked\", 2: \"off board\"]"
+    )]
+    fn test_unwrap_all_or_panic_lists_errors() {
+        let squares: [Result<u8, &str>; 3] = [Ok(1), Err("blocked"), Err("off board")];
+        let _ = squares.unwrap_all_or_panic();
+    }
+}
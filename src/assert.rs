@@ -0,0 +1,224 @@
+//! Test assertions that check the variant of an `Option` or `Result` and return its content.
+
+/// Asserts that a `Result` is `Ok`, returning the `Ok` value.
+///
+/// Like `assert!`, the assertion always panics on failure, regardless of the
+/// [policy](crate::policy) in effect. A custom message can follow the expression, with the same
+/// syntax as `format!`.
+///
+/// # Panics
+///
+/// Panics if the value is an `Err`, with a panic message including the text of the expression and
+/// the error formatted using `Debug`.
+///
+/// # Examples
+///
+/// ```
+/// use better_unwrap::assert_ok;
+///
+/// let port: u16 = assert_ok!("8080".parse::<u16>());
+/// assert_eq!(port, 8080);
+/// ```
+///
+/// ```should_panic
+/// use better_unwrap::assert_ok;
+///
+/// assert_ok!("80a".parse::<u16>()); // panics with `"assertion failed: `\"80a\".parse::<u16>()` is `Ok`\n  found: Err(ParseIntError { kind: InvalidDigit })"`
+/// ```
+#[macro_export]
+macro_rules! assert_ok {
+    ($expr:expr $(,)?) => {
+        match $expr {
+            ::core::result::Result::Ok(value) => value,
+            ::core::result::Result::Err(error) => ::core::panic!(
+                "assertion failed: `{}` is `Ok`\n  found: Err({:?})",
+                ::core::stringify!($expr),
+                $crate::__private::truncated(&error)
+            ),
+        }
+    };
+    ($expr:expr, $($arg:tt)+) => {
+        match $expr {
+            ::core::result::Result::Ok(value) => value,
+            ::core::result::Result::Err(error) => ::core::panic!(
+                "assertion failed: `{}` is `Ok`: {}\n  found: Err({:?})",
+                ::core::stringify!($expr),
+                ::core::format_args!($($arg)+),
+                $crate::__private::truncated(&error)
+            ),
+        }
+    };
+}
+
+/// Asserts that a `Result` is `Err`, returning the error.
+///
+/// A custom message can follow the expression, with the same syntax as `format!`.
+///
+/// # Panics
+///
+/// Panics if the value is `Ok`, with a panic message including the text of the expression and the
+/// `Ok` value formatted using `Debug`.
+///
+/// # Examples
+///
+/// ```
+/// use better_unwrap::assert_err;
+///
+/// let error = assert_err!("80a".parse::<u16>());
+/// assert_eq!(error.to_string(), "invalid digit found in string");
+/// ```
+#[macro_export]
+macro_rules! assert_err {
+    ($expr:expr $(,)?) => {
+        match $expr {
+            ::core::result::Result::Err(error) => error,
+            ::core::result::Result::Ok(value) => ::core::panic!(
+                "assertion failed: `{}` is `Err`\n  found: Ok({:?})",
+                ::core::stringify!($expr),
+                $crate::__private::truncated(&value)
+            ),
+        }
+    };
+    ($expr:expr, $($arg:tt)+) => {
+        match $expr {
+            ::core::result::Result::Err(error) => error,
+            ::core::result::Result::Ok(value) => ::core::panic!(
+                "assertion failed: `{}` is `Err`: {}\n  found: Ok({:?})",
+                ::core::stringify!($expr),
+                ::core::format_args!($($arg)+),
+                $crate::__private::truncated(&value)
+            ),
+        }
+    };
+}
+
+/// Asserts that an `Option` is `Some`, returning the contained value.
+///
+/// A custom message can follow the expression, with the same syntax as `format!`.
+///
+/// # Panics
+///
+/// Panics if the value is `None`, with a panic message including the text of the expression.
+///
+/// # Examples
+///
+/// ```
+/// use better_unwrap::assert_some;
+///
+/// let ports = [8080, 8443];
+/// assert_eq!(*assert_some!(ports.first()), 8080);
+/// ```
+#[macro_export]
+macro_rules! assert_some {
+    ($expr:expr $(,)?) => {
+        match $expr {
+            ::core::option::Option::Some(value) => value,
+            ::core::option::Option::None => ::core::panic!(
+                "assertion failed: `{}` is `Some`\n  found: None",
+                ::core::stringify!($expr)
+            ),
+        }
+    };
+    ($expr:expr, $($arg:tt)+) => {
+        match $expr {
+            ::core::option::Option::Some(value) => value,
+            ::core::option::Option::None => ::core::panic!(
+                "assertion failed: `{}` is `Some`: {}\n  found: None",
+                ::core::stringify!($expr),
+                ::core::format_args!($($arg)+)
+            ),
+        }
+    };
+}
+
+/// Asserts that an `Option` is `None`.
+///
+/// A custom message can follow the expression, with the same syntax as `format!`.
+///
+/// # Panics
+///
+/// Panics if the value is `Some`, with a panic message including the text of the expression and the
+/// contained value formatted using `Debug`.
+///
+/// # Examples
+///
+/// ```
+/// use better_unwrap::assert_none;
+///
+/// let ports: [u16; 0] = [];
+/// assert_none!(ports.first());
+/// ```
+#[macro_export]
+macro_rules! assert_none {
+    ($expr:expr $(,)?) => {
+        if let ::core::option::Option::Some(value) = $expr {
+            ::core::panic!(
+                "assertion failed: `{}` is `None`\n  found: Some({:?})",
+                ::core::stringify!($expr),
+                $crate::__private::truncated(&value)
+            );
+        }
+    };
+    ($expr:expr, $($arg:tt)+) => {
+        if let ::core::option::Option::Some(value) = $expr {
+            ::core::panic!(
+                "assertion failed: `{}` is `None`: {}\n  found: Some({:?})",
+                ::core::stringify!($expr),
+                ::core::format_args!($($arg)+),
+                $crate::__private::truncated(&value)
+            );
+        }
+    };
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use crate::policy::{self, Policy};
+
+    fn find(id: u32) -> Result<&'static str, String> {
+        match id {
+            1 => Ok("ada"),
+            _ => Err(format!("no user {id}")),
+        }
+    }
+
+    #[test]
+    fn test_assertions_return_content() {
+        assert_eq!(assert_ok!(find(1)), "ada");
+        assert_eq!(assert_err!(find(2)), "no user 2");
+        assert_eq!(assert_some!(Some(3), "expected a value"), 3);
+        assert_none!(None::<u32>);
+    }
+
+    #[test]
+    #[should_panic(expected = "assertion failed: `find(2)` is `Ok`\n  found: Err(\"no user 2\")")]
+    fn test_assert_ok_shows_error() {
+        assert_ok!(find(2));
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "assertion failed: `find(1)` is `Err`: looking up user 1\n  found: Ok(\"ada\")"
+    )]
+    fn test_assert_err_shows_value_and_message() {
+        assert_err!(find(1), "looking up user {}", 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "assertion failed: `[1, 2].get(5)` is `Some`\n  found: None")]
+    fn test_assert_some_shows_expression() {
+        assert_some!([1, 2].get(5));
+    }
+
+    #[test]
+    #[should_panic(expected = "assertion failed: `[1, 2].first()` is `None`\n  found: Some(1)")]
+    fn test_assert_none_shows_value() {
+        assert_none!([1, 2].first());
+    }
+
+    #[test]
+    #[should_panic(expected = "assertion failed: `None::<u32>` is `Some`")]
+    fn test_assertions_ignore_policy() {
+        policy::with_default(Policy::LogAndDefault, || assert_some!(None::<u32>));
+    }
+}
@@ -0,0 +1,113 @@
+use std::borrow::{Cow, ToOwned};
+use std::fmt::Debug;
+
+use crate::message::Truncated;
+use crate::policy;
+
+/// Trait that asserts which state a `Cow` is in and returns its contents.
+///
+/// Useful in zero-copy code paths where an unexpected allocation (or an unexpected borrow) is a bug.
+pub trait BUCow<'a, B: ?Sized + ToOwned> {
+    /// Returns the borrowed reference of a `Cow::Borrowed`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is `Cow::Owned`, with a panic message including the owned value formatted
+    /// using `Debug`.
+    ///
+    /// # Examples
+    ///
+    /// ```should_panic
+    /// use std::borrow::Cow;
+    /// use better_unwrap::BUCow;
+    ///
+    /// let x: Cow<str> = Cow::Owned("allocated".to_string());
+    /// x.borrowed_or_panic(); // panics with `"called `borrowed_or_panic()` on an `Owned` value: \"allocated\""`
+    /// ```
+    #[track_caller]
+    fn borrowed_or_panic(self) -> &'a B
+    where
+        B::Owned: Debug;
+
+    /// Returns the owned value of a `Cow::Owned`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is `Cow::Borrowed`, with a panic message including the borrowed value
+    /// formatted using `Debug`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::borrow::Cow;
+    /// use better_unwrap::BUCow;
+    ///
+    /// let x: Cow<str> = Cow::Owned("allocated".to_string());
+    /// assert_eq!(x.owned_or_panic(), "allocated");
+    /// ```
+    #[track_caller]
+    fn owned_or_panic(self) -> B::Owned
+    where
+        B: Debug;
+}
+
+impl<'a, B: ?Sized + ToOwned> BUCow<'a, B> for Cow<'a, B> {
+    #[track_caller]
+    fn borrowed_or_panic(self) -> &'a B
+    where
+        B::Owned: Debug,
+    {
+        match self {
+            Cow::Borrowed(borrowed) => borrowed,
+            Cow::Owned(owned) => policy::fail(format_args!(
+                "called `borrowed_or_panic()` on an `Owned` value: {owned:?}",
+                owned = Truncated(&owned)
+            )),
+        }
+    }
+
+    #[track_caller]
+    fn owned_or_panic(self) -> B::Owned
+    where
+        B: Debug,
+    {
+        match self {
+            Cow::Borrowed(borrowed) => policy::fail(format_args!(
+                "called `owned_or_panic()` on a `Borrowed` value: {borrowed:?}",
+                borrowed = Truncated(borrowed)
+            )),
+            Cow::Owned(owned) => owned,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_borrowed_or_panic_with_borrowed() {
+        let cow: Cow<str> = Cow::Borrowed("input");
+        assert_eq!(cow.borrowed_or_panic(), "input");
+    }
+
+    #[test]
+    #[should_panic(expected = "called `borrowed_or_panic()` on an `Owned` value: \"input\"")]
+    fn test_borrowed_or_panic_panics_on_owned() {
+        let cow: Cow<str> = Cow::Owned("input".to_string());
+        let _ = cow.borrowed_or_panic();
+    }
+
+    #[test]
+    fn test_owned_or_panic_with_owned() {
+        let cow: Cow<[u8]> = Cow::Owned(vec![1, 2]);
+        assert_eq!(cow.owned_or_panic(), vec![1, 2]);
+    }
+
+    #[test]
+    #[should_panic(expected = "called `owned_or_panic()` on a `Borrowed` value: [1, 2]")]
+    fn test_owned_or_panic_panics_on_borrowed() {
+        let cow: Cow<[u8]> = Cow::Borrowed(&[1, 2]);
+        let _ = cow.owned_or_panic();
+    }
+}
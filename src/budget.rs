@@ -0,0 +1,218 @@
+//! Budgets that escalate repeated soft failures into panics.
+//!
+//! Soft failures — failures defaulted under [`Policy::LogAndDefault`](crate::policy::Policy) and
+//! values discarded by methods such as [`ok_or_log`](crate::BUResult::ok_or_log) — are logged and the
+//! program carries on. With a budget set, they are also counted per call site, and a call site that
+//! exceeds the budget within its window panics instead, so a service that is silently degrading
+//! eventually surfaces the persistent fault.
+//!
+//! # Examples
+//!
+//! ```should_panic
+//! use std::time::Duration;
+//! use better_unwrap::budget::{self, Budget};
+//! use better_unwrap::prelude::*;
+//!
+//! budget::set(Budget::new(2, Duration::from_secs(60)));
+//!
+//! for _ in 0..3 {
+//!     let sample: Result<u32, &str> = Err("sensor offline");
+//!     sample.ok_or_log(); // the third failure panics with `"soft failure budget exceeded: 3 failures within 60s"`
+//! }
+//! ```
+
+use std::collections::{HashMap, VecDeque};
+use std::panic::Location;
+use std::sync::{LazyLock, Mutex, RwLock};
+use std::time::{Duration, Instant};
+
+static BUDGET: RwLock<Option<Budget>> = RwLock::new(None);
+
+static HITS: LazyLock<Mutex<Hits>> = LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// Recent soft failures per call site, oldest first.
+type Hits = HashMap<&'static Location<'static>, VecDeque<Instant>>;
+
+/// How many soft failures a single call site may report within a window before escalating.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Budget {
+    max: usize,
+    window: Duration,
+}
+
+impl Budget {
+    /// Creates a budget allowing `max` soft failures per call site within any `window`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use better_unwrap::budget::Budget;
+    ///
+    /// let budget = Budget::new(10, Duration::from_secs(60));
+    /// assert_eq!(budget.max(), 10);
+    /// assert_eq!(budget.window(), Duration::from_secs(60));
+    /// ```
+    pub const fn new(max: usize, window: Duration) -> Self {
+        Budget { max, window }
+    }
+
+    /// Returns the number of soft failures allowed per call site within the window.
+    pub fn max(&self) -> usize {
+        self.max
+    }
+
+    /// Returns the window soft failures are counted over.
+    pub fn window(&self) -> Duration {
+        self.window
+    }
+}
+
+/// Sets the budget applied to soft failures from now on, replacing any previous budget.
+///
+/// Soft failures counted under a previous budget still count towards the new one.
+pub fn set(budget: Budget) {
+    *BUDGET
+        .write()
+        .unwrap_or_else(|poisoned| poisoned.into_inner()) = Some(budget);
+}
+
+/// Removes the budget, so soft failures are only logged again.
+pub fn clear() {
+    *BUDGET
+        .write()
+        .unwrap_or_else(|poisoned| poisoned.into_inner()) = None;
+    HITS.lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .clear();
+}
+
+/// Returns the budget in effect, if any.
+pub fn current() -> Option<Budget> {
+    *BUDGET
+        .read()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// Counts a soft failure at `location`, returning the budget and the number of failures within its
+/// window if the budget is exceeded.
+pub(crate) fn record(location: &'static Location<'static>) -> Option<(Budget, usize)> {
+    let budget = current()?;
+    let mut hits = HITS.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+    let count = record_in(&mut hits, budget, location, Instant::now());
+    (count > budget.max).then_some((budget, count))
+}
+
+/// Records a failure at `location` and `now`, dropping failures that fell out of the window, and
+/// returns the number of failures within the window.
+fn record_in(
+    hits: &mut Hits,
+    budget: Budget,
+    location: &'static Location<'static>,
+    now: Instant,
+) -> usize {
+    let times = hits.entry(location).or_default();
+    while times
+        .front()
+        .is_some_and(|&time| now.duration_since(time) > budget.window)
+    {
+        times.pop_front();
+    }
+    times.push_back(now);
+    times.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::policy::tests::{in_child, run_in_child};
+    use crate::policy::{self, Policy};
+    use crate::{BUOption, BUResult};
+    use std::panic;
+
+    #[track_caller]
+    fn call_site() -> &'static Location<'static> {
+        Location::caller()
+    }
+
+    #[test]
+    fn test_record_in_counts_per_call_site() {
+        let budget = Budget::new(2, Duration::from_secs(60));
+        let (first, second) = (call_site(), call_site());
+        let mut hits = Hits::new();
+        let now = Instant::now();
+        assert_eq!(record_in(&mut hits, budget, first, now), 1);
+        assert_eq!(record_in(&mut hits, budget, first, now), 2);
+        assert_eq!(record_in(&mut hits, budget, second, now), 1);
+        assert_eq!(record_in(&mut hits, budget, first, now), 3);
+    }
+
+    #[test]
+    fn test_record_in_forgets_failures_outside_window() {
+        let budget = Budget::new(2, Duration::from_secs(60));
+        let location = call_site();
+        let mut hits = Hits::new();
+        let start = Instant::now();
+        assert_eq!(record_in(&mut hits, budget, location, start), 1);
+        assert_eq!(
+            record_in(&mut hits, budget, location, start + Duration::from_secs(30)),
+            2
+        );
+        assert_eq!(
+            record_in(&mut hits, budget, location, start + Duration::from_secs(61)),
+            2
+        );
+        assert_eq!(
+            record_in(
+                &mut hits,
+                budget,
+                location,
+                start + Duration::from_secs(200)
+            ),
+            1
+        );
+    }
+
+    #[test]
+    fn child_escalate_under_abort() {
+        if in_child() {
+            set(Budget::new(1, Duration::from_secs(60)));
+            policy::with(Policy::Abort, || {
+                for _ in 0..2 {
+                    Err::<u32, _>("sensor offline").ok_or_log();
+                }
+            });
+        }
+    }
+
+    #[test]
+    fn test_escalation_follows_the_policy() {
+        let output = run_in_child("budget::tests::child_escalate_under_abort");
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        assert!(!output.status.success());
+        assert!(
+            stderr.contains("soft failure budget exceeded: 2 failures within 60s"),
+            "unexpected stderr: {stderr}"
+        );
+        assert!(!stderr.contains("panicked at"));
+    }
+
+    #[test]
+    fn child_escalate_under_log_and_default() {
+        if in_child() {
+            set(Budget::new(1, Duration::from_secs(60)));
+            let result = panic::catch_unwind(|| {
+                for _ in 0..2 {
+                    policy::with_default(Policy::LogAndDefault, || None::<u32>.or_panic());
+                }
+            });
+            assert!(result.is_err());
+        }
+    }
+
+    #[test]
+    fn test_escalation_is_not_defaulted() {
+        let output = run_in_child("budget::tests::child_escalate_under_log_and_default");
+        assert!(output.status.success(), "{output:?}");
+    }
+}
@@ -0,0 +1,106 @@
+use std::any;
+use std::cell::{Ref, RefCell, RefMut};
+
+use crate::policy;
+
+/// Trait that provides alternatives to `borrow()` and `borrow_mut()` for `RefCell<T>` whose panic
+/// messages say which kind of borrow conflicted.
+pub trait BURefCell<T: ?Sized> {
+    /// Immutably borrows the wrapped value.
+    ///
+    /// Equivalent to `try_borrow().unwrap()`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is currently mutably borrowed.
+    ///
+    /// # Examples
+    ///
+    /// ```should_panic
+    /// use std::cell::RefCell;
+    /// use better_unwrap::BURefCell;
+    ///
+    /// let cache = RefCell::new(vec![1, 2]);
+    /// let _writer = cache.borrow_mut_or_panic();
+    /// cache.borrow_or_panic(); // panics with `"shared borrow of `RefCell<alloc::vec::Vec<i32>>` conflicts with an exclusive borrow that is still held"`
+    /// ```
+    #[track_caller]
+    fn borrow_or_panic(&self) -> Ref<'_, T>;
+
+    /// Mutably borrows the wrapped value.
+    ///
+    /// Equivalent to `try_borrow_mut().unwrap()`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is currently borrowed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::cell::RefCell;
+    /// use better_unwrap::BURefCell;
+    ///
+    /// let cache = RefCell::new(vec![1, 2]);
+    /// cache.borrow_mut_or_panic().push(3);
+    /// assert_eq!(*cache.borrow_or_panic(), [1, 2, 3]);
+    /// ```
+    #[track_caller]
+    fn borrow_mut_or_panic(&self) -> RefMut<'_, T>;
+}
+
+impl<T: ?Sized> BURefCell<T> for RefCell<T> {
+    #[track_caller]
+    fn borrow_or_panic(&self) -> Ref<'_, T> {
+        match self.try_borrow() {
+            Ok(borrowed) => borrowed,
+            Err(_) => policy::fail(format_args!(
+                "shared borrow of `RefCell<{}>` conflicts with an exclusive borrow that is still held",
+                any::type_name::<T>()
+            )),
+        }
+    }
+
+    #[track_caller]
+    fn borrow_mut_or_panic(&self) -> RefMut<'_, T> {
+        match self.try_borrow_mut() {
+            Ok(borrowed) => borrowed,
+            Err(_) => policy::fail(format_args!(
+                "exclusive borrow of `RefCell<{}>` conflicts with a shared or exclusive borrow that is still held",
+                any::type_name::<T>()
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_borrow_or_panic_allows_shared_borrows() {
+        let cell = RefCell::new(5);
+        let (first, second) = (cell.borrow_or_panic(), cell.borrow_or_panic());
+        assert_eq!(*first + *second, 10);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "shared borrow of `RefCell<u32>` conflicts with an exclusive borrow that is still held"
+    )]
+    fn test_borrow_or_panic_panics_while_mutably_borrowed() {
+        let cell = RefCell::new(5u32);
+        let _writer = cell.borrow_mut_or_panic();
+        let _ = cell.borrow_or_panic();
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "exclusive borrow of `RefCell<u32>` conflicts with a shared or exclusive borrow that is still held"
+    )]
+    fn test_borrow_mut_or_panic_panics_while_borrowed() {
+        let cell = RefCell::new(5u32);
+        let _reader = cell.borrow_or_panic();
+        let _ = cell.borrow_mut_or_panic();
+    }
+}
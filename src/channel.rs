@@ -0,0 +1,247 @@
+use std::any;
+use std::sync::mpsc::{Receiver, RecvTimeoutError, Sender, SyncSender};
+use std::time::Duration;
+
+use crate::policy;
+
+/// Trait that provides timeout-aware alternatives to `recv_timeout().unwrap()` for channel receivers.
+///
+/// This trait allows tests and workers to bound how long they wait on a channel and to fail with a
+/// message naming what they were waiting for, instead of hanging forever or reporting a bare
+/// `RecvTimeoutError`.
+pub trait BUReceiver<T> {
+    /// Receives a value from the channel, waiting at most `timeout`.
+    ///
+    /// # Panics
+    ///
+    /// Panics with `"timed out after {timeout:?} waiting on {label}"` if no value arrives in time, or
+    /// with `"channel disconnected while waiting on {label}"` if every sender has been dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::sync::mpsc;
+    /// use std::time::Duration;
+    /// use better_unwrap::{BUReceiver, BUResult};
+    ///
+    /// let (tx, rx) = mpsc::channel();
+    /// tx.send(42).or_panic();
+    /// assert_eq!(rx.recv_timeout_or_panic(Duration::from_secs(1), "worker result"), 42);
+    /// ```
+    ///
+    /// ```should_panic
+    /// use std::sync::mpsc;
+    /// use std::time::Duration;
+    /// use better_unwrap::BUReceiver;
+    ///
+    /// let (_tx, rx) = mpsc::channel::<u32>();
+    /// rx.recv_timeout_or_panic(Duration::from_millis(10), "worker result"); // panics with `"timed out after 10ms waiting on worker result"`
+    /// ```
+    #[track_caller]
+    fn recv_timeout_or_panic(&self, timeout: Duration, label: &str) -> T;
+
+    /// Receives a value from the channel, blocking until one arrives.
+    ///
+    /// Equivalent to `recv().unwrap()`, but the panic message says that the senders hung up.
+    ///
+    /// # Panics
+    ///
+    /// Panics if every sender has been dropped and the channel is empty, with a panic message including
+    /// the channel's item type.
+    ///
+    /// # Examples
+    ///
+    /// ```should_panic
+    /// use std::sync::mpsc;
+    /// use better_unwrap::BUReceiver;
+    ///
+    /// let (tx, rx) = mpsc::channel::<u32>();
+    /// drop(tx);
+    /// rx.recv_or_panic(); // panics with `"called `recv_or_panic()` on a channel of `u32` whose senders all hung up"`
+    /// ```
+    #[track_caller]
+    fn recv_or_panic(&self) -> T;
+}
+
+/// Trait that provides alternatives to `send().unwrap()` for channel senders.
+///
+/// A failed `send()` returns the unsent value, so `send().unwrap()` panics with the `Debug` rendering
+/// of the whole payload; these methods say instead that the receiver hung up.
+pub trait BUSender<T> {
+    /// Sends a value on the channel.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the receiver has been dropped, with a panic message including the channel's item type.
+    ///
+    /// # Examples
+    ///
+    /// ```should_panic
+    /// use std::sync::mpsc;
+    /// use better_unwrap::BUSender;
+    ///
+    /// let (tx, rx) = mpsc::channel::<u32>();
+    /// drop(rx);
+    /// tx.send_or_panic(42); // panics with `"called `send_or_panic()` on a channel of `u32` whose receiver hung up"`
+    /// ```
+    #[track_caller]
+    fn send_or_panic(&self, value: T);
+}
+
+impl<T> BUReceiver<T> for Receiver<T> {
+    #[track_caller]
+    fn recv_timeout_or_panic(&self, timeout: Duration, label: &str) -> T {
+        match self.recv_timeout(timeout) {
+            Ok(value) => value,
+            Err(RecvTimeoutError::Timeout) => policy::fail(format_args!(
+                "timed out after {timeout:?} waiting on {label}"
+            )),
+            Err(RecvTimeoutError::Disconnected) => policy::fail(format_args!(
+                "channel disconnected while waiting on {label}"
+            )),
+        }
+    }
+
+    #[track_caller]
+    fn recv_or_panic(&self) -> T {
+        match self.recv() {
+            Ok(value) => value,
+            Err(_) => senders_hung_up::<T>(),
+        }
+    }
+}
+
+impl<T> BUSender<T> for Sender<T> {
+    #[track_caller]
+    fn send_or_panic(&self, value: T) {
+        if self.send(value).is_err() {
+            receiver_hung_up::<T>()
+        }
+    }
+}
+
+impl<T> BUSender<T> for SyncSender<T> {
+    #[track_caller]
+    fn send_or_panic(&self, value: T) {
+        if self.send(value).is_err() {
+            receiver_hung_up::<T>()
+        }
+    }
+}
+
+#[track_caller]
+fn senders_hung_up<T>() -> ! {
+    policy::fail(format_args!(
+        "called `recv_or_panic()` on a channel of `{}` whose senders all hung up",
+        any::type_name::<T>()
+    ))
+}
+
+#[track_caller]
+fn receiver_hung_up<T>() -> ! {
+    policy::fail(format_args!(
+        "called `send_or_panic()` on a channel of `{}` whose receiver hung up",
+        any::type_name::<T>()
+    ))
+}
+
+#[cfg(feature = "crossbeam-channel")]
+impl<T> BUReceiver<T> for crossbeam_channel::Receiver<T> {
+    #[track_caller]
+    fn recv_timeout_or_panic(&self, timeout: Duration, label: &str) -> T {
+        match self.recv_timeout(timeout) {
+            Ok(value) => value,
+            Err(crossbeam_channel::RecvTimeoutError::Timeout) => policy::fail(format_args!(
+                "timed out after {timeout:?} waiting on {label}"
+            )),
+            Err(crossbeam_channel::RecvTimeoutError::Disconnected) => policy::fail(format_args!(
+                "channel disconnected while waiting on {label}"
+            )),
+        }
+    }
+
+    #[track_caller]
+    fn recv_or_panic(&self) -> T {
+        match self.recv() {
+            Ok(value) => value,
+            Err(_) => senders_hung_up::<T>(),
+        }
+    }
+}
+
+#[cfg(feature = "crossbeam-channel")]
+impl<T> BUSender<T> for crossbeam_channel::Sender<T> {
+    #[track_caller]
+    fn send_or_panic(&self, value: T) {
+        if self.send(value).is_err() {
+            receiver_hung_up::<T>()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::BUResult;
+    use std::sync::mpsc;
+
+    #[test]
+    fn test_recv_timeout_or_panic_with_value() {
+        let (tx, rx) = mpsc::channel();
+        tx.send(42).or_panic();
+        assert_eq!(
+            rx.recv_timeout_or_panic(Duration::from_secs(1), "value"),
+            42
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "timed out after 10ms waiting on worker result")]
+    fn test_recv_timeout_or_panic_panics_on_timeout() {
+        let (_tx, rx) = mpsc::channel::<u32>();
+        let _ = rx.recv_timeout_or_panic(Duration::from_millis(10), "worker result");
+    }
+
+    #[test]
+    #[should_panic(expected = "channel disconnected while waiting on worker result")]
+    fn test_recv_timeout_or_panic_panics_on_disconnect() {
+        let (tx, rx) = mpsc::channel::<u32>();
+        drop(tx);
+        let _ = rx.recv_timeout_or_panic(Duration::from_secs(1), "worker result");
+    }
+
+    #[test]
+    fn test_send_and_recv_or_panic() {
+        let (tx, rx) = mpsc::sync_channel(1);
+        tx.send_or_panic("ping");
+        assert_eq!(rx.recv_or_panic(), "ping");
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "called `recv_or_panic()` on a channel of `alloc::string::String` whose senders all hung up"
+    )]
+    fn test_recv_or_panic_names_item_type() {
+        let (tx, rx) = mpsc::channel::<String>();
+        drop(tx);
+        let _ = rx.recv_or_panic();
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "called `send_or_panic()` on a channel of `u32` whose receiver hung up"
+    )]
+    fn test_send_or_panic_does_not_print_payload() {
+        let (tx, rx) = mpsc::channel::<u32>();
+        drop(rx);
+        tx.send_or_panic(42);
+    }
+
+    #[cfg(feature = "crossbeam-channel")]
+    #[test]
+    #[should_panic(expected = "timed out after 10ms waiting on crossbeam result")]
+    fn test_recv_timeout_or_panic_crossbeam_panics_on_timeout() {
+        let (_tx, rx) = crossbeam_channel::unbounded::<u32>();
+        let _ = rx.recv_timeout_or_panic(Duration::from_millis(10), "crossbeam result");
+    }
+}
@@ -0,0 +1,274 @@
+//! Fallbacks for command-line tools that can ask the user instead of failing.
+
+use std::fmt::Display;
+use std::io::{self, BufRead, IsTerminal, Write};
+use std::process;
+use std::str::FromStr;
+
+use crate::policy;
+
+/// Trait that provides interactive alternatives to `unwrap()` for `Option<T>` in command-line tools.
+///
+/// When the value is missing and stdin is a terminal, the user is prompted for it instead of the
+/// program panicking. When stdin is not a terminal, the failure goes through the configured
+/// [policy] like any other.
+pub trait BUPrompt<T> {
+    /// Returns the contained value, or prompts the user for one on stdin if the value is `None`.
+    ///
+    /// The answer is trimmed of surrounding whitespace.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is `None` and stdin is not a terminal or is closed before an answer is
+    /// given.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use better_unwrap::cli::BUPrompt;
+    ///
+    /// let region = Some("eu-west-1".to_string());
+    /// assert_eq!(region.or_prompt("Enter region: "), "eu-west-1");
+    /// ```
+    #[track_caller]
+    fn or_prompt(self, prompt: &str) -> T
+    where
+        T: From<String>;
+
+    /// Returns the contained value, or prompts the user for one on stdin and parses it if the value
+    /// is `None`.
+    ///
+    /// The prompt is repeated, with the parse error, until the answer parses.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is `None` and stdin is not a terminal or is closed before a valid answer
+    /// is given.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use better_unwrap::cli::BUPrompt;
+    ///
+    /// let port: Option<u16> = None;
+    /// let port = port.or_prompt_parsed("Enter port: "); // asks until the answer is a valid `u16`
+    /// ```
+    #[track_caller]
+    fn or_prompt_parsed(self, prompt: &str) -> T
+    where
+        T: FromStr,
+        T::Err: Display;
+}
+
+impl<T> BUPrompt<T> for Option<T> {
+    #[track_caller]
+    fn or_prompt(self, prompt: &str) -> T
+    where
+        T: From<String>,
+    {
+        match self {
+            Some(value) => value,
+            None => {
+                let stdin = io::stdin();
+                if !stdin.is_terminal() {
+                    policy::fail(format_args!(
+                        "called `or_prompt()` on a `None` value and stdin is not a terminal"
+                    ));
+                }
+                match prompt_for(&mut stdin.lock(), &mut io::stderr(), prompt, |answer| {
+                    Ok::<_, String>(T::from(answer.to_owned()))
+                }) {
+                    Some(value) => value,
+                    None => policy::fail(format_args!(
+                        "called `or_prompt()` on a `None` value and stdin closed before an answer"
+                    )),
+                }
+            }
+        }
+    }
+
+    #[track_caller]
+    fn or_prompt_parsed(self, prompt: &str) -> T
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        match self {
+            Some(value) => value,
+            None => {
+                let stdin = io::stdin();
+                if !stdin.is_terminal() {
+                    policy::fail(format_args!(
+                        "called `or_prompt_parsed()` on a `None` value and stdin is not a terminal"
+                    ));
+                }
+                match prompt_for(&mut stdin.lock(), &mut io::stderr(), prompt, str::parse) {
+                    Some(value) => value,
+                    None => policy::fail(format_args!(
+                        "called `or_prompt_parsed()` on a `None` value and stdin closed before a valid answer"
+                    )),
+                }
+            }
+        }
+    }
+}
+
+/// The exit status used for usage errors, matching clap.
+const USAGE_ERROR_STATUS: i32 = 2;
+
+/// Trait that provides clap-style usage-error exits as alternatives to `unwrap()` for argument values
+/// validated after parsing.
+///
+/// Instead of a panic message and backtrace, the user sees the same kind of error clap prints for
+/// invalid arguments, and the process exits with the same status.
+pub trait BUUsage<T> {
+    /// Returns the contained value, or prints a usage error and exits with status 2.
+    ///
+    /// The error is printed to stderr as `"error: {msg}\n\nFor more information try --help"`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use better_unwrap::cli::BUUsage;
+    ///
+    /// let output: Option<&str> = Some("report.json");
+    /// assert_eq!(output.or_usage_error("--format json requires --output"), "report.json");
+    /// ```
+    #[track_caller]
+    fn or_usage_error(self, msg: &str) -> T;
+}
+
+impl<T> BUUsage<T> for Option<T> {
+    #[track_caller]
+    fn or_usage_error(self, msg: &str) -> T {
+        match self {
+            Some(value) => value,
+            None => usage_error(msg),
+        }
+    }
+}
+
+impl<T, E> BUUsage<T> for Result<T, E> {
+    #[track_caller]
+    fn or_usage_error(self, msg: &str) -> T {
+        match self {
+            Ok(value) => value,
+            Err(_) => usage_error(msg),
+        }
+    }
+}
+
+/// Prints a usage error to stderr and exits with [`USAGE_ERROR_STATUS`].
+#[track_caller]
+fn usage_error(msg: &str) -> ! {
+    let _ = write_usage_error(&mut io::stderr(), msg);
+    process::exit(USAGE_ERROR_STATUS)
+}
+
+fn write_usage_error(output: &mut impl Write, msg: &str) -> io::Result<()> {
+    writeln!(output, "error: {msg}\n\nFor more information try --help")
+}
+
+/// Writes `prompt` and reads answers from `input` until `parse` accepts one, reporting each rejected
+/// answer. Returns `None` if `input` ends or cannot be read first.
+fn prompt_for<T, E: Display>(
+    input: &mut impl BufRead,
+    output: &mut impl Write,
+    prompt: &str,
+    parse: impl Fn(&str) -> Result<T, E>,
+) -> Option<T> {
+    let mut line = String::new();
+    loop {
+        let _ = write!(output, "{prompt}");
+        let _ = output.flush();
+        line.clear();
+        match input.read_line(&mut line) {
+            Ok(0) | Err(_) => return None,
+            Ok(_) => {}
+        }
+        match parse(line.trim()) {
+            Ok(value) => return Some(value),
+            Err(err) => {
+                let _ = writeln!(output, "invalid value {:?}: {err}", line.trim());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::BUResult;
+
+    #[test]
+    fn test_or_prompt_with_some() {
+        let region = Some("eu-west-1".to_string());
+        assert_eq!(region.or_prompt("Enter region: "), "eu-west-1");
+    }
+
+    #[test]
+    fn test_or_prompt_parsed_with_some() {
+        assert_eq!(Some(8080u16).or_prompt_parsed("Enter port: "), 8080);
+    }
+
+    #[test]
+    fn test_or_usage_error_with_some() {
+        assert_eq!(Some(3).or_usage_error("--retries is required"), 3);
+    }
+
+    #[test]
+    fn test_or_usage_error_with_ok() {
+        let result: Result<u32, &str> = Ok(3);
+        assert_eq!(result.or_usage_error("--retries is required"), 3);
+    }
+
+    #[test]
+    fn test_write_usage_error_matches_clap() {
+        let mut output = Vec::new();
+        write_usage_error(&mut output, "--format json requires --output").or_panic();
+        assert_eq!(
+            String::from_utf8_lossy(&output),
+            "error: --format json requires --output\n\nFor more information try --help\n"
+        );
+    }
+
+    #[test]
+    fn test_prompt_for_trims_answer() {
+        let mut output = Vec::new();
+        let answer = prompt_for(
+            &mut "  eu-west-1 \n".as_bytes(),
+            &mut output,
+            "Enter region: ",
+            |s| Ok::<_, String>(s.to_owned()),
+        );
+        assert_eq!(answer.as_deref(), Some("eu-west-1"));
+        assert_eq!(output, b"Enter region: ");
+    }
+
+    #[test]
+    fn test_prompt_for_reprompts_until_parsed() {
+        let mut output = Vec::new();
+        let answer: Option<u16> = prompt_for(
+            &mut "http\n8080\n".as_bytes(),
+            &mut output,
+            "Enter port: ",
+            str::parse,
+        );
+        assert_eq!(answer, Some(8080));
+        assert_eq!(
+            String::from_utf8_lossy(&output),
+            "Enter port: invalid value \"http\": invalid digit found in string\nEnter port: "
+        );
+    }
+
+    #[test]
+    fn test_prompt_for_returns_none_at_end_of_input() {
+        let answer: Option<u16> = prompt_for(
+            &mut "".as_bytes(),
+            &mut Vec::new(),
+            "Enter port: ",
+            str::parse,
+        );
+        assert_eq!(answer, None);
+    }
+}
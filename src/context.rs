@@ -0,0 +1,266 @@
+//! Lightweight error context: attach a description of what was being done to an error as it
+//! propagates, so that a later `or_panic()` reads like a story instead of a bare error value.
+//!
+//! # Examples
+//!
+//! ```should_panic
+//! use std::fs;
+//! use better_unwrap::prelude::*;
+//! use better_unwrap::{BUContext, BUError};
+//!
+//! fn load_config(path: &str) -> Result<String, BUError> {
+//!     fs::read_to_string(path).with_context(|| format!("reading {path}"))
+//! }
+//!
+//! fn start() -> Result<(), BUError> {
+//!     load_config("/nonexistent/app.toml").context("loading the configuration")?;
+//!     Ok(())
+//! }
+//!
+//! start().or_panic(); // panics with `"called `or_panic()` on an `Err` value: loading the configuration\ncaused by: reading /nonexistent/app.toml\ncaused by: No such file or directory (os error 2)"`
+//! ```
+
+use std::error::Error;
+use std::fmt::{self, Display};
+
+/// An error together with the stack of contexts attached to it while it propagated.
+///
+/// Created with [`BUContext::context`] or [`BUContext::with_context`], or from any error with `?`.
+/// It displays as its outermost context, and its `Debug` rendering, used by `or_panic()`, lists every
+/// context from the outermost in, followed by the original error and its sources, one `caused by:`
+/// line per level.
+///
+/// Like `anyhow::Error`, it does not implement [`Error`] itself, so that any error can be converted
+/// into it.
+pub struct BUError {
+    /// The attached contexts, innermost first.
+    contexts: Vec<String>,
+    error: Option<Box<dyn Error + Send + Sync + 'static>>,
+}
+
+impl BUError {
+    /// Returns the attached contexts, outermost first.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use better_unwrap::BUContext;
+    ///
+    /// let port: Option<u16> = None;
+    /// let error = port
+    ///     .context("reading the port")
+    ///     .context("starting the server")
+    ///     .unwrap_err();
+    /// assert_eq!(
+    ///     error.contexts().collect::<Vec<_>>(),
+    ///     ["starting the server", "reading the port"]
+    /// );
+    /// ```
+    pub fn contexts(&self) -> impl Iterator<Item = &str> {
+        self.contexts.iter().rev().map(String::as_str)
+    }
+
+    /// Returns the original error, or `None` if the error started as a `None` value.
+    pub fn error(&self) -> Option<&(dyn Error + Send + Sync + 'static)> {
+        self.error.as_deref()
+    }
+
+    /// Attaches `context` as the new outermost context.
+    fn push(mut self, context: String) -> Self {
+        self.contexts.push(context);
+        self
+    }
+}
+
+impl<E> From<E> for BUError
+where
+    E: Error + Send + Sync + 'static,
+{
+    fn from(error: E) -> Self {
+        BUError {
+            contexts: Vec::new(),
+            error: Some(Box::new(error)),
+        }
+    }
+}
+
+impl fmt::Display for BUError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (self.contexts.last(), &self.error) {
+            (Some(context), _) => f.write_str(context),
+            (None, Some(error)) => error.fmt(f),
+            (None, None) => f.write_str(NONE_VALUE),
+        }
+    }
+}
+
+impl fmt::Debug for BUError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let contexts = self
+            .contexts
+            .iter()
+            .rev()
+            .map(|context| context as &dyn Display);
+        let errors = std::iter::successors(
+            self.error
+                .as_deref()
+                .map(|error| error as &(dyn Error + 'static)),
+            |&error| error.source(),
+        )
+        .map(|error| error as &dyn Display);
+        let none = self.error.is_none().then_some(&NONE_VALUE as &dyn Display);
+        for (depth, level) in contexts.chain(errors).chain(none).enumerate() {
+            if depth > 0 {
+                f.write_str("\ncaused by: ")?;
+            }
+            write!(f, "{level}")?;
+        }
+        Ok(())
+    }
+}
+
+/// How the cause of a [`BUError`] created from a `None` value is rendered.
+const NONE_VALUE: &str = "found a `None` value";
+
+/// Trait that provides `context()` and `with_context()` for `Option` and `Result`, converting them
+/// into a `Result<T, BUError>`.
+///
+/// Not part of the glob preludes: its method names match those of `anyhow::Context` and
+/// `eyre::ContextCompat`, so importing both would make `.context(..)` calls ambiguous. Import it
+/// explicitly.
+pub trait BUContext<T> {
+    /// Converts the value into a `Result`, attaching `context` to the error if there is one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use better_unwrap::BUContext;
+    ///
+    /// let port: Result<u16, std::num::ParseIntError> = "80a".parse();
+    /// let error = port.context("parsing the port").unwrap_err();
+    /// assert_eq!(error.to_string(), "parsing the port");
+    /// ```
+    fn context<C: Display>(self, context: C) -> Result<T, BUError>;
+
+    /// Converts the value into a `Result`, attaching the context returned by `f` to the error if
+    /// there is one.
+    ///
+    /// `f` is only called on failure.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use better_unwrap::BUContext;
+    ///
+    /// let user = 42;
+    /// let name: Option<&str> = None;
+    /// let error = name.with_context(|| format!("looking up user {user}")).unwrap_err();
+    /// assert_eq!(format!("{error:?}"), "looking up user 42\ncaused by: found a `None` value");
+    /// ```
+    fn with_context<C, F>(self, f: F) -> Result<T, BUError>
+    where
+        C: Display,
+        F: FnOnce() -> C;
+}
+
+impl<T> BUContext<T> for Option<T> {
+    fn context<C: Display>(self, context: C) -> Result<T, BUError> {
+        self.with_context(|| context)
+    }
+
+    fn with_context<C, F>(self, f: F) -> Result<T, BUError>
+    where
+        C: Display,
+        F: FnOnce() -> C,
+    {
+        self.ok_or_else(|| BUError {
+            contexts: vec![f().to_string()],
+            error: None,
+        })
+    }
+}
+
+impl<T, E> BUContext<T> for Result<T, E>
+where
+    E: Error + Send + Sync + 'static,
+{
+    fn context<C: Display>(self, context: C) -> Result<T, BUError> {
+        self.with_context(|| context)
+    }
+
+    fn with_context<C, F>(self, f: F) -> Result<T, BUError>
+    where
+        C: Display,
+        F: FnOnce() -> C,
+    {
+        self.map_err(|error| BUError::from(error).push(f().to_string()))
+    }
+}
+
+impl<T> BUContext<T> for Result<T, BUError> {
+    fn context<C: Display>(self, context: C) -> Result<T, BUError> {
+        self.with_context(|| context)
+    }
+
+    fn with_context<C, F>(self, f: F) -> Result<T, BUError>
+    where
+        C: Display,
+        F: FnOnce() -> C,
+    {
+        self.map_err(|error| error.push(f().to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{BUOption, BUResult};
+    use std::io;
+
+    fn read() -> Result<String, BUError> {
+        let error = io::Error::new(io::ErrorKind::NotFound, "no such file");
+        Err(error).context("reading app.toml")
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "called `or_panic()` on an `Err` value: loading the configuration\ncaused by: reading app.toml\ncaused by: no such file"
+    )]
+    fn test_or_panic_tells_the_story() {
+        read().context("loading the configuration").or_panic();
+    }
+
+    #[test]
+    fn test_question_mark_converts_errors() {
+        fn parse() -> Result<u16, BUError> {
+            Ok("80a".parse::<u16>()?)
+        }
+        let error = parse().or_panic_err();
+        assert_eq!(error.contexts().count(), 0);
+        assert_eq!(error.to_string(), "invalid digit found in string");
+        assert_eq!(format!("{error:?}"), "invalid digit found in string");
+    }
+
+    #[test]
+    fn test_error_keeps_original() {
+        let error = read().or_panic_err();
+        let original = error.error().or_panic().downcast_ref::<io::Error>();
+        assert_eq!(original.or_panic().kind(), io::ErrorKind::NotFound);
+    }
+
+    #[test]
+    fn test_none_context() {
+        let error = None::<u32>.context("reading the port").or_panic_err();
+        assert!(error.error().is_none());
+        assert_eq!(
+            format!("{error:?}"),
+            "reading the port\ncaused by: found a `None` value"
+        );
+    }
+
+    #[test]
+    fn test_with_context_is_lazy_on_success() {
+        let value = Some(3).with_context(|| -> &str { unreachable!() });
+        assert_eq!(value.or_panic(), 3);
+    }
+}
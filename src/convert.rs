@@ -0,0 +1,80 @@
+use std::any;
+use std::fmt::{Debug, Display};
+
+use crate::message::Truncated;
+use crate::policy;
+
+/// Trait that provides an alternative to `try_into().unwrap()` for fallible conversions.
+///
+/// Unlike unwrapping the `Result` returned by `try_into()`, the panic message includes the value that
+/// failed to convert and the source and target types.
+pub trait BUTryInto: Sized {
+    /// Converts the value into a `U`.
+    ///
+    /// Equivalent to `try_into::<U>().unwrap()`. The value is cloned before converting so that it can
+    /// be included in the panic message, which is free for the integer types this is mostly used with.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the conversion fails, with a panic message including the value formatted using
+    /// `Debug`, the source and target type names, and the conversion error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use better_unwrap::BUTryInto;
+    ///
+    /// let len: u8 = 200usize.try_into_or_panic();
+    /// assert_eq!(len, 200);
+    /// ```
+    ///
+    /// ```should_panic
+    /// use better_unwrap::BUTryInto;
+    ///
+    /// 300i32.try_into_or_panic::<u8>(); // panics with `"failed to convert 300 from `i32` to `u8`: out of range integral type conversion attempted"`
+    /// ```
+    #[track_caller]
+    fn try_into_or_panic<U>(self) -> U
+    where
+        Self: TryInto<U>,
+        <Self as TryInto<U>>::Error: Display;
+}
+
+impl<T: Clone + Debug> BUTryInto for T {
+    #[track_caller]
+    fn try_into_or_panic<U>(self) -> U
+    where
+        Self: TryInto<U>,
+        <Self as TryInto<U>>::Error: Display,
+    {
+        match self.clone().try_into() {
+            Ok(value) => value,
+            Err(error) => policy::fail(format_args!(
+                "failed to convert {value:?} from `{}` to `{}`: {error}",
+                any::type_name::<T>(),
+                any::type_name::<U>(),
+                value = Truncated(&self)
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_try_into_or_panic_in_range() {
+        assert_eq!(200usize.try_into_or_panic::<u8>(), 200);
+        assert_eq!((-5i64).try_into_or_panic::<i8>(), -5);
+        assert_eq!(7u8.try_into_or_panic::<u32>(), 7);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "failed to convert -1 from `i32` to `usize`: out of range integral type conversion attempted"
+    )]
+    fn test_try_into_or_panic_panics_with_value_and_types() {
+        let _ = (-1i32).try_into_or_panic::<usize>();
+    }
+}
@@ -0,0 +1,107 @@
+//! Line diffs of `Debug` renderings, for comparing expected and actual values in panic messages.
+
+use alloc::format;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt::{self, Debug};
+
+/// Displays a line diff between the pretty `Debug` renderings of an expected and an actual value.
+///
+/// Lines only in the expected value are prefixed with `-`, lines only in the actual value with `+`,
+/// and common lines with a space, under a legend naming the two sides.
+pub(crate) struct Diff<'a, U: ?Sized, T: ?Sized> {
+    pub(crate) expected: &'a U,
+    pub(crate) actual: &'a T,
+}
+
+impl<U: Debug + ?Sized, T: Debug + ?Sized> fmt::Display for Diff<'_, U, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let expected = format!("{:#?}", self.expected);
+        let actual = format!("{:#?}", self.actual);
+        let expected: Vec<&str> = expected.lines().collect();
+        let actual: Vec<&str> = actual.lines().collect();
+        f.write_str("- expected\n+ actual\n")?;
+        for (marker, line) in lines(&expected, &actual) {
+            write!(f, "\n{marker} {line}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Returns the lines of the diff between `expected` and `actual` with their markers, following a
+/// longest common subsequence of the two.
+fn lines<'a>(expected: &[&'a str], actual: &[&'a str]) -> Vec<(char, &'a str)> {
+    // common[i][j] is the length of the longest common subsequence of expected[i..] and actual[j..].
+    let mut common = vec![vec![0usize; actual.len() + 1]; expected.len() + 1];
+    for i in (0..expected.len()).rev() {
+        for j in (0..actual.len()).rev() {
+            common[i][j] = if expected[i] == actual[j] {
+                common[i + 1][j + 1] + 1
+            } else {
+                common[i + 1][j].max(common[i][j + 1])
+            };
+        }
+    }
+    let mut lines = Vec::with_capacity(expected.len() + actual.len());
+    let (mut i, mut j) = (0, 0);
+    while i < expected.len() && j < actual.len() {
+        if expected[i] == actual[j] {
+            lines.push((' ', expected[i]));
+            i += 1;
+            j += 1;
+        } else if common[i + 1][j] >= common[i][j + 1] {
+            lines.push(('-', expected[i]));
+            i += 1;
+        } else {
+            lines.push(('+', actual[j]));
+            j += 1;
+        }
+    }
+    lines.extend(expected[i..].iter().map(|&line| ('-', line)));
+    lines.extend(actual[j..].iter().map(|&line| ('+', line)));
+    lines
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+
+    #[derive(Debug)]
+    #[allow(dead_code)]
+    struct User {
+        id: u32,
+        name: &'static str,
+        admin: bool,
+    }
+
+    #[test]
+    fn test_diff_marks_changed_lines() {
+        let expected = User {
+            id: 1,
+            name: "ada",
+            admin: false,
+        };
+        let actual = User {
+            id: 1,
+            name: "grace",
+            admin: false,
+        };
+        let diff = Diff {
+            expected: &expected,
+            actual: &actual,
+        };
+        assert_eq!(
+            diff.to_string(),
+            "- expected\n+ actual\n\n  User {\n      id: 1,\n-     name: \"ada\",\n+     name: \"grace\",\n      admin: false,\n  }"
+        );
+    }
+
+    #[test]
+    fn test_lines_handles_insertions_and_removals() {
+        assert_eq!(
+            lines(&["a", "b", "c"], &["b", "c", "d"]),
+            [('-', "a"), (' ', "b"), (' ', "c"), ('+', "d")]
+        );
+        assert_eq!(lines(&[], &["a"]), [('+', "a")]);
+    }
+}
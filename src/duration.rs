@@ -0,0 +1,83 @@
+//! Helpers for parsing human-readable durations such as `"30s"` or `"1h 15m"`, built on `humantime`.
+//!
+//! Available with the `humantime` feature.
+
+use std::time::Duration;
+
+use crate::message::Truncated;
+use crate::policy;
+
+/// Parses a human-readable duration.
+///
+/// # Panics
+///
+/// Panics if `input` is not a valid duration, with a panic message including the input and the parse
+/// error.
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+/// use better_unwrap::duration::parse_duration_or_panic;
+///
+/// assert_eq!(parse_duration_or_panic("1m 30s"), Duration::from_secs(90));
+/// ```
+///
+/// ```should_panic
+/// use better_unwrap::duration::parse_duration_or_panic;
+///
+/// parse_duration_or_panic("30 parsecs"); // panics with `"failed to parse duration \"30 parsecs\": ..."`
+/// ```
+#[track_caller]
+pub fn parse_duration_or_panic(input: &str) -> Duration {
+    match humantime::parse_duration(input) {
+        Ok(duration) => duration,
+        Err(error) => policy::fail(format_args!(
+            "failed to parse duration {input:?}: {error}",
+            input = Truncated(input)
+        )),
+    }
+}
+
+/// Parses a human-readable duration, returning `default` if `input` is not a valid duration.
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+/// use better_unwrap::duration::parse_duration_or;
+///
+/// assert_eq!(parse_duration_or("soon", Duration::from_secs(5)), Duration::from_secs(5));
+/// ```
+pub fn parse_duration_or(input: &str, default: Duration) -> Duration {
+    humantime::parse_duration(input).unwrap_or(default)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_duration_or_panic_with_valid_input() {
+        assert_eq!(parse_duration_or_panic("250ms"), Duration::from_millis(250));
+    }
+
+    #[test]
+    #[should_panic(expected = "failed to parse duration \"30 parsecs\"")]
+    fn test_parse_duration_or_panic_echoes_input() {
+        let _ = parse_duration_or_panic("30 parsecs");
+    }
+
+    #[test]
+    fn test_parse_duration_or_with_valid_input() {
+        assert_eq!(
+            parse_duration_or("2s", Duration::ZERO),
+            Duration::from_secs(2)
+        );
+    }
+
+    #[test]
+    fn test_parse_duration_or_with_invalid_input() {
+        assert_eq!(parse_duration_or("", Duration::ZERO), Duration::ZERO);
+    }
+}
@@ -0,0 +1,254 @@
+use std::fmt;
+use std::panic::Location;
+
+#[cfg(not(all(feature = "fuzzing", not(test))))]
+use crate::payload::{self, BUPanicInfo};
+use crate::report::{self, Report};
+use crate::{budget, policy};
+
+/// Writes a diagnostic about a discarded or defaulted value to stderr, tagged with the caller location.
+///
+/// Under the `log` feature the diagnostic is logged with `log::warn!` instead.
+///
+/// Fails instead, according to the [policy](crate::policy) in effect, if the call site has
+/// exceeded the [budget](crate::budget) in effect.
+#[track_caller]
+pub(crate) fn log(location: &'static Location<'static>, message: fmt::Arguments<'_>) {
+    if let Some((budget, count)) = budget::record(location) {
+        policy::escalate(format_args!(
+            "soft failure budget exceeded: {count} failures within {:?}: {message}",
+            budget.window()
+        ));
+    }
+    let line = report::redact(format_args!("{location}: {message}"));
+    #[cfg(feature = "log")]
+    ::log::warn!(target: "better_unwrap", "{line}");
+    #[cfg(not(feature = "log"))]
+    eprintln!("[better_unwrap] {line}");
+}
+
+/// Logs that the `panic_or_log*` method `method` returned its fallback because it was called on
+/// `value`, and records the fallback like those of the other `panic_or*` methods.
+#[track_caller]
+pub(crate) fn log_fallback(method: &'static str, value: fmt::Arguments<'_>) {
+    log(
+        Location::caller(),
+        format_args!("called `{method}()` on {value}, using the fallback"),
+    );
+    #[cfg(any(feature = "metrics", feature = "tracing"))]
+    fallback(method);
+}
+
+/// Prints a failure to stderr as `"error: {message}"` and exits the process with `code`.
+pub(crate) fn exit(code: i32, message: fmt::Arguments<'_>) -> ! {
+    eprintln!("error: {}", report::redact(message));
+    std::process::exit(code)
+}
+
+/// Prints a rendered failure to stderr, tagged with the caller location, and aborts the process
+/// without unwinding.
+#[track_caller]
+pub(crate) fn abort(report: &Report<'_>) -> ! {
+    eprintln!("[better_unwrap] {}: {report}", Location::caller());
+    std::process::abort()
+}
+
+/// Panics with a rendered failure.
+///
+/// Under the `fuzzing` feature this prints a single-line reproducer to stderr and aborts the process
+/// instead, which fuzzing harnesses detect far more reliably than an unwinding panic.
+#[track_caller]
+pub(crate) fn panic(report: &Report<'_>) -> ! {
+    crate::hook::mark_crate_panic();
+    #[cfg(all(feature = "fuzzing", not(test)))]
+    {
+        eprintln!("{}", reproducer(Location::caller(), report));
+        std::process::abort()
+    }
+    #[cfg(not(all(feature = "fuzzing", not(test))))]
+    {
+        if payload::is_structured() {
+            std::panic::panic_any(BUPanicInfo::capture(report, Location::caller()))
+        }
+        panic!("{report}")
+    }
+}
+
+/// Emits a `tracing` error event for a failure, before the policy handles it.
+#[cfg(feature = "tracing")]
+pub(crate) fn trace_failure(location: &'static Location<'static>, report: &Report<'_>) {
+    tracing::error!(
+        target: "better_unwrap",
+        file = location.file(),
+        line = location.line(),
+        column = location.column(),
+        "{report}"
+    );
+}
+
+/// Name of the counter incremented, per method and caller location, when a `panic_or*` method takes
+/// its fallback.
+#[cfg(feature = "metrics")]
+const FALLBACK_COUNTER: &str = "better_unwrap_fallbacks_total";
+
+/// Records that a `panic_or*` method returned its fallback: a `tracing` warning event under the
+/// `tracing` feature, and an increment of [`FALLBACK_COUNTER`] under the `metrics` feature.
+#[cfg(any(feature = "metrics", feature = "tracing"))]
+#[track_caller]
+pub(crate) fn fallback(method: &'static str) {
+    let location = Location::caller();
+    #[cfg(feature = "metrics")]
+    metrics::counter!(
+        FALLBACK_COUNTER,
+        "method" => method,
+        "location" => location.to_string()
+    )
+    .increment(1);
+    #[cfg(feature = "tracing")]
+    tracing::warn!(
+        target: "better_unwrap",
+        method,
+        file = location.file(),
+        line = location.line(),
+        column = location.column(),
+        "called `{method}()` and took the fallback"
+    );
+}
+
+/// Renders a failure on one line: the location followed by the report with newlines escaped.
+#[cfg(feature = "fuzzing")]
+fn reproducer(location: &Location<'_>, report: &Report<'_>) -> String {
+    format!(
+        "[better_unwrap] {location}: {}",
+        report.to_string().escape_debug()
+    )
+}
+
+#[cfg(all(
+    test,
+    any(feature = "fuzzing", feature = "metrics", feature = "tracing")
+))]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "fuzzing")]
+    #[test]
+    fn test_reproducer_is_single_line() {
+        let location = Location::caller();
+        let report = crate::report::with_module_path("app::parser", || {
+            Report::capture(format_args!(
+                "called `or_panic()` on an `Err` value: \"bad\\ninput\""
+            ))
+        });
+        assert_eq!(
+            reproducer(location, &report),
+            format!(
+                "[better_unwrap] {location}: called `or_panic()` on an `Err` value: \\\"bad\\\\ninput\\\"\\n\\nmodule: app::parser"
+            )
+        );
+    }
+
+    /// Runs `f` under a `tracing` subscriber and returns the events it emitted, one per line.
+    #[cfg(feature = "tracing")]
+    fn capture_events(f: impl FnOnce()) -> String {
+        use std::io;
+        use std::sync::{Arc, Mutex};
+
+        use crate::BULockResult;
+
+        struct Buffer(Arc<Mutex<Vec<u8>>>);
+
+        impl io::Write for Buffer {
+            fn write(&mut self, bytes: &[u8]) -> io::Result<usize> {
+                self.0.lock().lock_or_panic().extend_from_slice(bytes);
+                Ok(bytes.len())
+            }
+
+            fn flush(&mut self) -> io::Result<()> {
+                Ok(())
+            }
+        }
+
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let writer = Arc::clone(&events);
+        let subscriber = tracing_subscriber::fmt()
+            .with_writer(move || Buffer(Arc::clone(&writer)))
+            .with_ansi(false)
+            .without_time()
+            .finish();
+        tracing::subscriber::with_default(subscriber, f);
+        String::from_utf8_lossy(&events.lock().lock_or_panic()).into_owned()
+    }
+
+    #[cfg(feature = "tracing")]
+    #[test]
+    fn test_failure_emits_error_event() {
+        use crate::BUOption;
+
+        let events = capture_events(|| {
+            let _ = std::panic::catch_unwind(|| None::<u32>.or_panic());
+        });
+        assert!(events.starts_with(
+            "ERROR better_unwrap: called `or_panic()` on a `None` value file=\"src/emit.rs\""
+        ));
+    }
+
+    #[cfg(all(feature = "tracing", not(feature = "test-strict")))]
+    #[test]
+    fn test_fallback_emits_warning_event() {
+        use crate::BUResult;
+
+        let line = line!() + 2;
+        let events = capture_events(|| {
+            assert_eq!(Err::<u32, &str>("offline").panic_or(7), 7);
+        });
+        assert_eq!(
+            events,
+            format!(
+                " WARN better_unwrap: called `panic_or()` and took the fallback method=\"panic_or\" file=\"src/emit.rs\" line={line} column=52\n"
+            )
+        );
+    }
+
+    #[cfg(all(feature = "metrics", not(feature = "test-strict")))]
+    #[test]
+    fn test_fallback_increments_counter_per_location() {
+        use metrics_util::debugging::{DebugValue, DebuggingRecorder};
+
+        use crate::BUOption;
+
+        let recorder = DebuggingRecorder::new();
+        let snapshotter = recorder.snapshotter();
+        let line = line!() + 3;
+        metrics::with_local_recorder(&recorder, || {
+            for _ in 0..2 {
+                assert_eq!(None.panic_or(7), 7);
+            }
+            assert_eq!(Some(1).panic_or_default(), 1);
+        });
+        let counters: Vec<_> = snapshotter
+            .snapshot()
+            .into_vec()
+            .into_iter()
+            .map(|(key, _, _, value)| {
+                let labels: Vec<_> = key
+                    .key()
+                    .labels()
+                    .map(|label| format!("{}={}", label.key(), label.value()))
+                    .collect();
+                (key.key().name().to_owned(), labels, value)
+            })
+            .collect();
+        assert_eq!(
+            counters,
+            [(
+                FALLBACK_COUNTER.to_owned(),
+                vec![
+                    "method=panic_or".to_owned(),
+                    format!("location={}:{line}:33", file!())
+                ],
+                DebugValue::Counter(2)
+            )]
+        );
+    }
+}
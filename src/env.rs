@@ -0,0 +1,244 @@
+//! Helpers for reading the process environment: command-line arguments, environment variables, and
+//! well-known directories.
+
+use std::any;
+use std::env;
+use std::env::VarError;
+use std::ffi::OsString;
+use std::fmt::Display;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use crate::message::Truncated;
+use crate::policy;
+
+/// Returns the `n`th command-line argument, where argument `0` is usually the program name.
+///
+/// # Panics
+///
+/// Panics if there are fewer than `n + 1` arguments or the argument is not valid unicode, with a panic
+/// message including the full argument list.
+///
+/// # Examples
+///
+/// ```
+/// use better_unwrap::env::arg_or_panic;
+///
+/// let program = arg_or_panic(0);
+/// assert!(!program.is_empty());
+/// ```
+///
+/// ```should_panic
+/// use better_unwrap::env::arg_or_panic;
+///
+/// arg_or_panic(1000); // panics with `"missing command-line argument 1000 (argv: [...])"`
+/// ```
+#[track_caller]
+pub fn arg_or_panic(n: usize) -> String {
+    let args: Vec<OsString> = env::args_os().collect();
+    match args.get(n).map(|arg| arg.to_str()) {
+        Some(Some(arg)) => arg.to_owned(),
+        Some(None) => policy::fail(format_args!(
+            "command-line argument {n} is not valid unicode (argv: {args:?})",
+            args = Truncated(&args)
+        )),
+        None => policy::fail(format_args!(
+            "missing command-line argument {n} (argv: {args:?})",
+            args = Truncated(&args)
+        )),
+    }
+}
+
+/// Returns the value of the environment variable `var`.
+///
+/// Equivalent to `std::env::var(var).unwrap()`.
+///
+/// # Panics
+///
+/// Panics if the variable is not set or its value is not valid unicode, with a panic message naming
+/// the variable.
+///
+/// # Examples
+///
+/// ```should_panic
+/// use better_unwrap::env::env_or_panic;
+///
+/// let url = env_or_panic("DATABASE_URL"); // panics with `"environment variable `DATABASE_URL` is not set"`
+/// ```
+#[track_caller]
+pub fn env_or_panic(var: &str) -> String {
+    match env::var(var) {
+        Ok(value) => value,
+        Err(VarError::NotPresent) => {
+            policy::fail(format_args!("environment variable `{var}` is not set"))
+        }
+        Err(VarError::NotUnicode(value)) => policy::fail(format_args!(
+            "environment variable `{var}` is not valid unicode: {value:?}",
+            value = Truncated(&value)
+        )),
+    }
+}
+
+/// Returns the value of the environment variable `var`, or `default` if it is not set.
+///
+/// # Panics
+///
+/// Panics if the variable is set but its value is not valid unicode, with a panic message naming the
+/// variable.
+///
+/// # Examples
+///
+/// ```
+/// use better_unwrap::env::env_or;
+///
+/// let level = env_or("BETTER_UNWRAP_EXAMPLE_LOG_LEVEL", "info");
+/// assert_eq!(level, "info");
+/// ```
+#[track_caller]
+pub fn env_or(var: &str, default: &str) -> String {
+    match env::var(var) {
+        Ok(value) => value,
+        Err(VarError::NotPresent) => default.to_owned(),
+        Err(VarError::NotUnicode(value)) => policy::fail(format_args!(
+            "environment variable `{var}` is not valid unicode: {value:?}",
+            value = Truncated(&value)
+        )),
+    }
+}
+
+/// Returns the value of the environment variable `var` parsed as a `T`.
+///
+/// # Panics
+///
+/// Panics if the variable is not set, its value is not valid unicode, or its value does not parse,
+/// with a panic message naming the variable and, for parse failures, the value, target type, and
+/// parse error.
+///
+/// # Examples
+///
+/// ```should_panic
+/// use better_unwrap::env::env_parse_or_panic;
+///
+/// let port: u16 = env_parse_or_panic("PORT"); // panics with `"environment variable `PORT` is not set"`
+/// ```
+#[track_caller]
+pub fn env_parse_or_panic<T>(var: &str) -> T
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    let value = env_or_panic(var);
+    match value.parse() {
+        Ok(parsed) => parsed,
+        Err(error) => policy::fail(format_args!(
+            "failed to parse environment variable `{var}` = {value:?} as `{}`: {error}",
+            any::type_name::<T>(),
+            value = Truncated(&value)
+        )),
+    }
+}
+
+/// Returns the current working directory.
+///
+/// Equivalent to `std::env::current_dir().or_panic()`.
+///
+/// # Panics
+///
+/// Panics if the current directory cannot be determined, for example because it was removed, with a
+/// panic message including the underlying error.
+///
+/// # Examples
+///
+/// ```
+/// use better_unwrap::env::current_dir_or_panic;
+///
+/// let cwd = current_dir_or_panic();
+/// assert!(cwd.is_absolute());
+/// ```
+#[track_caller]
+pub fn current_dir_or_panic() -> PathBuf {
+    match env::current_dir() {
+        Ok(dir) => dir,
+        Err(error) => policy::fail(format_args!(
+            "failed to determine the current directory: {error}"
+        )),
+    }
+}
+
+/// Returns the home directory of the current user.
+///
+/// Equivalent to `std::env::home_dir().unwrap()`.
+///
+/// # Panics
+///
+/// Panics if the home directory cannot be determined.
+///
+/// # Examples
+///
+/// ```no_run
+/// use better_unwrap::env::home_dir_or_panic;
+///
+/// let config = home_dir_or_panic().join(".config");
+/// ```
+#[track_caller]
+pub fn home_dir_or_panic() -> PathBuf {
+    match env::home_dir() {
+        Some(dir) => dir,
+        None => policy::fail(format_args!(
+            "failed to determine the home directory of the current user"
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{BUOption, BUResult};
+
+    #[test]
+    fn test_arg_or_panic_with_program_name() {
+        assert_eq!(arg_or_panic(0), env::args().next().or_panic());
+    }
+
+    #[test]
+    #[should_panic(expected = "missing command-line argument 1000 (argv: [")]
+    fn test_arg_or_panic_panics_with_argv() {
+        let _ = arg_or_panic(1000);
+    }
+
+    #[test]
+    fn test_env_or_panic_with_set_variable() {
+        assert_eq!(env_or_panic("CARGO_PKG_NAME"), env!("CARGO_PKG_NAME"));
+    }
+
+    #[test]
+    #[should_panic(expected = "environment variable `BETTER_UNWRAP_TEST_UNSET` is not set")]
+    fn test_env_or_panic_panics_with_variable_name() {
+        let _ = env_or_panic("BETTER_UNWRAP_TEST_UNSET");
+    }
+
+    #[test]
+    fn test_env_or() {
+        assert_eq!(env_or("BETTER_UNWRAP_TEST_UNSET", "info"), "info");
+        assert_eq!(env_or("CARGO_PKG_NAME", "info"), env!("CARGO_PKG_NAME"));
+    }
+
+    #[test]
+    fn test_env_parse_or_panic() {
+        let major: u32 = env_parse_or_panic("CARGO_PKG_VERSION_MAJOR");
+        assert_eq!(major.to_string(), env!("CARGO_PKG_VERSION_MAJOR"));
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "failed to parse environment variable `CARGO_PKG_NAME` = \"better_unwrap\" as `u16`"
+    )]
+    fn test_env_parse_or_panic_panics_with_value_and_type() {
+        let _: u16 = env_parse_or_panic("CARGO_PKG_NAME");
+    }
+
+    #[test]
+    fn test_current_dir_or_panic() {
+        assert_eq!(current_dir_or_panic(), env::current_dir().or_panic());
+    }
+}
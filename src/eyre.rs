@@ -0,0 +1,167 @@
+//! Panicking with [`eyre`](https://docs.rs/eyre) context.
+//!
+//! Available with the `eyre` feature. `or_panic()` on an `eyre::Result` renders the report through
+//! the installed report handler, so the sections and colors of handlers such as `color-eyre` are kept
+//! in the panic message. [`BUEyre`] adds one more layer of context at the failing call, in place of
+//! `.wrap_err("...").unwrap()`.
+
+use std::fmt::Display;
+
+use ::eyre::WrapErr;
+
+use crate::message::Truncated;
+use crate::policy;
+
+/// Trait that provides alternatives to `.wrap_err(...).unwrap()` for results whose error eyre can
+/// wrap.
+pub trait BUEyre<T> {
+    /// Unwraps the result, wrapping the error with `message` before panicking.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is an `Err`, with a panic message including the report as rendered by the
+    /// installed eyre report handler.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use better_unwrap::eyre::BUEyre;
+    ///
+    /// let x: eyre::Result<u32> = Ok(42);
+    /// assert_eq!(x.wrap_err_or_panic("reading the port"), 42);
+    /// ```
+    ///
+    /// ```should_panic
+    /// use std::fs;
+    /// use better_unwrap::eyre::BUEyre;
+    ///
+    /// let config = fs::read_to_string("/nonexistent/app.toml");
+    /// config.wrap_err_or_panic("loading the configuration"); // panics with `"called `wrap_err_or_panic()` on an `Err` value: loading the configuration\n\nCaused by:\n    No such file or directory (os error 2)\n\nLocation:\n    src/main.rs:7:8"`
+    /// ```
+    #[track_caller]
+    fn wrap_err_or_panic<M>(self, message: M) -> T
+    where
+        M: Display + Send + Sync + 'static;
+
+    /// Unwraps the result, wrapping the error with the message returned by `f` before panicking.
+    ///
+    /// `f` is only called if the value is an `Err`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is an `Err`, with a panic message including the report as rendered by the
+    /// installed eyre report handler.
+    ///
+    /// # Examples
+    ///
+    /// ```should_panic
+    /// use std::fs;
+    /// use better_unwrap::eyre::BUEyre;
+    ///
+    /// let path = "/nonexistent/app.toml";
+    /// fs::read_to_string(path).wrap_err_with_or_panic(|| format!("loading {path}"));
+    /// ```
+    #[track_caller]
+    fn wrap_err_with_or_panic<M, F>(self, f: F) -> T
+    where
+        M: Display + Send + Sync + 'static,
+        F: FnOnce() -> M;
+}
+
+impl<T, E> BUEyre<T> for Result<T, E>
+where
+    Result<T, E>: WrapErr<T, E>,
+{
+    #[track_caller]
+    fn wrap_err_or_panic<M>(self, message: M) -> T
+    where
+        M: Display + Send + Sync + 'static,
+    {
+        match self.wrap_err(message) {
+            Ok(value) => value,
+            Err(report) => fail_wrapped("wrap_err_or_panic", &report),
+        }
+    }
+
+    #[track_caller]
+    fn wrap_err_with_or_panic<M, F>(self, f: F) -> T
+    where
+        M: Display + Send + Sync + 'static,
+        F: FnOnce() -> M,
+    {
+        match self.wrap_err_with(f) {
+            Ok(value) => value,
+            Err(report) => fail_wrapped("wrap_err_with_or_panic", &report),
+        }
+    }
+}
+
+/// Fails with a report that `method` wrapped, rendered by the installed eyre report handler.
+#[track_caller]
+fn fail_wrapped(method: &str, report: &::eyre::Report) -> ! {
+    policy::fail(format_args!(
+        "called `{method}()` on an `Err` value: {report:?}",
+        report = Truncated(report)
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::BUResult;
+    use std::error::Error;
+    use std::fmt;
+    use std::io;
+    use std::sync::Once;
+
+    /// A report handler that tags its output, to tell it apart from eyre's default handler.
+    struct Tagged;
+
+    impl ::eyre::EyreHandler for Tagged {
+        fn debug(&self, error: &(dyn Error + 'static), f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "[tagged] {error}")?;
+            let mut source = error.source();
+            while let Some(error) = source {
+                write!(f, " <- {error}")?;
+                source = error.source();
+            }
+            Ok(())
+        }
+    }
+
+    fn install_handler() {
+        static HANDLER: Once = Once::new();
+        HANDLER.call_once(|| {
+            ::eyre::set_hook(Box::new(|_| Box::new(Tagged))).or_panic();
+        });
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "called `or_panic()` on an `Err` value: [tagged] reading the port <- refused"
+    )]
+    fn test_or_panic_renders_through_installed_handler() {
+        install_handler();
+        let result: Result<u16, io::Error> = Err(io::Error::other("refused"));
+        result.wrap_err("reading the port").or_panic();
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "called `wrap_err_or_panic()` on an `Err` value: [tagged] connecting to the broker <- reading the port <- refused"
+    )]
+    fn test_wrap_err_or_panic_adds_context() {
+        install_handler();
+        let result: Result<u16, io::Error> = Err(io::Error::other("refused"));
+        result
+            .wrap_err("reading the port")
+            .wrap_err_or_panic("connecting to the broker");
+    }
+
+    #[test]
+    fn test_wrap_err_with_or_panic_is_lazy_on_ok() {
+        let result: Result<u16, io::Error> = Ok(8080);
+        let port = result.wrap_err_with_or_panic(|| -> &str { unreachable!() });
+        assert_eq!(port, 8080);
+    }
+}
@@ -0,0 +1,227 @@
+//! Aggregated failures from processing many items, with the index of each failure.
+
+use std::fmt::{self, Debug};
+use std::panic::Location;
+
+use crate::message::Truncated;
+use crate::{emit, policy};
+
+/// An error together with the index of the item that produced it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct IndexedError<E> {
+    /// The position of the failing item.
+    pub index: usize,
+    /// The error the item produced.
+    pub error: E,
+}
+
+/// Every failure from processing a sequence of items, with the index of each failing item.
+///
+/// Returned by [`BUIterator::partition_report`](crate::BUIterator::partition_report). The report can be
+/// turned into a panic with [`or_panic`](Self::or_panic), logged with [`log`](Self::log), inspected,
+/// or serialized (with the `serde` feature).
+///
+/// # Examples
+///
+/// ```
+/// use better_unwrap::BUIterator;
+///
+/// let rows = vec![Ok(1), Err("bad row"), Ok(3)];
+/// let (values, report) = rows.into_iter().partition_report();
+/// assert_eq!(values, vec![1, 3]);
+/// assert_eq!(report.indices().collect::<Vec<_>>(), vec![1]);
+/// assert_eq!(report.to_string(), "1 of 3 items failed:\n  [1] \"bad row\"");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct FailureReport<E> {
+    total: usize,
+    failures: Vec<IndexedError<E>>,
+}
+
+impl<E> FailureReport<E> {
+    /// Creates a report of `failures` out of `total` processed items.
+    ///
+    /// The failures are sorted by index.
+    pub fn new(total: usize, mut failures: Vec<IndexedError<E>>) -> Self {
+        failures.sort_by_key(|failure| failure.index);
+        FailureReport { total, failures }
+    }
+
+    /// Returns the number of items that were processed, including the failed ones.
+    pub fn total(&self) -> usize {
+        self.total
+    }
+
+    /// Returns the number of failed items.
+    pub fn len(&self) -> usize {
+        self.failures.len()
+    }
+
+    /// Returns `true` if no item failed.
+    pub fn is_empty(&self) -> bool {
+        self.failures.is_empty()
+    }
+
+    /// Returns the failures, ordered by index.
+    pub fn failures(&self) -> &[IndexedError<E>] {
+        &self.failures
+    }
+
+    /// Returns the indices of the failed items, in order.
+    pub fn indices(&self) -> impl Iterator<Item = usize> + '_ {
+        self.failures.iter().map(|failure| failure.index)
+    }
+
+    /// Consumes the report, returning the failures ordered by index.
+    pub fn into_failures(self) -> Vec<IndexedError<E>> {
+        self.failures
+    }
+
+    /// Panics if any item failed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the report is not empty, with a panic message listing the index and error (formatted
+    /// using `Debug`) of every failed item.
+    ///
+    /// # Examples
+    ///
+    /// ```should_panic
+    /// use better_unwrap::BUIterator;
+    ///
+    /// let (_, report) = vec![Ok(1), Err("bad row")].into_iter().partition_report();
+    /// report.or_panic(); // panics with `"1 of 2 items failed:\n  [1] \"bad row\""`
+    /// ```
+    #[track_caller]
+    pub fn or_panic(self)
+    where
+        E: Debug,
+    {
+        if !self.is_empty() {
+            policy::fail(format_args!("{self}"));
+        }
+    }
+
+    /// Writes the report to stderr together with the caller location, if any item failed.
+    #[track_caller]
+    pub fn log(&self)
+    where
+        E: Debug,
+    {
+        if !self.is_empty() {
+            emit::log(Location::caller(), format_args!("{self}"));
+        }
+    }
+
+    /// Renders one indented `[index] error` line per failure.
+    pub(crate) fn lines(&self) -> impl fmt::Display + '_
+    where
+        E: Debug,
+    {
+        Lines(&self.failures)
+    }
+}
+
+impl<E: Debug> fmt::Display for FailureReport<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} of {} items failed:\n{}",
+            self.len(),
+            self.total,
+            self.lines()
+        )
+    }
+}
+
+struct Lines<'a, E>(&'a [IndexedError<E>]);
+
+impl<E: Debug> fmt::Display for Lines<'_, E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (position, failure) in self.0.iter().enumerate() {
+            if position > 0 {
+                f.write_str("\n")?;
+            }
+            write!(f, "  [{}] {:?}", failure.index, Truncated(&failure.error))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::BUIterator;
+
+    #[test]
+    fn test_partition_report_splits_values_and_failures() {
+        let results: Vec<Result<u32, &str>> = vec![Err("first"), Ok(2), Err("third")];
+        let (values, report) = results.into_iter().partition_report();
+        assert_eq!(values, vec![2]);
+        assert_eq!(report.total(), 3);
+        assert_eq!(report.len(), 2);
+        assert_eq!(report.indices().collect::<Vec<_>>(), vec![0, 2]);
+        assert_eq!(
+            report.into_failures(),
+            vec![
+                IndexedError {
+                    index: 0,
+                    error: "first"
+                },
+                IndexedError {
+                    index: 2,
+                    error: "third"
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_or_panic_with_empty_report() {
+        let results: Vec<Result<u32, &str>> = vec![Ok(1), Ok(2)];
+        let (values, report) = results.into_iter().partition_report();
+        report.or_panic();
+        assert_eq!(values, vec![1, 2]);
+    }
+
+    #[test]
+    #[should_panic(expected = "2 of 3 items failed:\n  [0] \"first\"\n  [2] \"third\"")]
+    fn test_or_panic_lists_every_failure() {
+        let results: Vec<Result<u32, &str>> = vec![Err("first"), Ok(2), Err("third")];
+        let (_, report) = results.into_iter().partition_report();
+        report.or_panic();
+    }
+
+    #[test]
+    fn test_new_sorts_failures_by_index() {
+        let report = FailureReport::new(
+            4,
+            vec![
+                IndexedError {
+                    index: 3,
+                    error: 'b',
+                },
+                IndexedError {
+                    index: 1,
+                    error: 'a',
+                },
+            ],
+        );
+        assert_eq!(report.indices().collect::<Vec<_>>(), vec![1, 3]);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_report_serializes_with_indices() {
+        use crate::BUResult;
+
+        let results: Vec<Result<u32, &str>> = vec![Ok(1), Err("bad")];
+        let (_, report) = results.into_iter().partition_report();
+        assert_eq!(
+            serde_json::to_string(&report).or_panic(),
+            r#"{"total":2,"failures":[{"index":1,"error":"bad"}]}"#
+        );
+    }
+}
@@ -0,0 +1,293 @@
+//! Helpers for checking the return values of raw C calls.
+//!
+//! On a failure sentinel (`-1`, a negative value, or a null pointer) these helpers read
+//! `io::Error::last_os_error()` and panic with the errno name and message, instead of each call site
+//! checking `errno` by hand.
+
+use std::borrow::Cow;
+use std::ffi::CStr;
+use std::fmt::Debug;
+use std::io;
+use std::panic::Location;
+
+use crate::{emit, policy};
+
+/// Return types of C calls that signal failure with a sentinel value.
+pub trait FfiReturn: Copy + Debug {
+    /// Returns `true` if the value is the failure sentinel: `-1` for integers, null for pointers.
+    fn is_failure_sentinel(&self) -> bool;
+}
+
+/// Integer return types of C calls that signal failure with any negative value.
+pub trait FfiInt: FfiReturn {
+    /// Returns `true` if the value is negative.
+    fn is_negative(&self) -> bool;
+}
+
+macro_rules! impl_ffi_int {
+    ($($ty:ty),*) => {
+        $(
+            impl FfiReturn for $ty {
+                fn is_failure_sentinel(&self) -> bool {
+                    *self == -1
+                }
+            }
+
+            impl FfiInt for $ty {
+                fn is_negative(&self) -> bool {
+                    *self < 0
+                }
+            }
+        )*
+    };
+}
+
+impl_ffi_int!(i8, i16, i32, i64, isize);
+
+impl<T> FfiReturn for *const T {
+    fn is_failure_sentinel(&self) -> bool {
+        self.is_null()
+    }
+}
+
+impl<T> FfiReturn for *mut T {
+    fn is_failure_sentinel(&self) -> bool {
+        self.is_null()
+    }
+}
+
+/// Returns `ret` unchanged unless it is the failure sentinel (`-1` or null).
+///
+/// # Panics
+///
+/// Panics if `ret` is the failure sentinel, with a panic message including the errno name (on Unix)
+/// and message of `io::Error::last_os_error()`.
+///
+/// # Examples
+///
+/// ```
+/// use better_unwrap::ffi::check_ret_or_panic;
+///
+/// let fd: i32 = 3; // e.g. the result of `libc::open(...)`
+/// assert_eq!(check_ret_or_panic(fd), 3);
+/// ```
+#[track_caller]
+pub fn check_ret_or_panic<T: FfiReturn>(ret: T) -> T {
+    if ret.is_failure_sentinel() {
+        fail_with_last_os_error(ret);
+    }
+    ret
+}
+
+/// Returns `ret` unchanged unless it is negative.
+///
+/// For calls that signal failure with any negative value rather than exactly `-1`.
+///
+/// # Panics
+///
+/// Panics if `ret` is negative, with a panic message including the errno name (on Unix) and message of
+/// `io::Error::last_os_error()`.
+///
+/// # Examples
+///
+/// ```
+/// use better_unwrap::ffi::check_nonneg_or_panic;
+///
+/// let written: isize = 12; // e.g. the result of `libc::write(...)`
+/// assert_eq!(check_nonneg_or_panic(written), 12);
+/// ```
+#[track_caller]
+pub fn check_nonneg_or_panic<T: FfiInt>(ret: T) -> T {
+    if ret.is_negative() {
+        fail_with_last_os_error(ret);
+    }
+    ret
+}
+
+#[track_caller]
+fn fail_with_last_os_error<T: Debug>(ret: T) -> ! {
+    let error = io::Error::last_os_error();
+    match error.raw_os_error().and_then(errno_name) {
+        Some(name) => policy::fail(format_args!("FFI call returned {ret:?}: {name}: {error}")),
+        None => policy::fail(format_args!("FFI call returned {ret:?}: {error}")),
+    }
+}
+
+#[cfg(unix)]
+fn errno_name(code: i32) -> Option<&'static str> {
+    let name = match code {
+        libc::EPERM => "EPERM",
+        libc::ENOENT => "ENOENT",
+        libc::ESRCH => "ESRCH",
+        libc::EINTR => "EINTR",
+        libc::EIO => "EIO",
+        libc::ENXIO => "ENXIO",
+        libc::E2BIG => "E2BIG",
+        libc::ENOEXEC => "ENOEXEC",
+        libc::EBADF => "EBADF",
+        libc::ECHILD => "ECHILD",
+        libc::EAGAIN => "EAGAIN",
+        libc::ENOMEM => "ENOMEM",
+        libc::EACCES => "EACCES",
+        libc::EFAULT => "EFAULT",
+        libc::EBUSY => "EBUSY",
+        libc::EEXIST => "EEXIST",
+        libc::EXDEV => "EXDEV",
+        libc::ENODEV => "ENODEV",
+        libc::ENOTDIR => "ENOTDIR",
+        libc::EISDIR => "EISDIR",
+        libc::EINVAL => "EINVAL",
+        libc::ENFILE => "ENFILE",
+        libc::EMFILE => "EMFILE",
+        libc::ENOTTY => "ENOTTY",
+        libc::EFBIG => "EFBIG",
+        libc::ENOSPC => "ENOSPC",
+        libc::ESPIPE => "ESPIPE",
+        libc::EROFS => "EROFS",
+        libc::EMLINK => "EMLINK",
+        libc::EPIPE => "EPIPE",
+        libc::EDOM => "EDOM",
+        libc::ERANGE => "ERANGE",
+        libc::EDEADLK => "EDEADLK",
+        libc::ENAMETOOLONG => "ENAMETOOLONG",
+        libc::ENOSYS => "ENOSYS",
+        libc::ENOTEMPTY => "ENOTEMPTY",
+        libc::ELOOP => "ELOOP",
+        libc::EADDRINUSE => "EADDRINUSE",
+        libc::ECONNREFUSED => "ECONNREFUSED",
+        libc::ECONNRESET => "ECONNRESET",
+        libc::EINPROGRESS => "EINPROGRESS",
+        libc::ETIMEDOUT => "ETIMEDOUT",
+        _ => return None,
+    };
+    Some(name)
+}
+
+#[cfg(not(unix))]
+fn errno_name(_code: i32) -> Option<&'static str> {
+    None
+}
+
+/// Trait that provides alternatives to `to_str().unwrap()` for strings handed back from C.
+pub trait BUCStr {
+    /// Converts the C string to a `&str`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the string is not valid UTF-8, with a panic message including the byte offset of the
+    /// invalid sequence.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use better_unwrap::BUCStr;
+    ///
+    /// assert_eq!(c"hello".to_str_or_panic(), "hello");
+    /// ```
+    #[track_caller]
+    fn to_str_or_panic(&self) -> &str;
+
+    /// Converts the C string to a `&str`, substituting `U+FFFD REPLACEMENT CHARACTER` for invalid
+    /// UTF-8 sequences and logging the substitution.
+    ///
+    /// Equivalent to `to_string_lossy()`, but the conversion failure is written to stderr together
+    /// with the caller location.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use better_unwrap::BUCStr;
+    ///
+    /// assert_eq!(c"caf\xe9".to_str_or_lossy_logged(), "caf\u{FFFD}");
+    /// ```
+    #[track_caller]
+    fn to_str_or_lossy_logged(&self) -> Cow<'_, str>;
+}
+
+impl BUCStr for CStr {
+    #[track_caller]
+    fn to_str_or_panic(&self) -> &str {
+        match self.to_str() {
+            Ok(string) => string,
+            Err(error) => policy::fail(format_args!(
+                "called `to_str_or_panic()` on a C string with invalid UTF-8 at byte offset {}: {error}",
+                error.valid_up_to()
+            )),
+        }
+    }
+
+    #[track_caller]
+    fn to_str_or_lossy_logged(&self) -> Cow<'_, str> {
+        match self.to_str() {
+            Ok(string) => Cow::Borrowed(string),
+            Err(error) => {
+                emit::log(
+                    Location::caller(),
+                    format_args!(
+                        "replaced invalid UTF-8 in C string at byte offset {}: {error}",
+                        error.valid_up_to()
+                    ),
+                );
+                self.to_string_lossy()
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_str_or_panic_with_valid_utf8() {
+        assert_eq!(c"plain".to_str_or_panic(), "plain");
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "called `to_str_or_panic()` on a C string with invalid UTF-8 at byte offset 3"
+    )]
+    fn test_to_str_or_panic_reports_byte_offset() {
+        let _ = c"caf\xe9".to_str_or_panic();
+    }
+
+    #[test]
+    fn test_to_str_or_lossy_logged() {
+        assert_eq!(c"ok".to_str_or_lossy_logged(), Cow::Borrowed("ok"));
+        assert_eq!(c"caf\xe9".to_str_or_lossy_logged(), "caf\u{FFFD}");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_check_ret_or_panic_passes_through_success() {
+        assert_eq!(check_ret_or_panic(0), 0);
+        let value = 7;
+        let ptr: *const i32 = &value;
+        assert_eq!(check_ret_or_panic(ptr), ptr);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    #[should_panic(expected = "FFI call returned -1: EBADF: Bad file descriptor")]
+    fn test_check_ret_or_panic_reports_errno() {
+        let ret = unsafe { libc::close(-1) };
+        let _ = check_ret_or_panic(ret);
+    }
+
+    #[test]
+    #[should_panic(expected = "FFI call returned 0x0")]
+    fn test_check_ret_or_panic_panics_on_null() {
+        let _ = check_ret_or_panic(std::ptr::null_mut::<u8>());
+    }
+
+    #[test]
+    fn test_check_nonneg_or_panic_passes_through_success() {
+        assert_eq!(check_nonneg_or_panic(12isize), 12);
+    }
+
+    #[test]
+    #[should_panic(expected = "FFI call returned -2")]
+    fn test_check_nonneg_or_panic_panics_on_negative() {
+        let _ = check_nonneg_or_panic(-2i32);
+    }
+}
@@ -0,0 +1,386 @@
+//! Filesystem helpers whose panic messages include the path involved.
+
+use std::error::Error;
+use std::fmt;
+use std::fs::{self, Metadata};
+use std::io;
+use std::ops::Deref;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use crate::policy;
+
+/// Metadata for a file, together with the path it was read from.
+///
+/// Returned by [`metadata_or_panic`]. Dereferences to [`Metadata`], and its [`BUMetadata`] methods
+/// include the path in their panic messages.
+#[derive(Debug, Clone)]
+pub struct PathMetadata {
+    path: PathBuf,
+    metadata: Metadata,
+}
+
+impl PathMetadata {
+    /// Returns the path the metadata was read from.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Unwraps the metadata, discarding the path.
+    pub fn into_inner(self) -> Metadata {
+        self.metadata
+    }
+}
+
+impl Deref for PathMetadata {
+    type Target = Metadata;
+
+    fn deref(&self) -> &Metadata {
+        &self.metadata
+    }
+}
+
+/// Reads the metadata of the file at `path`, following symlinks.
+///
+/// Equivalent to `std::fs::metadata(path).unwrap()`, but the returned [`PathMetadata`] remembers the
+/// path for later panic messages.
+///
+/// # Panics
+///
+/// Panics if the metadata cannot be read, with a panic message including the path and the error.
+///
+/// # Examples
+///
+/// ```
+/// use better_unwrap::fs::metadata_or_panic;
+///
+/// let metadata = metadata_or_panic("Cargo.toml");
+/// assert!(metadata.is_file());
+/// ```
+#[track_caller]
+pub fn metadata_or_panic(path: impl AsRef<Path>) -> PathMetadata {
+    let path = path.as_ref();
+    match fs::metadata(path) {
+        Ok(metadata) => PathMetadata {
+            path: path.to_path_buf(),
+            metadata,
+        },
+        Err(error) => policy::fail(format_args!(
+            "failed to read metadata of '{}': {error}",
+            path.display()
+        )),
+    }
+}
+
+/// Reads the entire contents of the file at `path` into a byte vector.
+///
+/// Equivalent to `std::fs::read(path).unwrap()`.
+///
+/// # Panics
+///
+/// Panics if the file cannot be read, with a panic message including the path and the error.
+///
+/// # Examples
+///
+/// ```should_panic
+/// use better_unwrap::fs::read_or_panic;
+///
+/// read_or_panic("/nonexistent/app.toml"); // panics with `"failed to read '/nonexistent/app.toml': No such file or directory (os error 2)"`
+/// ```
+#[track_caller]
+pub fn read_or_panic(path: impl AsRef<Path>) -> Vec<u8> {
+    let path = path.as_ref();
+    match fs::read(path) {
+        Ok(value) => value,
+        Err(error) => fail_at("read", path, &error),
+    }
+}
+
+/// Reads the entire contents of the file at `path` into a string.
+///
+/// Equivalent to `std::fs::read_to_string(path).unwrap()`.
+///
+/// # Panics
+///
+/// Panics if the file cannot be read or is not valid UTF-8, with a panic message including the path
+/// and the error.
+///
+/// # Examples
+///
+/// ```
+/// use better_unwrap::fs::read_to_string_or_panic;
+///
+/// let manifest = read_to_string_or_panic("Cargo.toml");
+/// assert!(manifest.contains("[package]"));
+/// ```
+#[track_caller]
+pub fn read_to_string_or_panic(path: impl AsRef<Path>) -> String {
+    let path = path.as_ref();
+    match fs::read_to_string(path) {
+        Ok(value) => value,
+        Err(error) => fail_at("read", path, &error),
+    }
+}
+
+/// Writes `contents` to the file at `path`, creating or truncating it.
+///
+/// Equivalent to `std::fs::write(path, contents).unwrap()`.
+///
+/// # Panics
+///
+/// Panics if the file cannot be written, with a panic message including the path and the error.
+#[track_caller]
+pub fn write_or_panic(path: impl AsRef<Path>, contents: impl AsRef<[u8]>) {
+    let path = path.as_ref();
+    match fs::write(path, contents) {
+        Ok(value) => value,
+        Err(error) => fail_at("write", path, &error),
+    }
+}
+
+#[track_caller]
+fn fail_at(action: &str, path: &Path, error: &io::Error) -> ! {
+    policy::fail(format_args!(
+        "failed to {action} '{}': {error}",
+        path.display()
+    ))
+}
+
+/// An I/O error together with the path it occurred on.
+///
+/// Returned by [`PathContext::with_path`]. Its `Debug` output is the same as its `Display` output,
+/// so that `or_panic()` and `unwrap()` print the path and the error's message rather than the nested
+/// structure.
+pub struct PathError {
+    path: PathBuf,
+    error: io::Error,
+}
+
+impl PathError {
+    /// Returns the path the error occurred on.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Returns the kind of the underlying I/O error.
+    pub fn kind(&self) -> io::ErrorKind {
+        self.error.kind()
+    }
+
+    /// Unwraps the underlying I/O error, discarding the path.
+    pub fn into_inner(self) -> io::Error {
+        self.error
+    }
+}
+
+impl fmt::Display for PathError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "failed to access '{}': {}",
+            self.path.display(),
+            self.error
+        )
+    }
+}
+
+impl fmt::Debug for PathError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+impl Error for PathError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+/// Trait that attaches a path to the error of an `io::Result`.
+///
+/// `io::Error` does not record the path it occurred on, so `fs::read(path).unwrap()` panics with
+/// only `Os { code: 2, kind: NotFound, .. }`. For the common operations, [`read_or_panic`],
+/// [`read_to_string_or_panic`] and [`write_or_panic`] name the operation as well.
+pub trait PathContext<T> {
+    /// Wraps the error, if any, in a [`PathError`] recording `path`.
+    ///
+    /// # Examples
+    ///
+    /// ```should_panic
+    /// use std::fs;
+    /// use better_unwrap::BUResult;
+    /// use better_unwrap::fs::PathContext;
+    ///
+    /// let path = "/nonexistent/app.toml";
+    /// fs::read(path).with_path(path).or_panic(); // panics with `"called `or_panic()` on an `Err` value: failed to access '/nonexistent/app.toml': No such file or directory (os error 2)"`
+    /// ```
+    fn with_path(self, path: impl AsRef<Path>) -> Result<T, PathError>;
+}
+
+impl<T> PathContext<T> for io::Result<T> {
+    fn with_path(self, path: impl AsRef<Path>) -> Result<T, PathError> {
+        self.map_err(|error| PathError {
+            path: path.as_ref().to_path_buf(),
+            error,
+        })
+    }
+}
+
+/// Trait that provides alternatives to `modified().unwrap()` and `created().unwrap()` for file
+/// metadata.
+///
+/// The panic messages explain when the platform or filesystem does not record the timestamp, and
+/// include the path when the metadata came from [`metadata_or_panic`].
+pub trait BUMetadata {
+    /// Returns the last modification time of the file.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the modification time is not available on this platform or filesystem.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use better_unwrap::BUMetadata;
+    /// use better_unwrap::fs::metadata_or_panic;
+    ///
+    /// let modified = metadata_or_panic("Cargo.toml").modified_or_panic();
+    /// ```
+    #[track_caller]
+    fn modified_or_panic(&self) -> SystemTime;
+
+    /// Returns the creation time of the file.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the creation time is not available on this platform or filesystem, which is common
+    /// on older Linux filesystems.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use better_unwrap::BUMetadata;
+    /// use better_unwrap::fs::metadata_or_panic;
+    ///
+    /// let created = metadata_or_panic("Cargo.toml").created_or_panic();
+    /// ```
+    #[track_caller]
+    fn created_or_panic(&self) -> SystemTime;
+}
+
+impl BUMetadata for Metadata {
+    #[track_caller]
+    fn modified_or_panic(&self) -> SystemTime {
+        timestamp_or_fail(self.modified(), "modification", None)
+    }
+
+    #[track_caller]
+    fn created_or_panic(&self) -> SystemTime {
+        timestamp_or_fail(self.created(), "creation", None)
+    }
+}
+
+impl BUMetadata for PathMetadata {
+    #[track_caller]
+    fn modified_or_panic(&self) -> SystemTime {
+        timestamp_or_fail(self.metadata.modified(), "modification", Some(&self.path))
+    }
+
+    #[track_caller]
+    fn created_or_panic(&self) -> SystemTime {
+        timestamp_or_fail(self.metadata.created(), "creation", Some(&self.path))
+    }
+}
+
+#[track_caller]
+fn timestamp_or_fail(
+    result: io::Result<SystemTime>,
+    kind: &str,
+    path: Option<&Path>,
+) -> SystemTime {
+    let error = match result {
+        Ok(time) => return time,
+        Err(error) => error,
+    };
+    let subject = match path {
+        Some(path) => format!("the {kind} time of '{}'", path.display()),
+        None => format!("the {kind} time"),
+    };
+    if error.kind() == io::ErrorKind::Unsupported {
+        policy::fail(format_args!(
+            "{subject} is not available: this platform or filesystem does not record it ({error})"
+        ))
+    } else {
+        policy::fail(format_args!("failed to read {subject}: {error}"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::BUResult;
+
+    #[test]
+    fn test_metadata_or_panic_keeps_path() {
+        let metadata = metadata_or_panic("Cargo.toml");
+        assert_eq!(metadata.path(), Path::new("Cargo.toml"));
+        assert!(metadata.is_file());
+    }
+
+    #[test]
+    #[should_panic(expected = "failed to read metadata of 'does/not/exist.toml'")]
+    fn test_metadata_or_panic_panics_with_path() {
+        let _ = metadata_or_panic("does/not/exist.toml");
+    }
+
+    #[test]
+    fn test_modified_or_panic() {
+        let metadata = metadata_or_panic("Cargo.toml");
+        assert_eq!(metadata.modified_or_panic(), metadata.modified().or_panic());
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "the creation time of 'Cargo.toml' is not available: this platform or filesystem does not record it"
+    )]
+    fn test_timestamp_or_fail_explains_unsupported() {
+        let unsupported = io::Error::from(io::ErrorKind::Unsupported);
+        timestamp_or_fail(Err(unsupported), "creation", Some(Path::new("Cargo.toml")));
+    }
+
+    #[test]
+    fn test_read_to_string_or_panic() {
+        assert_eq!(
+            read_to_string_or_panic("Cargo.toml").into_bytes(),
+            read_or_panic("Cargo.toml")
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "failed to read 'does/not/exist.toml': ")]
+    fn test_read_or_panic_panics_with_path() {
+        read_or_panic("does/not/exist.toml");
+    }
+
+    #[test]
+    #[should_panic(expected = "failed to write 'does/not/exist.toml': ")]
+    fn test_write_or_panic_panics_with_path() {
+        write_or_panic("does/not/exist.toml", "x");
+    }
+
+    #[test]
+    fn test_with_path_records_path() {
+        let error = fs::read("does/not/exist.toml")
+            .with_path("does/not/exist.toml")
+            .unwrap_err();
+        assert_eq!(error.path(), Path::new("does/not/exist.toml"));
+        assert_eq!(error.kind(), io::ErrorKind::NotFound);
+        assert!(error.source().is_some());
+        assert_eq!(format!("{error:?}"), error.to_string());
+        assert!(
+            error
+                .to_string()
+                .starts_with("failed to access 'does/not/exist.toml': ")
+        );
+    }
+}
@@ -0,0 +1,237 @@
+//! Helpers for awaiting fallible futures.
+//!
+//! Available with the `async` feature. [`join_all_or_panic`] and [`select_ok_or_panic`] also require
+//! the `futures` feature.
+
+use std::fmt::Debug;
+use std::future::Future;
+use std::panic::Location;
+
+#[cfg(feature = "futures")]
+use futures::stream::{FuturesUnordered, StreamExt};
+
+#[cfg(feature = "futures")]
+use crate::BUIterator;
+#[cfg(feature = "futures")]
+use crate::failure_report::{FailureReport, IndexedError};
+use crate::message::Truncated;
+use crate::policy;
+
+/// Trait that provides an alternative to `.await.or_panic()` for futures resolving to a `Result`.
+pub trait BUTryFuture<T, E>: Future<Output = Result<T, E>> + Sized {
+    /// Awaits the future, yielding the content of an `Ok`.
+    ///
+    /// The caller location is captured when `await_or_panic()` is called, so the panic message points
+    /// at the call site rather than at the executor that polled the future.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the future resolves to an `Err`, with a panic message including the caller location
+    /// and the error formatted using `Debug`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use better_unwrap::future::BUTryFuture;
+    ///
+    /// async fn fetch() -> Result<String, std::io::Error> {
+    ///     Ok("payload".to_string())
+    /// }
+    ///
+    /// # tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(async {
+    /// let body = fetch().await_or_panic().await;
+    /// assert_eq!(body, "payload");
+    /// # });
+    /// ```
+    #[track_caller]
+    fn await_or_panic(self) -> impl Future<Output = T>
+    where
+        E: Debug;
+}
+
+impl<F, T, E> BUTryFuture<T, E> for F
+where
+    F: Future<Output = Result<T, E>>,
+{
+    #[track_caller]
+    fn await_or_panic(self) -> impl Future<Output = T>
+    where
+        E: Debug,
+    {
+        let location = Location::caller();
+        async move {
+            match self.await {
+                Ok(value) => value,
+                Err(error) => policy::fail(format_args!(
+                    "called `await_or_panic()` at {location} on a future that resolved to an `Err` value: {error:?}",
+                    error = Truncated(&error)
+                )),
+            }
+        }
+    }
+}
+
+/// Awaits every future concurrently and returns their `Ok` values in order.
+///
+/// Unlike `try_join_all(...).await.unwrap()`, every future is driven to completion, so the panic
+/// reports all failures instead of whichever happened first.
+///
+/// # Panics
+///
+/// Panics if any future resolves to an `Err`, with a panic message listing the index and error
+/// (formatted using `Debug`) of every failed future.
+///
+/// # Examples
+///
+/// ```
+/// use better_unwrap::future::join_all_or_panic;
+///
+/// let requests = (1..=3).map(|id| async move { Ok::<u32, String>(id * 10) });
+/// let responses = futures::executor::block_on(join_all_or_panic(requests));
+/// assert_eq!(responses, vec![10, 20, 30]);
+/// ```
+#[cfg(feature = "futures")]
+pub async fn join_all_or_panic<I, F, T, E>(futures: I) -> Vec<T>
+where
+    I: IntoIterator<Item = F>,
+    F: Future<Output = Result<T, E>>,
+    E: Debug,
+{
+    let results = futures::future::join_all(futures).await;
+    let (values, report) = results.into_iter().partition_report();
+    if !report.is_empty() {
+        policy::fail(format_args!(
+            "called `join_all_or_panic()` but {} of {} futures failed:\n{}",
+            report.len(),
+            report.total(),
+            report.lines()
+        ));
+    }
+    values
+}
+
+/// Races the futures concurrently and returns the first `Ok` value.
+///
+/// The async analogue of trying redundant endpoints in turn: the remaining futures are dropped as soon
+/// as one succeeds.
+///
+/// # Panics
+///
+/// Panics if every future resolves to an `Err`, with a panic message listing the index and error
+/// (formatted using `Debug`) of every future.
+///
+/// # Examples
+///
+/// ```
+/// use better_unwrap::future::select_ok_or_panic;
+///
+/// let endpoints = [Err("primary down"), Ok("replica"), Err("backup down")];
+/// let requests = endpoints.into_iter().map(|outcome| async move { outcome });
+/// assert_eq!(futures::executor::block_on(select_ok_or_panic(requests)), "replica");
+/// ```
+#[cfg(feature = "futures")]
+pub async fn select_ok_or_panic<I, F, T, E>(futures: I) -> T
+where
+    I: IntoIterator<Item = F>,
+    F: Future<Output = Result<T, E>>,
+    E: Debug,
+{
+    let mut pending: FuturesUnordered<_> = futures
+        .into_iter()
+        .enumerate()
+        .map(|(index, future)| async move { (index, future.await) })
+        .collect();
+    let total = pending.len();
+    let mut failures = Vec::with_capacity(total);
+    while let Some((index, result)) = pending.next().await {
+        match result {
+            Ok(value) => return value,
+            Err(error) => failures.push(IndexedError { index, error }),
+        }
+    }
+    let report = FailureReport::new(total, failures);
+    policy::fail(format_args!(
+        "called `select_ok_or_panic()` but all {total} futures failed:\n{}",
+        report.lines()
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::BUResult;
+    #[cfg(feature = "futures")]
+    use futures::executor::block_on;
+    use std::panic::{self, AssertUnwindSafe};
+
+    async fn fetch(
+        outcome: Result<&'static str, &'static str>,
+    ) -> Result<&'static str, &'static str> {
+        outcome
+    }
+
+    #[tokio::test]
+    async fn test_await_or_panic_with_ok() {
+        assert_eq!(fetch(Ok("payload")).await_or_panic().await, "payload");
+    }
+
+    #[tokio::test]
+    async fn test_await_or_panic_future_is_send() {
+        let handle = tokio::spawn(fetch(Ok("payload")).await_or_panic());
+        assert_eq!(handle.await.map_err(|_| "join failed"), Ok("payload"));
+    }
+
+    #[test]
+    fn test_await_or_panic_reports_caller_location() {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .build()
+            .or_panic();
+        let line = line!() + 1;
+        let pending = fetch(Err("connection reset")).await_or_panic();
+        let payload = panic::catch_unwind(AssertUnwindSafe(|| runtime.block_on(pending)));
+        assert_eq!(
+            *payload.or_panic_err().downcast::<String>().or_panic(),
+            format!(
+                "called `await_or_panic()` at {}:{line}:54 on a future that resolved to an `Err` value: \"connection reset\"",
+                file!()
+            )
+        );
+    }
+
+    #[cfg(feature = "futures")]
+    #[test]
+    fn test_join_all_or_panic_with_all_ok() {
+        let futures = (0..3).map(|n| async move { Ok::<u32, &str>(n) });
+        assert_eq!(block_on(join_all_or_panic(futures)), vec![0, 1, 2]);
+    }
+
+    #[cfg(feature = "futures")]
+    #[test]
+    #[should_panic(
+        expected = "called `join_all_or_panic()` but 2 of 4 futures failed:\n  [1] \"timeout\"\n  [3] \"refused\""
+    )]
+    fn test_join_all_or_panic_reports_every_failure() {
+        let outcomes = [Ok(1), Err("timeout"), Ok(3), Err("refused")];
+        let futures = outcomes.into_iter().map(|outcome| async move { outcome });
+        let _ = block_on(join_all_or_panic(futures));
+    }
+
+    #[cfg(feature = "futures")]
+    #[test]
+    fn test_select_ok_or_panic_returns_first_success() {
+        let outcomes = [Err("primary down"), Ok(2), Ok(3)];
+        let futures = outcomes.into_iter().map(|outcome| async move { outcome });
+        assert_eq!(block_on(select_ok_or_panic(futures)), 2);
+    }
+
+    #[cfg(feature = "futures")]
+    #[test]
+    #[should_panic(
+        expected = "called `select_ok_or_panic()` but all 2 futures failed:\n  [0] \"primary down\"\n  [1] \"backup down\""
+    )]
+    fn test_select_ok_or_panic_reports_every_error() {
+        let outcomes: [Result<u32, &str>; 2] = [Err("primary down"), Err("backup down")];
+        let futures = outcomes.into_iter().map(|outcome| async move { outcome });
+        let _ = block_on(select_ok_or_panic(futures));
+    }
+}
@@ -0,0 +1,269 @@
+//! A panic hook that renders the crate's failures for people reading crash output.
+
+use std::backtrace::{Backtrace, BacktraceStatus};
+use std::cell::Cell;
+use std::fmt::Write;
+use std::panic::{self, Location};
+
+use crate::payload::BUPanicInfo;
+
+thread_local! {
+    static CRATE_PANIC: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Symbol prefixes of the backtrace frames left out of rendered failures: the standard library, the
+/// panic machinery, and this crate.
+const HIDDEN_FRAME_PREFIXES: &[&str] = &[
+    "std::",
+    "core::",
+    "alloc::",
+    "better_unwrap::",
+    "rust_begin_unwind",
+    "__rustc::",
+    "__rust_",
+    "__libc_start",
+];
+
+/// Symbols of the process entry frames left out of rendered failures.
+const HIDDEN_FRAMES: &[&str] = &["main", "_start", "<unknown>"];
+
+/// Marks the panic about to be raised on the current thread as a failure of the crate.
+pub(crate) fn mark_crate_panic() {
+    CRATE_PANIC.set(true);
+}
+
+/// Installs a panic hook that renders failures of the crate's panicking methods for readability.
+///
+/// A failure is rendered as a one-line summary with its location, the error on its own indented
+/// block, any report sections such as the module path, and, when backtraces are enabled through
+/// `RUST_BACKTRACE`, a backtrace without the frames of the standard library and this crate:
+///
+/// ```text
+/// error: called `or_panic()` on an `Err` value
+///   --> src/main.rs:12:40
+///
+///     Os { code: 2, kind: NotFound, message: "No such file or directory" }
+/// ```
+///
+/// Failures raised with a [structured payload](crate::payload) are rendered the same way. Other
+/// panics are passed to the previously installed hook.
+///
+/// # Examples
+///
+/// ```
+/// better_unwrap::install_panic_hook();
+/// ```
+pub fn install_panic_hook() {
+    let previous = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+        if !CRATE_PANIC.replace(false) {
+            return previous(info);
+        }
+        let payload = info.payload();
+        let message = match payload.downcast_ref::<BUPanicInfo>() {
+            Some(failure) => failure.message(),
+            None => payload.downcast_ref::<String>().map_or("", String::as_str),
+        };
+        let backtrace = Backtrace::capture();
+        let backtrace = (backtrace.status() == BacktraceStatus::Captured)
+            .then(|| trim_backtrace(&backtrace.to_string()));
+        eprint!(
+            "{}",
+            render(
+                message,
+                info.location(),
+                backtrace.as_deref(),
+                Palette::for_stderr()
+            )
+        );
+    }));
+}
+
+/// The ANSI escape sequences used to color rendered failures, empty when output is not colored.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Palette {
+    label: &'static str,
+    summary: &'static str,
+    location: &'static str,
+    error: &'static str,
+    reset: &'static str,
+}
+
+impl Palette {
+    const PLAIN: Palette = Palette {
+        label: "",
+        summary: "",
+        location: "",
+        error: "",
+        reset: "",
+    };
+
+    #[cfg(feature = "color")]
+    const COLORED: Palette = Palette {
+        label: "\x1b[1;31m",
+        summary: "\x1b[1m",
+        location: "\x1b[36m",
+        error: "\x1b[33m",
+        reset: "\x1b[0m",
+    };
+
+    /// Returns the palette for stderr: colored under the `color` feature when stderr is a terminal
+    /// and `NO_COLOR` is unset or empty, plain otherwise.
+    fn for_stderr() -> Palette {
+        #[cfg(feature = "color")]
+        {
+            use std::io::IsTerminal;
+
+            let no_color = std::env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty());
+            if !no_color && std::io::stderr().is_terminal() {
+                return Palette::COLORED;
+            }
+        }
+        Palette::PLAIN
+    }
+}
+
+/// Renders a failure message as a summary line, its location, the indented error, the remaining
+/// report sections, and the backtrace.
+fn render(
+    message: &str,
+    location: Option<&Location<'_>>,
+    backtrace: Option<&str>,
+    palette: Palette,
+) -> String {
+    let Palette {
+        label,
+        summary: bold,
+        location: cyan,
+        error: yellow,
+        reset,
+    } = palette;
+    let (body, sections) = message.split_once("\n\n").unwrap_or((message, ""));
+    let (summary, detail) = body.split_once(": ").unwrap_or((body, ""));
+    let mut output = format!("{label}error{reset}: {bold}{summary}{reset}\n");
+    if let Some(location) = location {
+        let _ = writeln!(output, "  {cyan}--> {location}{reset}");
+    }
+    if !detail.is_empty() {
+        output.push('\n');
+        for line in detail.lines() {
+            let _ = writeln!(output, "    {yellow}{line}{reset}");
+        }
+    }
+    if !sections.is_empty() {
+        let _ = writeln!(output, "\n{sections}");
+    }
+    if let Some(backtrace) = backtrace {
+        let _ = write!(output, "\nbacktrace:\n{backtrace}");
+    }
+    output
+}
+
+/// Removes the frames of [`HIDDEN_FRAMES`] and those whose symbol starts with one of
+/// [`HIDDEN_FRAME_PREFIXES`] from a rendered backtrace.
+fn trim_backtrace(backtrace: &str) -> String {
+    let mut output = String::new();
+    let mut keep = false;
+    for line in backtrace.lines() {
+        let trimmed = line.trim_start();
+        if let Some((index, symbol)) = trimmed.split_once(": ")
+            && index.chars().all(|c| c.is_ascii_digit())
+        {
+            let path = symbol.trim_start_matches(['<', '&']);
+            let path = path.strip_prefix("dyn ").unwrap_or(path);
+            keep = !HIDDEN_FRAMES.contains(&symbol)
+                && !HIDDEN_FRAME_PREFIXES
+                    .iter()
+                    .any(|prefix| path.starts_with(prefix));
+        }
+        if keep {
+            output.push_str(line);
+            output.push('\n');
+        }
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_splits_summary_and_error() {
+        let location = Location::caller();
+        assert_eq!(
+            render(
+                "called `or_panic()` on an `Err` value: NotFound\n\nmodule: app::config",
+                Some(location),
+                None,
+                Palette::PLAIN
+            ),
+            format!(
+                "error: called `or_panic()` on an `Err` value\n  --> {location}\n\n    NotFound\n\nmodule: app::config\n"
+            )
+        );
+    }
+
+    #[test]
+    fn test_render_without_error_or_location() {
+        assert_eq!(
+            render(
+                "called `or_panic()` on a `None` value",
+                None,
+                None,
+                Palette::PLAIN
+            ),
+            "error: called `or_panic()` on a `None` value\n"
+        );
+    }
+
+    #[test]
+    fn test_render_indents_multiline_errors() {
+        assert_eq!(
+            render(
+                "called `or_panic_multi()` on an `Err` value with 2 errors:\n  1. \"a\"\n  2. \"b\"",
+                None,
+                Some("   4: app::main\n"),
+                Palette::PLAIN
+            ),
+            "error: called `or_panic_multi()` on an `Err` value with 2 errors:\n  1. \"a\"\n  2. \"b\"\n\nbacktrace:\n   4: app::main\n"
+        );
+    }
+
+    #[cfg(feature = "color")]
+    #[test]
+    fn test_render_colors_summary_location_and_error() {
+        let location = Location::caller();
+        assert_eq!(
+            render(
+                "called `or_panic()` on an `Err` value: NotFound",
+                Some(location),
+                None,
+                Palette::COLORED
+            ),
+            format!(
+                "\x1b[1;31merror\x1b[0m: \x1b[1mcalled `or_panic()` on an `Err` value\x1b[0m\n  \x1b[36m--> {location}\x1b[0m\n\n    \x1b[33mNotFound\x1b[0m\n"
+            )
+        );
+    }
+
+    #[test]
+    fn test_trim_backtrace_drops_std_and_crate_frames() {
+        let backtrace = "   0: std::backtrace::Backtrace::capture
+             at /rustc/library/std/src/backtrace.rs:296:9
+   1: better_unwrap::emit::panic
+             at ./src/emit.rs:80:5
+   2: <core::option::Option<T> as better_unwrap::option::BUOption<T>>::or_panic
+   3: app::load_config
+             at ./src/main.rs:12:40
+   4: core::ops::function::FnOnce::call_once
+   5: <&dyn core::ops::function::Fn<(), Output = i32> as core::ops::function::FnOnce<()>>::call_once
+   6: main
+   7: __libc_start_main
+";
+        assert_eq!(
+            trim_backtrace(backtrace),
+            "   3: app::load_config\n             at ./src/main.rs:12:40\n"
+        );
+    }
+}
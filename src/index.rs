@@ -0,0 +1,113 @@
+use std::fmt::Debug;
+use std::slice::SliceIndex;
+
+use crate::message::Truncated;
+use crate::policy;
+
+/// Trait that provides range-checked indexing for slices, `Vec`, and `str`.
+///
+/// Unlike plain indexing, the panic message always contains the requested index or range together
+/// with the length of the container, and is routed through the crate's [policy].
+pub trait BUIndex<I> {
+    /// The type returned by indexing.
+    type Output: ?Sized;
+
+    /// Returns a reference to the element or subslice at `index`.
+    ///
+    /// Equivalent to `&container[index]`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of range, with a panic message including the index and the length.
+    /// For `str`, also panics if a range does not fall on a char boundary.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use better_unwrap::BUIndex;
+    ///
+    /// let values = vec![1, 2, 3];
+    /// assert_eq!(*values.index_or_panic(1), 2);
+    /// assert_eq!(values.index_or_panic(1..), &[2, 3]);
+    /// ```
+    ///
+    /// ```should_panic
+    /// use better_unwrap::BUIndex;
+    ///
+    /// let values = [1, 2, 3];
+    /// values.index_or_panic(2..5); // panics with `"index 2..5 out of range for slice of length 3"`
+    /// ```
+    #[track_caller]
+    fn index_or_panic(&self, index: I) -> &Self::Output;
+}
+
+impl<T, I> BUIndex<I> for [T]
+where
+    I: SliceIndex<[T]> + Clone + Debug,
+{
+    type Output = I::Output;
+
+    #[track_caller]
+    fn index_or_panic(&self, index: I) -> &I::Output {
+        match self.get(index.clone()) {
+            Some(output) => output,
+            None => policy::fail(format_args!(
+                "index {index:?} out of range for slice of length {}",
+                self.len(),
+                index = Truncated(&index)
+            )),
+        }
+    }
+}
+
+impl<I> BUIndex<I> for str
+where
+    I: SliceIndex<str> + Clone + Debug,
+{
+    type Output = I::Output;
+
+    #[track_caller]
+    fn index_or_panic(&self, index: I) -> &I::Output {
+        match self.get(index.clone()) {
+            Some(output) => output,
+            None => policy::fail(format_args!(
+                "byte index {index:?} out of range or not on a char boundary for str of length {}",
+                self.len(),
+                index = Truncated(&index)
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_index_or_panic_with_vec() {
+        let values: Vec<u32> = (1..=3).map(|n| n * 10).collect();
+        assert_eq!(*values.index_or_panic(2), 30);
+        assert_eq!(values.index_or_panic(..2), &[10, 20]);
+    }
+
+    #[test]
+    #[should_panic(expected = "index 12 out of range for slice of length 3")]
+    fn test_index_or_panic_panics_on_out_of_range_index() {
+        let values = [10, 20, 30];
+        let _ = values.index_or_panic(12);
+    }
+
+    #[test]
+    fn test_index_or_panic_with_str() {
+        let text = String::from("hello");
+        assert_eq!(text.index_or_panic(1..3), "el");
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "byte index 0..1 out of range or not on a char boundary for str of length 2"
+    )]
+    fn test_index_or_panic_panics_off_char_boundary() {
+        let _ = "é".index_or_panic(0..1);
+    }
+}
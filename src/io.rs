@@ -0,0 +1,134 @@
+//! Alternatives to `unwrap()` for `io::Result`, with panic messages built for I/O errors.
+
+use std::fmt;
+use std::io::{self, ErrorKind};
+
+use crate::policy;
+
+/// Trait that provides alternatives to `unwrap()` for `std::io::Result<T>`.
+///
+/// `unwrap()` prints an `io::Error` with `Debug`, as in `Os { code: 2, kind: NotFound, message: "No
+/// such file or directory" }`; these methods print its `Display` text and kind instead.
+pub trait BUIoResult<T> {
+    /// Unwraps an I/O result, yielding the content of an `Ok`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is an `Err`, with a panic message including the error formatted using
+    /// `Display`, which ends with the raw OS error code for OS errors, and its [`ErrorKind`].
+    ///
+    /// # Examples
+    ///
+    /// ```should_panic
+    /// use std::fs;
+    /// use better_unwrap::BUIoResult;
+    ///
+    /// fs::read_to_string("/nonexistent/app.toml").or_panic_io(); // panics with `"called `or_panic_io()` on an `Err` value: No such file or directory (os error 2) [kind: NotFound]"`
+    /// ```
+    #[track_caller]
+    fn or_panic_io(self) -> T;
+
+    /// Unwraps an I/O result into an `Option`, treating errors of `kind` as `None`.
+    ///
+    /// For errors that are expected and handled, such as a missing optional file.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is an `Err` of another kind, with the same panic message as
+    /// [`or_panic_io()`](Self::or_panic_io).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::fs;
+    /// use std::io::ErrorKind;
+    /// use better_unwrap::BUIoResult;
+    ///
+    /// let overrides = fs::read_to_string("/nonexistent/overrides.toml")
+    ///     .or_panic_io_if_not(ErrorKind::NotFound);
+    /// assert_eq!(overrides, None);
+    /// ```
+    #[track_caller]
+    fn or_panic_io_if_not(self, kind: ErrorKind) -> Option<T>;
+}
+
+impl<T> BUIoResult<T> for io::Result<T> {
+    #[track_caller]
+    fn or_panic_io(self) -> T {
+        match self {
+            Ok(value) => value,
+            Err(error) => fail("or_panic_io", &error),
+        }
+    }
+
+    #[track_caller]
+    fn or_panic_io_if_not(self, kind: ErrorKind) -> Option<T> {
+        match self {
+            Ok(value) => Some(value),
+            Err(error) if error.kind() == kind => None,
+            Err(error) => fail("or_panic_io_if_not", &error),
+        }
+    }
+}
+
+#[track_caller]
+fn fail(method: &'static str, error: &io::Error) -> ! {
+    policy::fail_detailed(
+        method,
+        Some(error),
+        format_args!("called `{method}()` on an `Err` value: {}", IoError(error)),
+    )
+}
+
+/// Renders an I/O error as its `Display` text followed by its kind.
+struct IoError<'a>(&'a io::Error);
+
+impl fmt::Display for IoError<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} [kind: {:?}]", self.0, self.0.kind())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_or_panic_io_with_ok() {
+        assert_eq!(Ok(3).or_panic_io(), 3);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "called `or_panic_io()` on an `Err` value: lock file is held [kind: WouldBlock]"
+    )]
+    fn test_or_panic_io_shows_display_and_kind() {
+        let result: io::Result<()> =
+            Err(io::Error::new(ErrorKind::WouldBlock, "lock file is held"));
+        result.or_panic_io();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    #[should_panic(expected = "(os error 13) [kind: PermissionDenied]")]
+    fn test_or_panic_io_shows_os_error_code() {
+        let denied: io::Result<u32> = Err(io::Error::from_raw_os_error(libc::EACCES));
+        denied.or_panic_io();
+    }
+
+    #[test]
+    fn test_or_panic_io_if_not_skips_kind() {
+        let missing: io::Result<u32> = Err(ErrorKind::NotFound.into());
+        assert_eq!(missing.or_panic_io_if_not(ErrorKind::NotFound), None);
+        assert_eq!(Ok(4).or_panic_io_if_not(ErrorKind::NotFound), Some(4));
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "called `or_panic_io_if_not()` on an `Err` value: permission denied [kind: PermissionDenied]"
+    )]
+    fn test_or_panic_io_if_not_panics_on_other_kinds() {
+        let denied: io::Result<u32> = Err(ErrorKind::PermissionDenied.into());
+        denied.or_panic_io_if_not(ErrorKind::NotFound);
+    }
+}
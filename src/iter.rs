@@ -0,0 +1,495 @@
+use std::any;
+use std::fmt::Debug;
+use std::iter::Peekable;
+
+use crate::failure_report::{FailureReport, IndexedError};
+use crate::message::Truncated;
+use crate::policy;
+
+/// Trait that provides alternatives to `peek().unwrap()` for `Peekable` iterators.
+///
+/// Hand-written parsers peek at nearly every token; this trait gives those calls a clear name and an
+/// end-of-input message.
+pub trait BUPeekable<I: Iterator> {
+    /// Returns a reference to the next item without advancing the iterator.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the iterator is exhausted.
+    ///
+    /// # Examples
+    ///
+    /// ```should_panic
+    /// use better_unwrap::BUPeekable;
+    ///
+    /// let mut tokens = std::iter::empty::<char>().peekable();
+    /// tokens.peek_or_panic(); // panics with `"called `peek_or_panic()` at end of input"`
+    /// ```
+    #[track_caller]
+    fn peek_or_panic(&mut self) -> &I::Item;
+
+    /// Returns a reference to the next item, or the provided default at end of input.
+    ///
+    /// Equivalent to `peek().unwrap_or(default)`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use better_unwrap::BUPeekable;
+    ///
+    /// let mut tokens = "a".chars().peekable();
+    /// assert_eq!(*tokens.peek_or(&'\0'), 'a');
+    /// tokens.next();
+    /// assert_eq!(*tokens.peek_or(&'\0'), '\0');
+    /// ```
+    fn peek_or<'a>(&'a mut self, default: &'a I::Item) -> &'a I::Item;
+}
+
+impl<I: Iterator> BUPeekable<I> for Peekable<I> {
+    #[track_caller]
+    fn peek_or_panic(&mut self) -> &I::Item {
+        match self.peek() {
+            Some(item) => item,
+            None => policy::fail(format_args!("called `peek_or_panic()` at end of input")),
+        }
+    }
+
+    fn peek_or<'a>(&'a mut self, default: &'a I::Item) -> &'a I::Item {
+        self.peek().unwrap_or(default)
+    }
+}
+
+/// Trait that provides alternatives to `unwrap()`-based patterns on iterators.
+pub trait BUIterator: Iterator {
+    /// Advances the iterator and returns the next item.
+    ///
+    /// Equivalent to `next().unwrap()`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the iterator is exhausted, with a panic message including the iterator's type name.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use better_unwrap::BUIterator;
+    ///
+    /// let mut fields = "GET /index.html".split(' ');
+    /// assert_eq!(fields.next_or_panic(), "GET");
+    /// assert_eq!(fields.next_or_panic(), "/index.html");
+    /// ```
+    ///
+    /// ```should_panic
+    /// use better_unwrap::BUIterator;
+    ///
+    /// let mut values = std::iter::empty::<u32>();
+    /// values.next_or_panic(); // panics with `"called `next_or_panic()` on an exhausted iterator of type `core::iter::sources::empty::Empty<u32>`"`
+    /// ```
+    #[track_caller]
+    fn next_or_panic(&mut self) -> Self::Item;
+
+    /// Returns the only item of an iterator that must yield exactly one item.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the iterator yields no items, or more than one, with a panic message including the
+    /// first two items formatted using `Debug`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use better_unwrap::BUIterator;
+    ///
+    /// let users = ["alice", "bob"];
+    /// assert_eq!(users.iter().filter(|u| u.starts_with('b')).single_or_panic(), &"bob");
+    /// ```
+    ///
+    /// ```should_panic
+    /// use better_unwrap::BUIterator;
+    ///
+    /// let users = ["alice", "bob", "bea"];
+    /// users.iter().filter(|u| u.starts_with('b')).single_or_panic(); // panics with `"expected exactly one element, found at least 2 (first two: \"bob\", \"bea\")"`
+    /// ```
+    #[track_caller]
+    fn single_or_panic(self) -> Self::Item
+    where
+        Self: Sized,
+        Self::Item: Debug;
+
+    /// Collects an iterator of `Option<T>` into a collection of `T`.
+    ///
+    /// Equivalent to `collect::<Option<C>>().unwrap()`, but the panic message reports where the first
+    /// `None` was found.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any item is `None`, with a panic message including the index of the first `None` and
+    /// the number of items seen.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use better_unwrap::BUIterator;
+    ///
+    /// let values: Vec<u32> = vec![Some(1), Some(2)].into_iter().collect_some_or_panic();
+    /// assert_eq!(values, vec![1, 2]);
+    /// ```
+    ///
+    /// ```should_panic
+    /// use better_unwrap::BUIterator;
+    ///
+    /// let values: Vec<u32> = vec![Some(1), None].into_iter().collect_some_or_panic(); // panics with `"called `collect_some_or_panic()` on an iterator that yielded `None` at index 1 (2 items seen)"`
+    /// ```
+    #[track_caller]
+    fn collect_some_or_panic<T, C>(self) -> C
+    where
+        Self: Iterator<Item = Option<T>> + Sized,
+        C: FromIterator<T>;
+
+    /// Collects an iterator of `Result<T, E>` into a collection of `T`.
+    ///
+    /// Equivalent to `collect::<Result<C, E>>().unwrap()`, but the panic message reports where the
+    /// first `Err` was found.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any item is an `Err`, with a panic message including the index of the first `Err`
+    /// and its error value formatted using `Debug`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use better_unwrap::BUIterator;
+    ///
+    /// let ports: Vec<u16> = ["80", "443"].iter().map(|s| s.parse()).collect_or_panic();
+    /// assert_eq!(ports, vec![80, 443]);
+    /// ```
+    ///
+    /// ```should_panic
+    /// use better_unwrap::BUIterator;
+    ///
+    /// let ports: Vec<u16> = ["80", "44x"].iter().map(|s| s.parse()).collect_or_panic(); // panics with `"called `collect_or_panic()` on an iterator that yielded an `Err` value at index 1: ParseIntError { kind: InvalidDigit }"`
+    /// ```
+    #[track_caller]
+    fn collect_or_panic<T, E, C>(self) -> C
+    where
+        Self: Iterator<Item = Result<T, E>> + Sized,
+        E: Debug,
+        C: FromIterator<T>;
+
+    /// Drains an iterator of `Result<T, E>`, separating the `Ok` values from the errors.
+    ///
+    /// The errors are collected into a [`FailureReport`] that records the index of each failing item,
+    /// and can be panicked on, logged, or inspected.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use better_unwrap::BUIterator;
+    ///
+    /// let rows = vec![Ok(1), Err("bad row"), Ok(3)];
+    /// let (values, report) = rows.into_iter().partition_report();
+    /// assert_eq!(values, vec![1, 3]);
+    /// assert_eq!(report.len(), 1);
+    /// ```
+    fn partition_report<T, E>(self) -> (Vec<T>, FailureReport<E>)
+    where
+        Self: Iterator<Item = Result<T, E>> + Sized;
+
+    /// Collects an iterator of `Result<T, E>` into a collection of `T`, or returns every error.
+    ///
+    /// Unlike `collect::<Result<C, E>>()`, which stops at the first `Err`, the whole iterator is
+    /// drained, so that all the problems of a config file or a batch of rows are reported at once.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`FailureReport`] with the index and error of every failing item if any item is an
+    /// `Err`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use better_unwrap::BUIterator;
+    ///
+    /// let rows = vec![Ok(1), Err("missing name"), Ok(3), Err("bad date")];
+    /// let report = rows.into_iter().try_collect_all::<_, _, Vec<u32>>().unwrap_err();
+    /// assert_eq!(report.indices().collect::<Vec<_>>(), vec![1, 3]);
+    /// ```
+    fn try_collect_all<T, E, C>(self) -> Result<C, FailureReport<E>>
+    where
+        Self: Iterator<Item = Result<T, E>> + Sized,
+        C: FromIterator<T>;
+
+    /// Collects an iterator of `Result<T, E>` into a collection of `T`, or panics with every error.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any item is an `Err`, with a panic message listing the index and error (formatted
+    /// using `Debug`) of every failing item.
+    ///
+    /// # Examples
+    ///
+    /// ```should_panic
+    /// use better_unwrap::BUIterator;
+    ///
+    /// let rows = vec![Ok(1), Err("missing name"), Ok(3), Err("bad date")];
+    /// let values: Vec<u32> = rows.into_iter().collect_all_or_panic(); // panics with `"called `collect_all_or_panic()` on an iterator with failures: 2 of 4 items failed:\n  [1] \"missing name\"\n  [3] \"bad date\""`
+    /// ```
+    #[track_caller]
+    fn collect_all_or_panic<T, E, C>(self) -> C
+    where
+        Self: Iterator<Item = Result<T, E>> + Sized,
+        E: Debug,
+        C: FromIterator<T>;
+}
+
+impl<I: Iterator> BUIterator for I {
+    #[track_caller]
+    fn next_or_panic(&mut self) -> I::Item {
+        match self.next() {
+            Some(item) => item,
+            None => policy::fail(format_args!(
+                "called `next_or_panic()` on an exhausted iterator of type `{}`",
+                any::type_name::<I>()
+            )),
+        }
+    }
+
+    #[track_caller]
+    fn single_or_panic(mut self) -> I::Item
+    where
+        I::Item: Debug,
+    {
+        let Some(first) = self.next() else {
+            policy::fail(format_args!("expected exactly one element, found 0"));
+        };
+        match self.next() {
+            None => first,
+            Some(second) => policy::fail(format_args!(
+                "expected exactly one element, found at least 2 (first two: {first:?}, {second:?})",
+                first = Truncated(&first),
+                second = Truncated(&second)
+            )),
+        }
+    }
+
+    #[track_caller]
+    fn collect_some_or_panic<T, C>(self) -> C
+    where
+        Self: Iterator<Item = Option<T>> + Sized,
+        C: FromIterator<T>,
+    {
+        let mut first_none = None;
+        let collected = self
+            .enumerate()
+            .map_while(|(index, item)| {
+                if item.is_none() {
+                    first_none = Some(index);
+                }
+                item
+            })
+            .collect();
+        match first_none {
+            None => collected,
+            Some(index) => policy::fail(format_args!(
+                "called `collect_some_or_panic()` on an iterator that yielded `None` at index {index} ({} items seen)",
+                index + 1
+            )),
+        }
+    }
+
+    #[track_caller]
+    fn collect_or_panic<T, E, C>(self) -> C
+    where
+        Self: Iterator<Item = Result<T, E>> + Sized,
+        E: Debug,
+        C: FromIterator<T>,
+    {
+        let mut first_err = None;
+        let collected = self
+            .enumerate()
+            .map_while(|(index, item)| match item {
+                Ok(value) => Some(value),
+                Err(error) => {
+                    first_err = Some((index, error));
+                    None
+                }
+            })
+            .collect();
+        match first_err {
+            None => collected,
+            Some((index, error)) => policy::fail_detailed(
+                "collect_or_panic",
+                Some(&error),
+                format_args!(
+                    "called `collect_or_panic()` on an iterator that yielded an `Err` value at index {index}: {error:?}",
+                    error = Truncated(&error)
+                ),
+            ),
+        }
+    }
+
+    fn partition_report<T, E>(self) -> (Vec<T>, FailureReport<E>)
+    where
+        Self: Iterator<Item = Result<T, E>> + Sized,
+    {
+        let mut values = Vec::new();
+        let mut failures = Vec::new();
+        let mut total = 0;
+        for (index, item) in self.enumerate() {
+            total += 1;
+            match item {
+                Ok(value) => values.push(value),
+                Err(error) => failures.push(IndexedError { index, error }),
+            }
+        }
+        (values, FailureReport::new(total, failures))
+    }
+
+    fn try_collect_all<T, E, C>(self) -> Result<C, FailureReport<E>>
+    where
+        Self: Iterator<Item = Result<T, E>> + Sized,
+        C: FromIterator<T>,
+    {
+        let (values, report) = self.partition_report();
+        match report.is_empty() {
+            true => Ok(values.into_iter().collect()),
+            false => Err(report),
+        }
+    }
+
+    #[track_caller]
+    fn collect_all_or_panic<T, E, C>(self) -> C
+    where
+        Self: Iterator<Item = Result<T, E>> + Sized,
+        E: Debug,
+        C: FromIterator<T>,
+    {
+        match self.try_collect_all() {
+            Ok(collected) => collected,
+            Err(report) => policy::fail(format_args!(
+                "called `collect_all_or_panic()` on an iterator with failures: {report}"
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::BUResult;
+
+    #[test]
+    fn test_peek_or_panic_does_not_advance() {
+        let mut tokens = [1, 2].into_iter().peekable();
+        assert_eq!(*tokens.peek_or_panic(), 1);
+        assert_eq!(tokens.next(), Some(1));
+    }
+
+    #[test]
+    #[should_panic(expected = "called `peek_or_panic()` at end of input")]
+    fn test_peek_or_panic_panics_at_end() {
+        let mut tokens = std::iter::empty::<u32>().peekable();
+        let _ = tokens.peek_or_panic();
+    }
+
+    #[test]
+    fn test_peek_or_at_end() {
+        let mut tokens = std::iter::empty::<u32>().peekable();
+        assert_eq!(*tokens.peek_or(&0), 0);
+    }
+
+    #[test]
+    fn test_next_or_panic_advances() {
+        let mut values = [1, 2].into_iter();
+        assert_eq!(values.next_or_panic(), 1);
+        assert_eq!(values.next_or_panic(), 2);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "called `next_or_panic()` on an exhausted iterator of type `core::array::iter::IntoIter<u32, 0>`"
+    )]
+    fn test_next_or_panic_panics_with_type_name() {
+        let mut values = ([] as [u32; 0]).into_iter();
+        let _ = values.next_or_panic();
+    }
+
+    #[test]
+    fn test_single_or_panic_with_one_item() {
+        assert_eq!([7].into_iter().single_or_panic(), 7);
+    }
+
+    #[test]
+    #[should_panic(expected = "expected exactly one element, found 0")]
+    fn test_single_or_panic_panics_on_empty() {
+        let _ = std::iter::empty::<u32>().single_or_panic();
+    }
+
+    #[test]
+    #[should_panic(expected = "expected exactly one element, found at least 2 (first two: 1, 2)")]
+    fn test_single_or_panic_panics_on_many() {
+        let _ = [1, 2, 3].into_iter().single_or_panic();
+    }
+
+    #[test]
+    fn test_collect_some_or_panic_with_all_some() {
+        let values: Vec<u32> = [Some(1), Some(2), Some(3)]
+            .into_iter()
+            .collect_some_or_panic();
+        assert_eq!(values, vec![1, 2, 3]);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "called `collect_some_or_panic()` on an iterator that yielded `None` at index 2 (3 items seen)"
+    )]
+    fn test_collect_some_or_panic_panics_with_index() {
+        let _: Vec<u32> = [Some(1), Some(2), None, Some(4)]
+            .into_iter()
+            .collect_some_or_panic();
+    }
+
+    #[test]
+    fn test_collect_or_panic_with_all_ok() {
+        let values: Vec<u32> = [Ok::<_, &str>(1), Ok(2)].into_iter().collect_or_panic();
+        assert_eq!(values, vec![1, 2]);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "called `collect_or_panic()` on an iterator that yielded an `Err` value at index 1: \"bad row\""
+    )]
+    fn test_collect_or_panic_panics_with_index_and_error() {
+        let _: Vec<u32> = [Ok(1), Err("bad row"), Err("worse row")]
+            .into_iter()
+            .collect_or_panic();
+    }
+
+    #[test]
+    fn test_try_collect_all_returns_every_value() {
+        let values: Result<Vec<u32>, _> = [Ok::<_, &str>(1), Ok(2)].into_iter().try_collect_all();
+        assert_eq!(values, Ok(vec![1, 2]));
+    }
+
+    #[test]
+    fn test_try_collect_all_returns_every_error() {
+        let rows = [Err("first"), Ok(2), Err("third")];
+        let report = rows
+            .into_iter()
+            .try_collect_all::<u32, _, Vec<_>>()
+            .or_panic_err();
+        assert_eq!(report.total(), 3);
+        assert_eq!(report.indices().collect::<Vec<_>>(), vec![0, 2]);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "called `collect_all_or_panic()` on an iterator with failures: 2 of 3 items failed:\n  [0] \"first\"\n  [2] \"third\""
+    )]
+    fn test_collect_all_or_panic_lists_every_error() {
+        let _: Vec<u32> = [Err("first"), Ok(2), Err("third")]
+            .into_iter()
+            .collect_all_or_panic();
+    }
+}
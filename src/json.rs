@@ -0,0 +1,283 @@
+//! Alternatives to `unwrap()` for exploring `serde_json::Value`s, with panic messages that say what
+//! was expected and preview what was found.
+//!
+//! Available with the `serde_json` feature.
+//!
+//! # Examples
+//!
+//! ```should_panic
+//! use better_unwrap::json::BUJson;
+//! use serde_json::json;
+//!
+//! let response = json!({ "user": { "id": "42", "name": "ada" } });
+//! let id = response.pointer_or_panic("/user/id").as_u64_or_panic(); // panics with `"expected a JSON unsigned integer, found a string: \"42\""`
+//! ```
+
+use std::fmt;
+
+use serde_json::{Map, Value};
+
+use crate::policy;
+
+/// How many characters of a value a preview shows before it is cut off.
+const PREVIEW_LEN: usize = 60;
+
+/// Trait that provides alternatives to `pointer(..).unwrap()` and `as_*().unwrap()` for
+/// `serde_json::Value`.
+pub trait BUJson {
+    /// Looks up a value by JSON pointer, such as `"/user/id"`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is no value at `pointer`, with a panic message including the pointer and a
+    /// preview of the deepest value that exists along it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use better_unwrap::json::BUJson;
+    /// use serde_json::json;
+    ///
+    /// let response = json!({ "user": { "id": 42 } });
+    /// assert_eq!(response.pointer_or_panic("/user/id"), 42);
+    /// ```
+    ///
+    /// ```should_panic
+    /// use better_unwrap::json::BUJson;
+    /// use serde_json::json;
+    ///
+    /// let response = json!({ "user": { "name": "ada" } });
+    /// response.pointer_or_panic("/user/id"); // panics with `"no JSON value at `/user/id`; `/user` is {\"name\":\"ada\"}"`
+    /// ```
+    #[track_caller]
+    fn pointer_or_panic(&self, pointer: &str) -> &Value;
+
+    /// Returns the string of a JSON string.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is not a string, with a panic message including a preview of the value.
+    #[track_caller]
+    fn as_str_or_panic(&self) -> &str;
+
+    /// Returns the number of a JSON number that fits in a `u64`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is not an unsigned integer, with a panic message including a preview of
+    /// the value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use better_unwrap::json::BUJson;
+    /// use serde_json::json;
+    ///
+    /// assert_eq!(json!(8080).as_u64_or_panic(), 8080);
+    /// ```
+    #[track_caller]
+    fn as_u64_or_panic(&self) -> u64;
+
+    /// Returns the number of a JSON number that fits in an `i64`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is not an integer, with a panic message including a preview of the value.
+    #[track_caller]
+    fn as_i64_or_panic(&self) -> i64;
+
+    /// Returns the number of a JSON number as an `f64`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is not a number, with a panic message including a preview of the value.
+    #[track_caller]
+    fn as_f64_or_panic(&self) -> f64;
+
+    /// Returns the boolean of a JSON boolean.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is not a boolean, with a panic message including a preview of the value.
+    #[track_caller]
+    fn as_bool_or_panic(&self) -> bool;
+
+    /// Returns the elements of a JSON array.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is not an array, with a panic message including a preview of the value.
+    #[track_caller]
+    fn as_array_or_panic(&self) -> &Vec<Value>;
+
+    /// Returns the members of a JSON object.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is not an object, with a panic message including a preview of the value.
+    #[track_caller]
+    fn as_object_or_panic(&self) -> &Map<String, Value>;
+}
+
+impl BUJson for Value {
+    #[track_caller]
+    fn pointer_or_panic(&self, pointer: &str) -> &Value {
+        if let Some(value) = self.pointer(pointer) {
+            return value;
+        }
+        let mut prefix = pointer;
+        while let Some((parent, _)) = prefix.rsplit_once('/') {
+            prefix = parent;
+            match self.pointer(prefix) {
+                Some(found) if prefix.is_empty() => policy::fail(format_args!(
+                    "no JSON value at `{pointer}`; the document is {}",
+                    Preview(found)
+                )),
+                Some(found) => policy::fail(format_args!(
+                    "no JSON value at `{pointer}`; `{prefix}` is {}",
+                    Preview(found)
+                )),
+                None => {}
+            }
+        }
+        policy::fail(format_args!(
+            "no JSON value at `{pointer}`, which is not a valid JSON pointer"
+        ))
+    }
+
+    #[track_caller]
+    fn as_str_or_panic(&self) -> &str {
+        match self.as_str() {
+            Some(value) => value,
+            None => mismatch("a JSON string", self),
+        }
+    }
+
+    #[track_caller]
+    fn as_u64_or_panic(&self) -> u64 {
+        match self.as_u64() {
+            Some(value) => value,
+            None => mismatch("a JSON unsigned integer", self),
+        }
+    }
+
+    #[track_caller]
+    fn as_i64_or_panic(&self) -> i64 {
+        match self.as_i64() {
+            Some(value) => value,
+            None => mismatch("a JSON integer", self),
+        }
+    }
+
+    #[track_caller]
+    fn as_f64_or_panic(&self) -> f64 {
+        match self.as_f64() {
+            Some(value) => value,
+            None => mismatch("a JSON number", self),
+        }
+    }
+
+    #[track_caller]
+    fn as_bool_or_panic(&self) -> bool {
+        match self.as_bool() {
+            Some(value) => value,
+            None => mismatch("a JSON boolean", self),
+        }
+    }
+
+    #[track_caller]
+    fn as_array_or_panic(&self) -> &Vec<Value> {
+        match self.as_array() {
+            Some(value) => value,
+            None => mismatch("a JSON array", self),
+        }
+    }
+
+    #[track_caller]
+    fn as_object_or_panic(&self) -> &Map<String, Value> {
+        match self.as_object() {
+            Some(value) => value,
+            None => mismatch("a JSON object", self),
+        }
+    }
+}
+
+#[track_caller]
+fn mismatch(expected: &str, found: &Value) -> ! {
+    let kind = match found {
+        Value::Null => "null",
+        Value::Bool(_) => "a boolean",
+        Value::Number(_) => "a number",
+        Value::String(_) => "a string",
+        Value::Array(_) => "an array",
+        Value::Object(_) => "an object",
+    };
+    policy::fail(format_args!(
+        "expected {expected}, found {kind}: {}",
+        Preview(found)
+    ))
+}
+
+/// Renders a value as compact JSON, cut off after [`PREVIEW_LEN`] characters.
+struct Preview<'a>(&'a Value);
+
+impl fmt::Display for Preview<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let json = self.0.to_string();
+        match json.char_indices().nth(PREVIEW_LEN) {
+            Some((end, _)) => write!(f, "{}…", &json[..end]),
+            None => f.write_str(&json),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_accessors_return_values() {
+        let value = json!({ "id": 7, "offset": -3, "ratio": 0.5, "ok": true, "tags": ["a"], "name": "ada" });
+        assert_eq!(value.pointer_or_panic("/id").as_u64_or_panic(), 7);
+        assert_eq!(value.pointer_or_panic("/offset").as_i64_or_panic(), -3);
+        assert_eq!(value.pointer_or_panic("/ratio").as_f64_or_panic(), 0.5);
+        assert!(value.pointer_or_panic("/ok").as_bool_or_panic());
+        assert_eq!(value.pointer_or_panic("/tags").as_array_or_panic().len(), 1);
+        assert_eq!(value.pointer_or_panic("/name").as_str_or_panic(), "ada");
+        assert_eq!(value.as_object_or_panic().len(), 6);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "no JSON value at `/user/address/city`; `/user` is {\"name\":\"ada\"}"
+    )]
+    fn test_pointer_or_panic_shows_deepest_existing_value() {
+        json!({ "user": { "name": "ada" } }).pointer_or_panic("/user/address/city");
+    }
+
+    #[test]
+    #[should_panic(expected = "no JSON value at `/user`; the document is []")]
+    fn test_pointer_or_panic_at_root() {
+        json!([]).pointer_or_panic("/user");
+    }
+
+    #[test]
+    #[should_panic(expected = "no JSON value at `user`, which is not a valid JSON pointer")]
+    fn test_pointer_or_panic_with_invalid_pointer() {
+        json!({ "user": 1 }).pointer_or_panic("user");
+    }
+
+    #[test]
+    #[should_panic(expected = "expected a JSON string, found null: null")]
+    fn test_as_str_or_panic_names_kind() {
+        Value::Null.as_str_or_panic();
+    }
+
+    #[test]
+    fn test_preview_is_cut_off() {
+        let preview = Preview(&json!("x".repeat(100))).to_string();
+        assert_eq!(preview.chars().count(), PREVIEW_LEN + 1);
+        assert!(preview.ends_with("xx…"));
+    }
+}
@@ -1,11 +1,267 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+extern crate self as better_unwrap;
+
+#[cfg(feature = "anyhow")]
+pub mod anyhow;
+#[cfg(feature = "std")]
+pub mod array;
+mod assert;
+#[cfg(feature = "std")]
+pub mod borrow;
+#[cfg(feature = "std")]
+pub mod budget;
+#[cfg(feature = "std")]
+pub mod cell;
+#[cfg(feature = "std")]
+pub mod channel;
+#[cfg(feature = "cli")]
+pub mod cli;
+#[cfg(feature = "std")]
+pub mod context;
+#[cfg(feature = "std")]
+pub mod convert;
+#[cfg(feature = "alloc")]
+mod diff;
+#[cfg(feature = "humantime")]
+pub mod duration;
+#[cfg(feature = "std")]
+mod emit;
+#[cfg(feature = "std")]
+pub mod env;
+#[cfg(feature = "eyre")]
+pub mod eyre;
+#[cfg(feature = "std")]
+pub mod failure_report;
+#[cfg(feature = "std")]
+pub mod ffi;
+#[cfg(feature = "std")]
+pub mod fs;
+#[cfg(feature = "async")]
+pub mod future;
+#[cfg(feature = "std")]
+mod hook;
+#[cfg(feature = "std")]
+pub mod index;
+#[cfg(feature = "std")]
+pub mod io;
+#[cfg(feature = "std")]
+pub mod iter;
+#[cfg(feature = "serde_json")]
+pub mod json;
+pub mod located;
+#[cfg(feature = "log")]
+pub mod logging;
+#[cfg(feature = "std")]
+pub mod map;
+#[cfg(feature = "std")]
+pub mod mem;
+pub mod message;
+#[cfg(feature = "miette")]
+pub mod miette;
+#[cfg(feature = "std")]
+pub mod num;
+#[cfg(feature = "std")]
+pub mod once;
+#[cfg(feature = "std")]
+pub mod ops;
 pub mod option;
+#[cfg(feature = "std")]
+pub mod payload;
+#[cfg(feature = "std")]
+pub mod policy;
+/// Without `std` there is no thread-local policy to consult, so every failure panics.
+#[cfg(not(feature = "std"))]
+mod policy {
+    #[track_caller]
+    pub(crate) fn fail(message: core::fmt::Arguments<'_>) -> ! {
+        panic!("{message}")
+    }
+
+    #[track_caller]
+    pub(crate) fn fail_detailed(
+        _method: &'static str,
+        _error: Option<&dyn core::fmt::Debug>,
+        message: core::fmt::Arguments<'_>,
+    ) -> ! {
+        panic!("{message}")
+    }
+}
+#[cfg(feature = "std")]
+pub mod process;
+#[cfg(feature = "std")]
+pub mod rc;
+#[cfg(feature = "std")]
+pub mod report;
 pub mod result;
+#[cfg(feature = "std")]
+pub mod retry;
+#[cfg(feature = "std")]
+pub mod slice;
+mod strict;
+#[cfg(feature = "std")]
+pub mod string;
+#[cfg(feature = "std")]
+pub mod sync;
+#[cfg(feature = "std")]
+pub mod task;
+#[cfg(feature = "std")]
+pub mod thread;
+#[cfg(feature = "std")]
+pub mod traced;
+
+#[cfg(feature = "std")]
+pub use array::BUArray;
+#[cfg(feature = "std")]
+pub use borrow::BUCow;
+#[cfg(feature = "std")]
+pub use cell::BURefCell;
+#[cfg(feature = "std")]
+pub use channel::{BUReceiver, BUSender};
+#[cfg(feature = "std")]
+pub use context::{BUContext, BUError};
+#[cfg(feature = "std")]
+pub use convert::BUTryInto;
+#[cfg(feature = "std")]
+pub use failure_report::{FailureReport, IndexedError};
+#[cfg(feature = "std")]
+pub use ffi::BUCStr;
+#[cfg(feature = "std")]
+pub use fs::{BUMetadata, PathContext};
+#[cfg(feature = "std")]
+pub use hook::install_panic_hook;
+#[cfg(feature = "std")]
+pub use index::BUIndex;
+#[cfg(feature = "std")]
+pub use io::BUIoResult;
+#[cfg(feature = "std")]
+pub use iter::{BUIterator, BUPeekable};
+pub use located::BULocatedError;
+#[cfg(feature = "std")]
+pub use map::BUMap;
+pub use message::{PanicMessage, set_max_error_len};
+#[cfg(feature = "std")]
+pub use num::{BUChecked, BUNonZero};
+#[cfg(feature = "std")]
+pub use once::BUOnce;
+#[cfg(feature = "std")]
+pub use ops::BUControlFlow;
+pub use option::{BUNestedOption, BUOption, BUOptionRef};
+#[cfg(feature = "std")]
+pub use payload::BUPanicInfo;
+#[cfg(feature = "std")]
+pub use process::BUExitStatus;
+#[cfg(feature = "std")]
+pub use rc::BUWeak;
+#[cfg(feature = "std")]
+pub use report::{RedactionHook, Report};
+#[cfg(feature = "alloc")]
+pub use result::BUMultiResult;
+pub use result::{BUResult, BUResultOption};
+#[cfg(feature = "std")]
+pub use retry::{retry, retry_or_panic};
+#[cfg(feature = "std")]
+pub use slice::BUSlice;
+#[cfg(feature = "std")]
+pub use string::{BUStr, BUUtf8};
+#[cfg(feature = "std")]
+pub use sync::{BUExchange, BULockResult, BUTryLockResult};
+#[cfg(feature = "std")]
+pub use task::{BUPoll, BUPollOption};
+#[cfg(feature = "std")]
+pub use thread::BUJoinHandle;
+#[cfg(feature = "std")]
+pub use traced::Traced;
+
+#[cfg(feature = "macros")]
+pub use better_unwrap_macros::{FromReprOrPanic, PanicMessage, forbid_unwrap, panic_policy};
 
-pub use option::BUOption;
-pub use result::BUResult;
+/// Unwraps an `Option` or `Result`, including the text of the expression in the panic message.
+///
+/// `or_panic!(expr)` behaves like `expr.or_panic()`, but the message names the expression that
+/// failed rather than only saying that a `None` or `Err` was unwrapped.
+///
+/// # Panics
+///
+/// Panics if the expression is `None` or an `Err`, with a panic message including the stringified
+/// expression and, for an `Err`, the error formatted using `Debug`.
+///
+/// # Examples
+///
+/// ```
+/// use better_unwrap::or_panic;
+///
+/// let port: u16 = or_panic!("8080".parse::<u16>());
+/// assert_eq!(port, 8080);
+/// ```
+///
+/// ```should_panic
+/// use better_unwrap::or_panic;
+///
+/// let ports = [8080, 8443];
+/// or_panic!(ports.get(2)); // panics with `"`ports.get(2)` was `None`"`
+/// ```
+#[macro_export]
+macro_rules! or_panic {
+    ($expr:expr $(,)?) => {
+        $crate::__private::OrPanicExpr::or_panic_expr($expr, ::core::stringify!($expr))
+    };
+}
+
+/// Items used by the code generated by the crate's macros. Not part of the public API.
+#[doc(hidden)]
+pub mod __private {
+    use core::fmt::{self, Debug};
+
+    #[track_caller]
+    pub fn fail(message: fmt::Arguments<'_>) -> ! {
+        crate::policy::fail(message)
+    }
+
+    /// Formats `value` using `Debug`, truncated to the length set with
+    /// [`set_max_error_len`](crate::set_max_error_len).
+    pub fn truncated<T: Debug + ?Sized>(value: &T) -> impl Debug + '_ {
+        crate::message::Truncated(value)
+    }
+
+    /// Unwrapping with the text of the unwrapped expression, as expanded by `or_panic!`.
+    pub trait OrPanicExpr<T> {
+        #[track_caller]
+        fn or_panic_expr(self, expr: &'static str) -> T;
+    }
+
+    impl<T> OrPanicExpr<T> for Option<T> {
+        #[track_caller]
+        fn or_panic_expr(self, expr: &'static str) -> T {
+            match self {
+                Some(value) => value,
+                None => fail(format_args!("`{expr}` was `None`")),
+            }
+        }
+    }
+
+    impl<T, E: Debug> OrPanicExpr<T> for Result<T, E> {
+        #[track_caller]
+        fn or_panic_expr(self, expr: &'static str) -> T {
+            match self {
+                Ok(value) => value,
+                Err(error) => fail(format_args!(
+                    "`{expr}` was an `Err` value: {error:?}",
+                    error = crate::message::Truncated(&error)
+                )),
+            }
+        }
+    }
+}
 
 /// A prelude for conveniently importing the traits.
 ///
+/// `prelude::*` imports every trait of the crate, which grows with new releases. Import a versioned
+/// prelude such as [`prelude::v1`] to pin the set of traits, or a targeted one such as
+/// [`prelude::collections`] to import only the traits for one area.
+///
 /// # Example
 ///
 /// ```
@@ -40,12 +296,159 @@ pub use result::BUResult;
 /// let error2 = err_result4.panic_err_with("Expected an error");
 /// ```
 pub mod prelude {
-    pub use crate::{BUOption, BUResult};
+    #[cfg(not(feature = "std"))]
+    pub use self::v1::*;
+    #[cfg(feature = "std")]
+    pub use self::v3::*;
+
+    /// The traits exported by the prelude of `better_unwrap` 1.0: [`BUOption`] and
+    /// [`BUResult`].
+    ///
+    /// This set never changes, so a glob import of it cannot start colliding with downstream names
+    /// when the crate adds traits.
+    pub mod v1 {
+        pub use crate::{BUOption, BUResult};
+    }
+
+    /// The traits exported by the prelude when versioned preludes were introduced: [`v1`] plus the
+    /// traits for arrays, indexing, iterators, multi-error results, `Cow`, C strings, exit statuses,
+    /// file metadata, and channel receivers.
+    ///
+    /// This set never changes.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use better_unwrap::prelude::v2::*;
+    ///
+    /// let values = [Some(1), Some(2)];
+    /// assert_eq!(values.into_iter().collect_some_or_panic::<u32, Vec<_>>(), vec![1, 2]);
+    /// ```
+    #[cfg(feature = "std")]
+    pub mod v2 {
+        pub use crate::{
+            BUArray, BUCStr, BUCow, BUExitStatus, BUIndex, BUIterator, BUMetadata, BUMultiResult,
+            BUOption, BUPeekable, BUReceiver, BUResult,
+        };
+    }
+
+    /// The extension traits re-exported by the unversioned prelude.
+    ///
+    /// This set never changes: traits added later go to the unversioned prelude and a later
+    /// versioned prelude.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use better_unwrap::prelude::v3::*;
+    ///
+    /// let hits = std::sync::Mutex::new(0);
+    /// *hits.lock().lock_or_panic() += 1;
+    /// ```
+    #[cfg(feature = "std")]
+    pub mod v3 {
+        pub use crate::{
+            BUArray, BUCStr, BUChecked, BUControlFlow, BUCow, BUExchange, BUExitStatus, BUIndex,
+            BUIoResult, BUIterator, BUJoinHandle, BULockResult, BUMap, BUMetadata, BUMultiResult,
+            BUNestedOption, BUNonZero, BUOnce, BUOption, BUOptionRef, BUPeekable, BUPoll,
+            BUPollOption, BUReceiver, BURefCell, BUResult, BUResultOption, BUSender, BUSlice,
+            BUStr, BUTryInto, BUTryLockResult, BUWeak, PathContext,
+        };
+    }
+
+    // Not named `core`: glob-importing the prelude would then shadow the `core` crate.
+    /// The traits for `Option`, `Result`, `ControlFlow`, integer arithmetic, and conversions.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use better_unwrap::prelude::base::*;
+    ///
+    /// let port: Result<u16, &str> = Ok(8080);
+    /// assert_eq!(port.or_panic(), 8080);
+    /// ```
+    #[cfg(feature = "std")]
+    pub mod base {
+        pub use crate::{
+            BUChecked, BUControlFlow, BUMultiResult, BUNestedOption, BUNonZero, BUOption,
+            BUOptionRef, BUResult, BUResultOption, BUTryInto,
+        };
+    }
+
+    /// The traits for arrays, slices, strings, maps, iterators, and `Cow`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use better_unwrap::prelude::collections::*;
+    ///
+    /// let ports = [8080, 8443];
+    /// assert_eq!(*ports.index_or_panic(1), 8443);
+    /// ```
+    #[cfg(feature = "std")]
+    pub mod collections {
+        pub use crate::{
+            BUArray, BUCow, BUIndex, BUIterator, BUMap, BUPeekable, BUSlice, BUStr, BUUtf8,
+        };
+    }
+
+    /// The traits for locks, atomics, cells, channels, threads, weak references, and polling.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::sync::mpsc;
+    /// use std::time::Duration;
+    /// use better_unwrap::prelude::sync::*;
+    ///
+    /// let (tx, rx) = mpsc::channel();
+    /// tx.send(42).ok();
+    /// assert_eq!(rx.recv_timeout_or_panic(Duration::from_secs(1), "worker result"), 42);
+    /// ```
+    #[cfg(feature = "std")]
+    pub mod sync {
+        pub use crate::{
+            BUExchange, BUJoinHandle, BULockResult, BUOnce, BUPoll, BUPollOption, BUReceiver,
+            BURefCell, BUSender, BUTryLockResult, BUWeak,
+        };
+    }
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "std"))]
 mod tests {
     use super::*;
+    use std::cell::RefCell;
+    use std::panic::{self, UnwindSafe};
+    use std::sync::Once;
+
+    thread_local! {
+        static PANIC_LOCATION: RefCell<Option<(String, u32)>> = const { RefCell::new(None) };
+    }
+
+    /// Runs `f`, which must panic, and returns the file and line the panic was reported at.
+    fn panic_location<R>(f: impl FnOnce() -> R + UnwindSafe) -> (String, u32) {
+        static HOOK: Once = Once::new();
+        HOOK.call_once(|| {
+            let previous = panic::take_hook();
+            panic::set_hook(Box::new(move |info| {
+                if let Some(location) = info.location() {
+                    PANIC_LOCATION.set(Some((location.file().to_owned(), location.line())));
+                }
+                previous(info);
+            }));
+        });
+        assert!(panic::catch_unwind(f).is_err(), "expected a panic");
+        PANIC_LOCATION.take().or_panic()
+    }
+
+    /// Runs `f`, which must panic, and returns its panic message.
+    fn panic_message<R>(f: impl FnOnce() -> R + UnwindSafe) -> String {
+        *panic::catch_unwind(f)
+            .err()
+            .or_panic()
+            .downcast::<String>()
+            .or_panic()
+    }
 
     #[test]
     fn test_or_panic_with_ok() {
@@ -85,12 +488,128 @@ mod tests {
         let _ = option.or_panic();
     }
 
+    #[test]
+    fn test_or_panic_display_with_ok() {
+        let result: Result<u32, std::num::ParseIntError> = "42".parse::<u32>();
+        assert_eq!(result.or_panic_display(), 42);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "called `or_panic_display()` on an `Err` value: invalid digit found in string"
+    )]
+    fn test_or_panic_display_panics_with_display_form() {
+        let result: Result<u32, std::num::ParseIntError> = "4x".parse::<u32>();
+        let _ = result.or_panic_display();
+    }
+
+    #[test]
+    fn test_or_panic_ref_and_mut_with_some() {
+        let mut option: Option<Vec<u32>> = Some(vec![1]);
+        option.or_panic_mut().push(2);
+        assert_eq!(option.or_panic_ref(), &[1, 2]);
+    }
+
+    #[test]
+    #[should_panic(expected = "called `or_panic_ref()` on a `None` value")]
+    fn test_or_panic_ref_panics_on_none() {
+        let option: Option<u32> = None;
+        let _ = option.or_panic_ref();
+    }
+
+    #[test]
+    fn test_or_panic_ref_and_mut_with_ok() {
+        let mut result: Result<Vec<u32>, &str> = Ok(vec![1]);
+        result.or_panic_mut().push(2);
+        assert_eq!(result.or_panic_ref(), &[1, 2]);
+    }
+
+    #[test]
+    #[should_panic(expected = "called `or_panic_mut()` on an `Err` value: \"error\"")]
+    fn test_or_panic_mut_panics_on_err() {
+        let mut result: Result<u32, &str> = Err("error");
+        let _ = result.or_panic_mut();
+    }
+
+    #[test]
+    fn test_copied_and_cloned_or_panic_with_some() {
+        let ports = std::collections::HashMap::from([("https", 443)]);
+        assert_eq!(ports.get("https").copied_or_panic(), 443);
+        let names = ["primary".to_string()];
+        assert_eq!(names.first().cloned_or_panic(), "primary");
+    }
+
+    #[test]
+    fn test_some_or_panic_with_some() {
+        assert_eq!(Ok::<_, &str>(Some(3)).some_or_panic(), 3);
+    }
+
+    #[test]
+    #[should_panic(expected = "called `some_or_panic()` on an `Err` value: \"connection reset\"")]
+    fn test_some_or_panic_on_err() {
+        Err::<Option<u32>, _>("connection reset").some_or_panic();
+    }
+
+    #[test]
+    #[should_panic(expected = "called `some_or_panic()` on an `Ok(None)` value: no value found")]
+    fn test_some_or_panic_on_ok_none() {
+        Ok::<Option<u32>, &str>(None).some_or_panic();
+    }
+
+    #[test]
+    fn test_some_or_keeps_errors() {
+        assert_eq!(Ok::<_, &str>(Some(3)).some_or(0), Ok(3));
+        assert_eq!(Ok::<_, &str>(None).some_or(0), Ok(0));
+        assert_eq!(Err::<Option<u32>, _>("down").some_or(0), Err("down"));
+        assert_eq!(
+            Err::<Option<u32>, _>("down").some_or_else(|| unreachable!()),
+            Err("down")
+        );
+    }
+
+    #[test]
+    fn test_flatten_or_panic_with_some() {
+        assert_eq!(Some(Some(3)).flatten_or_panic(), 3);
+        assert_eq!(Some(Some(Some(3))).flatten_or_panic().or_panic(), 3);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "called `flatten_or_panic()` on a `None` value: the outer option is `None`"
+    )]
+    fn test_flatten_or_panic_names_outer_none() {
+        None::<Option<u32>>.flatten_or_panic();
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "called `flatten_or_panic()` on a `Some(None)` value: the inner option is `None`"
+    )]
+    fn test_flatten_or_panic_names_inner_none() {
+        Some(None::<u32>).flatten_or_panic();
+    }
+
+    #[test]
+    #[should_panic(expected = "called `copied_or_panic()` on a `None` value")]
+    fn test_copied_or_panic_panics_on_none() {
+        let ports: std::collections::HashMap<&str, u16> = std::collections::HashMap::new();
+        let _ = ports.get("https").copied_or_panic();
+    }
+
+    #[test]
+    #[should_panic(expected = "called `cloned_or_panic()` on a `None` value")]
+    fn test_cloned_or_panic_panics_on_none() {
+        let names: [String; 0] = [];
+        let _ = names.first().cloned_or_panic();
+    }
+
     #[test]
     fn test_panic_or_with_result_ok() {
         let result: Result<u32, &str> = Ok(42);
         assert_eq!(result.panic_or(100), 42);
     }
 
+    #[cfg(not(feature = "test-strict"))]
     #[test]
     fn test_panic_or_with_result_err() {
         let result: Result<u32, &str> = Err("error");
@@ -103,25 +622,77 @@ mod tests {
         assert_eq!(option.panic_or(100), 42);
     }
 
+    #[cfg(not(feature = "test-strict"))]
     #[test]
     fn test_panic_or_with_option_none() {
         let option: Option<u32> = None;
         assert_eq!(option.panic_or(100), 100);
     }
 
+    #[cfg(feature = "test-strict")]
+    #[test]
+    #[should_panic(
+        expected = "called `panic_or()` on a `None` value, which panics instead of falling back under the `test-strict` feature"
+    )]
+    fn test_panic_or_panics_under_test_strict() {
+        let option: Option<u32> = None;
+        option.panic_or(100);
+    }
+
+    #[cfg(feature = "test-strict")]
+    #[test]
+    #[should_panic(expected = "called `panic_or_default()` on an `Err` value")]
+    fn test_panic_or_default_panics_under_test_strict() {
+        let result: Result<u32, &str> = Err("error");
+        result.panic_or_default();
+    }
+
+    #[test]
+    fn test_debug_panic_or_with_values() {
+        assert_eq!(Some(42).debug_panic_or(100), 42);
+        assert_eq!(Ok::<u32, &str>(42).debug_panic_or_else(|_| 100), 42);
+    }
+
+    #[cfg(debug_assertions)]
+    #[test]
+    #[should_panic(expected = "called `debug_panic_or()` on a `None` value")]
+    fn test_debug_panic_or_panics_in_debug_builds() {
+        let option: Option<u32> = None;
+        option.debug_panic_or(100);
+    }
+
+    #[cfg(debug_assertions)]
+    #[test]
+    #[should_panic(expected = "called `debug_panic_or_else()` on an `Err` value: \"error\"")]
+    fn test_debug_panic_or_else_panics_in_debug_builds() {
+        let result: Result<u32, &str> = Err("error");
+        result.debug_panic_or_else(|_| 100);
+    }
+
+    #[cfg(not(debug_assertions))]
+    #[test]
+    fn test_debug_panic_or_falls_back_in_release_builds() {
+        assert_eq!(None.debug_panic_or_else(|| 100), 100);
+        assert_eq!(Err::<u32, &str>("error").debug_panic_or(100), 100);
+    }
+
     #[test]
     fn test_panic_or_else_with_result_ok() {
         let result: Result<u32, &str> = Ok(42);
         assert_eq!(result.panic_or_else(|_| 200), 42);
     }
 
+    #[cfg(not(feature = "test-strict"))]
     #[test]
     fn test_panic_or_else_with_result_err() {
         let result: Result<u32, &str> = Err("error");
-        assert_eq!(result.panic_or_else(|err| {
-            assert_eq!(err, "error");
+        assert_eq!(
+            result.panic_or_else(|err| {
+                assert_eq!(err, "error");
+                200
+            }),
             200
-        }), 200);
+        );
     }
 
     #[test]
@@ -130,6 +701,7 @@ mod tests {
         assert_eq!(option.panic_or_else(|| 200), 42);
     }
 
+    #[cfg(not(feature = "test-strict"))]
     #[test]
     fn test_panic_or_else_with_option_none() {
         let option: Option<u32> = None;
@@ -142,6 +714,7 @@ mod tests {
         assert_eq!(result.panic_or_default(), 42);
     }
 
+    #[cfg(not(feature = "test-strict"))]
     #[test]
     fn test_panic_or_default_with_result_err() {
         let result: Result<u32, &str> = Err("error");
@@ -154,12 +727,29 @@ mod tests {
         assert_eq!(option.panic_or_default(), "hello".to_string());
     }
 
+    #[cfg(not(feature = "test-strict"))]
     #[test]
     fn test_panic_or_default_with_option_none() {
         let option: Option<String> = None;
         assert_eq!(option.panic_or_default(), String::new());
     }
 
+    #[test]
+    fn test_panic_or_log_with_option() {
+        assert_eq!(Some(42).panic_or_log(100), 42);
+        assert_eq!(None.panic_or_log(100), 100);
+        assert_eq!(None.panic_or_log_else(|| 100), 100);
+    }
+
+    #[test]
+    fn test_panic_or_log_with_result() {
+        let ok: Result<u32, &str> = Ok(42);
+        assert_eq!(ok.panic_or_log(100), 42);
+        let err: Result<u32, &str> = Err("timeout");
+        assert_eq!(err.panic_or_log(100), 100);
+        assert_eq!(err.panic_or_log_else(|err| err.len() as u32), 7);
+    }
+
     #[test]
     fn test_panic_with_result_ok() {
         let result: Result<u32, &str> = Ok(42);
@@ -173,6 +763,22 @@ mod tests {
         let _ = result.panic_with("Custom error message");
     }
 
+    #[test]
+    fn test_panic_with_else_result_ok() {
+        let result: Result<u32, &str> = Ok(42);
+        assert_eq!(
+            result.panic_with_else(|_| -> String { panic!("closure must not run on `Ok`") }),
+            42
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "failed to load config: permission denied")]
+    fn test_panic_with_else_result_err() {
+        let result: Result<u32, &str> = Err("permission denied");
+        let _ = result.panic_with_else(|err| format!("failed to load config: {err}"));
+    }
+
     #[test]
     fn test_panic_with_option_some() {
         let option: Option<u32> = Some(42);
@@ -186,6 +792,38 @@ mod tests {
         let _ = option.panic_with("Custom error message");
     }
 
+    #[test]
+    fn test_panic_with_else_option_some() {
+        let option: Option<u32> = Some(42);
+        assert_eq!(
+            option.panic_with_else(|| -> String { panic!("closure must not run on `Some`") }),
+            42
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "missing config key \"region\"")]
+    fn test_panic_with_else_option_none() {
+        let key = "region";
+        let option: Option<&str> = None;
+        let _ = option.panic_with_else(|| format!("missing config key {key:?}"));
+    }
+
+    #[test]
+    #[should_panic(expected = "loading config.toml failed: \"permission denied\"")]
+    fn test_panic_with_format_args_result_err() {
+        let path = "config.toml";
+        let result: Result<u32, &str> = Err("permission denied");
+        let _ = result.panic_with(format_args!("loading {path} failed"));
+    }
+
+    #[test]
+    #[should_panic(expected = "expected 3 retries")]
+    fn test_panic_with_display_option_none() {
+        let option: Option<u32> = None;
+        let _ = option.panic_with(format_args!("expected {} retries", 3));
+    }
+
     #[test]
     fn test_or_panic_err_with_err() {
         let result: Result<u32, &str> = Err("error message");
@@ -205,6 +843,19 @@ mod tests {
         let _ = result.or_panic_err();
     }
 
+    #[test]
+    fn test_or_panic_err_display_with_err() {
+        let result: Result<&str, u32> = Err(7);
+        assert_eq!(result.or_panic_err_display(), 7);
+    }
+
+    #[test]
+    #[should_panic(expected = "called `or_panic_err_display()` on an `Ok` value: connected")]
+    fn test_or_panic_err_display_panics_on_ok() {
+        let result: Result<&str, u32> = Ok("connected");
+        let _ = result.or_panic_err_display();
+    }
+
     #[test]
     fn test_panic_err_with_with_err() {
         let result: Result<u32, &str> = Err("error message");
@@ -217,4 +868,463 @@ mod tests {
         let result: Result<u32, &str> = Ok(42);
         let _ = result.panic_err_with("Custom error message");
     }
+
+    #[cfg(not(feature = "test-strict"))]
+    struct Callback(Box<dyn Fn() -> u32>);
+
+    #[cfg(not(feature = "test-strict"))]
+    #[test]
+    fn test_panic_or_with_non_debug_error() {
+        let result: Result<u32, Callback> = Err(Callback(Box::new(|| 7)));
+        assert_eq!(result.panic_or(100), 100);
+    }
+
+    #[cfg(not(feature = "test-strict"))]
+    #[test]
+    fn test_panic_or_else_with_non_debug_error() {
+        let result: Result<u32, Callback> = Err(Callback(Box::new(|| 7)));
+        assert_eq!(result.panic_or_else(|callback| (callback.0)()), 7);
+    }
+
+    #[cfg(not(feature = "test-strict"))]
+    #[test]
+    fn test_panic_or_default_with_non_debug_error() {
+        let result: Result<u32, Callback> = Err(Callback(Box::new(|| 7)));
+        assert_eq!(result.panic_or_default(), 0);
+    }
+
+    #[test]
+    fn test_or_exit_with_some() {
+        assert_eq!(Some("app.toml").or_exit(1), "app.toml");
+    }
+
+    #[test]
+    fn test_or_exit_with_ok() {
+        let result: Result<u16, &str> = Ok(8080);
+        assert_eq!(result.or_exit(2), 8080);
+    }
+
+    #[test]
+    fn test_or_exit_display_with_ok() {
+        let result: Result<u16, String> = Ok(8080);
+        assert_eq!(result.or_exit_display(2), 8080);
+    }
+
+    #[test]
+    fn test_or_exit_with_custom_message_keeps_value() {
+        assert_eq!(Some(3).or_exit_with(64, "missing"), 3);
+        assert_eq!(Ok::<_, &str>(3).or_exit_with(65, "invalid"), 3);
+    }
+
+    #[test]
+    fn test_or_exit_with_else_is_lazy_on_success() {
+        let value = Some(3).or_exit_with_else(|| -> (i32, &str) { unreachable!() });
+        assert_eq!(value, 3);
+        let value = Ok::<_, &str>(4).or_exit_with_else(|_| -> (i32, &str) { unreachable!() });
+        assert_eq!(value, 4);
+    }
+
+    #[test]
+    fn test_or_abort_with_some() {
+        assert_eq!(Some(3).or_abort(), 3);
+    }
+
+    #[test]
+    fn test_or_abort_with_ok() {
+        let result: Result<usize, &str> = Ok(512);
+        assert_eq!(result.or_abort(), 512);
+    }
+
+    #[test]
+    fn test_or_panic_macro_with_value() {
+        let ports = [8080, 8443];
+        assert_eq!(*or_panic!(ports.get(1)), 8443);
+        assert_eq!(or_panic!("8080".parse::<u16>()), 8080);
+    }
+
+    #[test]
+    #[should_panic(expected = "`config.get(\"region\").copied()` was `None`")]
+    fn test_or_panic_macro_panics_with_option_expression() {
+        let config: std::collections::HashMap<&str, &str> = std::collections::HashMap::new();
+        let _ = or_panic!(config.get("region").copied());
+    }
+
+    #[test]
+    #[should_panic(expected = "`\"80x\".parse::<u16>()` was an `Err` value: ParseIntError")]
+    fn test_or_panic_macro_panics_with_result_expression() {
+        let _ = or_panic!("80x".parse::<u16>());
+    }
+
+    #[test]
+    fn test_or_panic_macro_reports_caller_location() {
+        let (location, line) = (panic_location(|| or_panic!(None::<u32>)), line!());
+        assert_eq!(location, (file!().to_owned(), line));
+    }
+
+    #[test]
+    fn test_ok_or_log_with_ok() {
+        let result: Result<u32, &str> = Ok(42);
+        assert_eq!(result.ok_or_log(), Some(42));
+    }
+
+    #[test]
+    fn test_ok_or_log_with_err() {
+        let result: Result<u32, &str> = Err("error");
+        assert_eq!(result.ok_or_log(), None);
+    }
+
+    #[test]
+    fn test_err_or_log_with_err() {
+        let result: Result<u32, &str> = Err("error");
+        assert_eq!(result.err_or_log(), Some("error"));
+    }
+
+    #[test]
+    fn test_err_or_log_with_ok() {
+        let result: Result<u32, &str> = Ok(42);
+        assert_eq!(result.err_or_log(), None);
+    }
+
+    #[test]
+    fn test_or_panic_multi_with_ok() {
+        let result: Result<u32, Vec<&str>> = Ok(42);
+        assert_eq!(result.or_panic_multi(), 42);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "called `or_panic_multi()` on an `Err` value with 2 errors:\n  1. \"missing field `name`\"\n  2. \"unknown field `nmae`\""
+    )]
+    fn test_or_panic_multi_lists_every_error() {
+        let result: Result<u32, Vec<&str>> =
+            Err(vec!["missing field `name`", "unknown field `nmae`"]);
+        let _ = result.or_panic_multi();
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "called `or_panic_multi()` on an `Err` value with 1 error:\n  1. 404"
+    )]
+    fn test_or_panic_multi_with_single_error() {
+        let result: Result<u32, Vec<u16>> = Err(vec![404]);
+        let _ = result.or_panic_multi();
+    }
+
+    #[test]
+    fn test_option_panics_report_caller_location() {
+        let none = || None::<u32>;
+        let (location, line) = (panic_location(|| none().or_panic()), line!());
+        assert_eq!(location, (file!().to_owned(), line));
+        let (location, line) = (panic_location(|| none().panic_with("missing")), line!());
+        assert_eq!(location, (file!().to_owned(), line));
+        let line = line!() + 1;
+        let location = panic_location(|| none().panic_with_else(|| "missing"));
+        assert_eq!(location, (file!().to_owned(), line));
+    }
+
+    #[test]
+    fn test_result_panics_report_caller_location() {
+        let err = || Err::<u32, &str>("error");
+        let ok = || Ok::<u32, &str>(42);
+        let (location, line) = (panic_location(|| err().or_panic()), line!());
+        assert_eq!(location, (file!().to_owned(), line));
+        let (location, line) = (panic_location(|| err().panic_with("failed")), line!());
+        assert_eq!(location, (file!().to_owned(), line));
+        let (location, line) = (panic_location(|| err().panic_with_else(|e| e)), line!());
+        assert_eq!(location, (file!().to_owned(), line));
+        let (location, line) = (panic_location(|| ok().or_panic_err()), line!());
+        assert_eq!(location, (file!().to_owned(), line));
+        let line = line!() + 1;
+        let location = panic_location(|| ok().panic_err_with("expected Err"));
+        assert_eq!(location, (file!().to_owned(), line));
+        let multi = || Err::<u32, Vec<&str>>(vec!["error"]);
+        let (location, line) = (panic_location(|| multi().or_panic_multi()), line!());
+        assert_eq!(location, (file!().to_owned(), line));
+    }
+
+    /// Asserts that evaluating `$expr` panics at the line of the assertion.
+    macro_rules! assert_panics_here {
+        ($expr:expr) => {
+            assert_eq!(
+                panic_location(panic::AssertUnwindSafe(|| {
+                    $expr;
+                })),
+                (file!().to_owned(), line!())
+            )
+        };
+    }
+
+    #[test]
+    fn test_extension_panics_report_caller_location() {
+        let (_, rx) = std::sync::mpsc::channel::<u32>();
+        assert_panics_here!(rx.recv_timeout_or_panic(std::time::Duration::ZERO, "result"));
+        assert_panics_here!(std::iter::empty::<u8>().peekable().peek_or_panic());
+        assert_panics_here!(std::borrow::Cow::<str>::Owned("a".into()).borrowed_or_panic());
+        assert_panics_here!(std::borrow::Cow::Borrowed("a").owned_or_panic());
+        assert_panics_here!([1, 2].index_or_panic(5));
+        assert_panics_here!("é".index_or_panic(0..1));
+        #[cfg(feature = "humantime")]
+        assert_panics_here!(duration::parse_duration_or_panic("30 parsecs"));
+        #[cfg(unix)]
+        {
+            use std::os::unix::process::ExitStatusExt;
+            assert_panics_here!(std::process::ExitStatus::from_raw(9).code_or_panic());
+        }
+        assert_panics_here!(env::arg_or_panic(1000));
+        let items = [Some(1), None].into_iter();
+        assert_panics_here!(items.collect_some_or_panic::<u32, Vec<_>>());
+        assert_panics_here!([Some(1), None].unwrap_all_or_panic());
+        assert_panics_here!([Ok(1), Err("blocked")].unwrap_all_or_panic());
+        assert_panics_here!(fs::metadata_or_panic("does/not/exist.toml"));
+        assert_panics_here!(fs::read_or_panic("does/not/exist.toml"));
+        assert_panics_here!(fs::read_to_string_or_panic("does/not/exist.toml"));
+        assert_panics_here!(fs::write_or_panic("does/not/exist.toml", "x"));
+        assert_panics_here!(ffi::check_ret_or_panic(-1));
+        assert_panics_here!(ffi::check_nonneg_or_panic(-2));
+        assert_panics_here!(c"caf\xe9".to_str_or_panic());
+        assert_panics_here!(mem::LateInit::<u32>::new().get_or_panic());
+        assert_panics_here!(mem::LateInit::<u32>::new().assume_init_or_panic());
+        assert_panics_here!(std::sync::OnceLock::<u32>::new().get_or_panic());
+        assert_panics_here!(std::cell::OnceCell::<u32>::new().get_or_panic());
+        let (_, report) = [Ok(1), Err("bad row")].into_iter().partition_report();
+        assert_panics_here!(report.or_panic());
+        let backoff = retry::Backoff::constant(std::time::Duration::ZERO).max(1);
+        let refused = || Err::<(), _>("refused");
+        assert_panics_here!(retry::retry_or_panic_with(backoff, refused));
+        assert_panics_here!(retry_or_panic(2, refused));
+        assert_panics_here!(200u8.try_add(100).or_panic());
+        assert_panics_here!(200u8.checked_add_or_panic(100));
+        #[cfg(feature = "serde_json")]
+        assert_panics_here!(json::BUJson::as_str_or_panic(&serde_json::json!(42)));
+    }
+
+    #[cfg(feature = "macros")]
+    #[derive(FromReprOrPanic, Debug, PartialEq)]
+    #[repr(u8)]
+    enum Opcode {
+        Ping = 1,
+        Pong,
+        Close = 8,
+    }
+
+    #[cfg(feature = "macros")]
+    #[test]
+    fn test_from_repr_or_panic_with_valid_discriminants() {
+        assert_eq!(Opcode::from_repr_or_panic(1), Opcode::Ping);
+        assert_eq!(Opcode::from_repr_or_panic(2), Opcode::Pong);
+        assert_eq!(Opcode::from_repr_or_panic(8), Opcode::Close);
+    }
+
+    #[cfg(feature = "macros")]
+    #[test]
+    #[should_panic(expected = "invalid discriminant 3 for enum `Opcode`")]
+    fn test_from_repr_or_panic_panics_on_invalid_discriminant() {
+        let _ = Opcode::from_repr_or_panic(3);
+    }
+
+    #[cfg(feature = "macros")]
+    #[test]
+    fn test_from_repr_or_with_invalid_discriminant() {
+        assert_eq!(Opcode::from_repr_or(3, Opcode::Close), Opcode::Close);
+        assert_eq!(Opcode::from_repr_or(1, Opcode::Close), Opcode::Ping);
+    }
+
+    #[cfg(feature = "macros")]
+    #[derive(PanicMessage, Debug)]
+    enum UploadError {
+        #[panic_message("upload of {path} failed with status {status}")]
+        Rejected {
+            path: &'static str,
+            status: u16,
+            body: Vec<u8>,
+        },
+        #[panic_message("upload timed out after {0}s ({1:?})")]
+        TimedOut(u64, &'static str),
+        Cancelled,
+    }
+
+    #[cfg(feature = "macros")]
+    #[test]
+    #[should_panic(
+        expected = "called `or_panic_message()` on an `Err` value: upload of logs.tar failed with status 413"
+    )]
+    fn test_or_panic_message_uses_derived_format() {
+        let result: Result<(), UploadError> = Err(UploadError::Rejected {
+            path: "logs.tar",
+            status: 413,
+            body: vec![0; 4096],
+        });
+        result.or_panic_message();
+    }
+
+    #[cfg(feature = "macros")]
+    #[test]
+    #[should_panic(
+        expected = "called `or_panic_message()` on an `Err` value: upload timed out after 30s (\"eu-west-1\")"
+    )]
+    fn test_or_panic_message_names_tuple_fields_by_position() {
+        let result: Result<(), UploadError> = Err(UploadError::TimedOut(30, "eu-west-1"));
+        result.or_panic_message();
+    }
+
+    #[cfg(feature = "macros")]
+    #[test]
+    #[should_panic(expected = "called `or_panic_message()` on an `Err` value: Cancelled")]
+    fn test_or_panic_message_falls_back_to_debug() {
+        let result: Result<(), UploadError> = Err(UploadError::Cancelled);
+        result.or_panic_message();
+    }
+
+    #[cfg(feature = "macros")]
+    #[derive(PanicMessage, Debug)]
+    enum FetchError {
+        Refused,
+        #[panic_message("timeout {0}")]
+        Timeout(u64),
+    }
+
+    #[cfg(feature = "macros")]
+    #[test]
+    #[should_panic(expected = "called `or_panic_message()` on an `Err` value: timeout 3")]
+    fn test_or_panic_message_formats_variants_after_a_debug_one() {
+        let result: Result<(), FetchError> = Err(FetchError::Timeout(3));
+        result.or_panic_message();
+    }
+
+    #[cfg(feature = "macros")]
+    #[test]
+    #[should_panic(expected = "called `or_panic_message()` on an `Err` value: Refused")]
+    fn test_or_panic_message_falls_back_to_debug_before_formatted_variants() {
+        let result: Result<(), FetchError> = Err(FetchError::Refused);
+        result.or_panic_message();
+    }
+
+    #[derive(Debug)]
+    struct Layer(&'static str, Option<Box<Layer>>);
+
+    impl std::fmt::Display for Layer {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.write_str(self.0)
+        }
+    }
+
+    impl std::error::Error for Layer {
+        fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+            self.1.as_deref().map(|source| source as _)
+        }
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "called `or_panic_chained()` on an `Err` value: failed to start\ncaused by: failed to load config\ncaused by: permission denied"
+    )]
+    fn test_or_panic_chained_prints_every_source() {
+        let root = Layer("permission denied", None);
+        let config = Layer("failed to load config", Some(Box::new(root)));
+        let result: Result<(), Layer> = Err(Layer("failed to start", Some(Box::new(config))));
+        result.or_panic_chained();
+    }
+
+    #[test]
+    fn test_or_panic_redacted_hides_error() {
+        let line = line!() + 1;
+        let message = panic_message(|| Err::<u32, _>("sk-live-42".to_string()).or_panic_redacted());
+        assert_eq!(
+            message,
+            format!(
+                "called `or_panic_redacted()` at {}:{line}:80 on an `Err` value of type `alloc::string::String` (contents redacted)",
+                file!()
+            )
+        );
+        assert_eq!(Ok::<_, &str>(1).or_panic_redacted(), 1);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "called `or_panic_pretty()` on an `Err` value: (\n    \"users\",\n    [\n        1205,\n    ],\n)"
+    )]
+    fn test_or_panic_pretty_prints_one_field_per_line() {
+        Err::<u32, _>(("users", [1205])).or_panic_pretty();
+    }
+
+    #[test]
+    fn test_or_panic_with_backtrace_includes_backtrace() {
+        let message = panic_message(|| Err::<u32, _>("lease expired").or_panic_with_backtrace());
+        assert!(message.starts_with(
+            "called `or_panic_with_backtrace()` on an `Err` value: \"lease expired\"\n\nbacktrace:\n"
+        ));
+        assert!(message.contains("test_or_panic_with_backtrace_includes_backtrace"));
+
+        let message = panic_message(|| None::<u32>.or_panic_with_backtrace());
+        assert!(
+            message.starts_with(
+                "called `or_panic_with_backtrace()` on a `None` value\n\nbacktrace:\n"
+            )
+        );
+    }
+
+    #[test]
+    fn test_or_panic_with_backtrace_returns_value() {
+        assert_eq!(Some(1).or_panic_with_backtrace(), 1);
+        assert_eq!(Ok::<_, &str>(2).or_panic_with_backtrace(), 2);
+    }
+
+    #[test]
+    fn test_or_panic_chained_returns_ok_value() {
+        let result: Result<u32, Layer> = Ok(7);
+        assert_eq!(result.or_panic_chained(), 7);
+    }
+
+    #[test]
+    fn test_expect_eq_accepts_equal_values() {
+        Some(vec![1, 2]).expect_some_eq([1, 2]);
+        Ok::<_, &str>("ada".to_string()).expect_ok_eq("ada");
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "called `expect_ok_eq()` on an `Ok` value that differs from the expected one:\n- expected\n+ actual\n\n  [\n      1,\n-     2,\n+     3,\n  ]"
+    )]
+    fn test_expect_ok_eq_shows_diff() {
+        Ok::<_, &str>(vec![1, 3]).expect_ok_eq(vec![1, 2]);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "called `expect_ok_eq()` on an `Err` value: \"not found\"\nexpected: Ok(1)"
+    )]
+    fn test_expect_ok_eq_on_err_shows_error() {
+        Err::<u32, _>("not found").expect_ok_eq(1);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "called `expect_some_eq()` on a `None` value\nexpected: Some(\"ada\")"
+    )]
+    fn test_expect_some_eq_on_none() {
+        None::<&str>.expect_some_eq("ada");
+    }
+
+    #[cfg(feature = "macros")]
+    #[forbid_unwrap]
+    mod strict {
+        use crate::prelude::*;
+
+        pub fn parse_port(input: &str) -> u16 {
+            input
+                .parse()
+                .panic_with(format_args!("invalid port {input:?}"))
+        }
+
+        pub fn first_port(ports: &[u16]) -> u16 {
+            *ports.first().or_panic()
+        }
+    }
+
+    #[cfg(feature = "macros")]
+    #[test]
+    fn test_forbid_unwrap_accepts_better_unwrap_calls() {
+        assert_eq!(strict::parse_port("8080"), 8080);
+        assert_eq!(strict::first_port(&[8443]), 8443);
+    }
 }
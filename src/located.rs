@@ -0,0 +1,93 @@
+//! Errors recording where a missing value was first observed.
+
+use core::error::Error;
+use core::fmt;
+use core::panic::Location;
+
+/// The error of [`ok_or_located_err()`](crate::BUOption::ok_or_located_err): a `None` converted into
+/// an error, together with the location of the conversion and an optional message.
+///
+/// Propagate it with `?` when the caller should handle a missing value instead of panicking, without
+/// losing track of where the `None` came from.
+///
+/// # Examples
+///
+/// ```
+/// use better_unwrap::{BULocatedError, BUOption};
+///
+/// fn port(config: Option<u16>) -> Result<u16, BULocatedError> {
+///     let port = config.ok_or_located_err_with("no port configured")?;
+///     Ok(port)
+/// }
+///
+/// let error = port(None).unwrap_err();
+/// assert_eq!(error.message(), Some("no port configured"));
+/// assert_eq!(error.location().file(), file!());
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BULocatedError {
+    message: Option<&'static str>,
+    location: &'static Location<'static>,
+}
+
+impl BULocatedError {
+    /// Creates an error located at the caller, with an optional message.
+    #[track_caller]
+    pub fn new(message: Option<&'static str>) -> Self {
+        BULocatedError {
+            message,
+            location: Location::caller(),
+        }
+    }
+
+    /// Returns the message given when the error was created, if any.
+    pub fn message(&self) -> Option<&'static str> {
+        self.message
+    }
+
+    /// Returns the location where the `None` was converted into the error.
+    pub fn location(&self) -> &'static Location<'static> {
+        self.location
+    }
+}
+
+impl fmt::Display for BULocatedError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let message = self.message.unwrap_or("found a `None` value");
+        write!(f, "{message} at {}", self.location)
+    }
+}
+
+impl Error for BULocatedError {}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use crate::{BUOption, BUResult};
+
+    #[test]
+    fn test_ok_or_located_err_records_caller() {
+        let line = line!() + 1;
+        let error = None::<u32>.ok_or_located_err().or_panic_err();
+        assert_eq!(error.message(), None);
+        assert_eq!(error.location().file(), file!());
+        assert_eq!(error.location().line(), line);
+        assert_eq!(
+            error.to_string(),
+            format!("found a `None` value at {}", error.location())
+        );
+    }
+
+    #[test]
+    fn test_ok_or_located_err_with_message() {
+        let error = None::<u32>
+            .ok_or_located_err_with("no session token")
+            .or_panic_err();
+        assert_eq!(error.message(), Some("no session token"));
+        assert!(error.to_string().starts_with("no session token at "));
+    }
+
+    #[test]
+    fn test_ok_or_located_err_keeps_value() {
+        assert_eq!(Some(5).ok_or_located_err(), Ok(5));
+    }
+}
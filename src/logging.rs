@@ -0,0 +1,170 @@
+//! Variants of `or_panic()` that also log the failure through the [`log`](https://docs.rs/log)
+//! facade, so it reaches the log aggregator even when the panic message is lost.
+
+use std::fmt::{self, Debug};
+use std::panic::Location;
+
+use crate::message::Truncated;
+use crate::policy;
+use crate::report;
+
+/// Trait that provides `or_panic()` variants logging the failure with `log::error!` before panicking.
+///
+/// The log record has the target `better_unwrap` and includes the caller location and the failure
+/// message.
+pub trait BULogged<T> {
+    /// Unwraps the value, logging the failure at error level before panicking.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is `None` or an `Err`, with the same message that was logged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use better_unwrap::logging::BULogged;
+    ///
+    /// let x: Result<u32, &str> = Ok(42);
+    /// assert_eq!(x.or_panic_logged(), 42);
+    /// ```
+    ///
+    /// ```should_panic
+    /// use better_unwrap::logging::BULogged;
+    ///
+    /// let x: Option<u32> = None;
+    /// x.or_panic_logged(); // logs, then panics with `"called `or_panic_logged()` on a `None` value"`
+    /// ```
+    #[track_caller]
+    fn or_panic_logged(self) -> T;
+}
+
+impl<T> BULogged<T> for Option<T> {
+    #[track_caller]
+    fn or_panic_logged(self) -> T {
+        match self {
+            Some(value) => value,
+            None => fail_logged(format_args!("called `or_panic_logged()` on a `None` value")),
+        }
+    }
+}
+
+impl<T, E: Debug> BULogged<T> for Result<T, E> {
+    #[track_caller]
+    fn or_panic_logged(self) -> T {
+        match self {
+            Ok(value) => value,
+            Err(error) => fail_logged(format_args!(
+                "called `or_panic_logged()` on an `Err` value: {error:?}",
+                error = Truncated(&error)
+            )),
+        }
+    }
+}
+
+/// Logs `message` with the caller location at error level, then fails with it.
+#[track_caller]
+fn fail_logged(message: fmt::Arguments<'_>) -> ! {
+    let line = report::redact(format_args!("{}: {message}", Location::caller()));
+    ::log::error!(target: "better_unwrap", "{line}");
+    policy::fail(message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::BUResult;
+    use crate::policy::tests::{in_child, run_in_child};
+    use std::panic;
+    use std::sync::{Mutex, Once};
+
+    static RECORDS: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+    struct CaptureLogger;
+
+    impl ::log::Log for CaptureLogger {
+        fn enabled(&self, _: &::log::Metadata<'_>) -> bool {
+            true
+        }
+
+        fn log(&self, record: &::log::Record<'_>) {
+            RECORDS.lock().or_panic().push(format!(
+                "{} {}: {}",
+                record.level(),
+                record.target(),
+                record.args()
+            ));
+        }
+
+        fn flush(&self) {}
+    }
+
+    #[test]
+    fn test_or_panic_logged_with_value() {
+        assert_eq!(Some(42).or_panic_logged(), 42);
+        assert_eq!(Ok::<_, &str>(42).or_panic_logged(), 42);
+    }
+
+    /// Installs [`CaptureLogger`] once for the whole test binary and returns the records whose
+    /// message contains `needle`, so tests running alongside each other don't see each other's.
+    fn records_containing(needle: &str) -> Vec<String> {
+        static INSTALL: Once = Once::new();
+        INSTALL.call_once(|| {
+            ::log::set_logger(&CaptureLogger).or_panic();
+            ::log::set_max_level(::log::LevelFilter::Warn);
+        });
+        let records = RECORDS.lock().or_panic();
+        records
+            .iter()
+            .filter(|record| record.contains(needle))
+            .cloned()
+            .collect()
+    }
+
+    #[test]
+    fn test_or_panic_logged_logs_before_panicking() {
+        records_containing("");
+        let line = line!() + 3;
+        let result = panic::catch_unwind(|| {
+            let x: Result<u32, &str> = Err("connection refused");
+            x.or_panic_logged()
+        });
+        assert!(result.is_err());
+        assert_eq!(
+            records_containing("connection refused"),
+            [format!(
+                "ERROR better_unwrap: {}:{line}:15: called `or_panic_logged()` on an `Err` value: \"connection refused\"",
+                file!()
+            )]
+        );
+    }
+
+    #[test]
+    fn child_logged_lines_are_redacted() {
+        if !in_child() {
+            return;
+        }
+        records_containing("");
+        crate::Report::set_redaction_hook(Some(|text| text.replace("SECRET", "[redacted]")));
+        let result = panic::catch_unwind(|| Err::<u32, _>("session SECRET").or_panic_logged());
+        assert!(result.is_err());
+        assert_eq!(Err::<u32, _>("session SECRET").ok_or_log(), None);
+        let records = records_containing("session ");
+        assert_eq!(records.len(), 2);
+        assert!(records[0].starts_with("ERROR better_unwrap: "));
+        assert!(records[1].starts_with("WARN better_unwrap: "));
+        for record in records {
+            assert!(
+                record.ends_with("value: \"session [redacted]\""),
+                "{record}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_logged_lines_are_redacted() {
+        // The redaction hook is process-wide, so it is installed in a child process where it cannot
+        // rewrite the messages other tests check.
+        let output = run_in_child("logging::tests::child_logged_lines_are_redacted");
+        assert!(output.status.success(), "{output:?}");
+    }
+}
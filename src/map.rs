@@ -0,0 +1,149 @@
+use std::borrow::Borrow;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Debug;
+use std::hash::{BuildHasher, Hash};
+
+use crate::message::Truncated;
+use crate::policy;
+
+/// Trait that provides alternatives to `get(key).unwrap()` and `get_mut(key).unwrap()` for `HashMap`
+/// and `BTreeMap`.
+///
+/// The panic message includes the missing key formatted using `Debug`.
+pub trait BUMap<Q: ?Sized, V> {
+    /// Returns a reference to the value for `key`.
+    ///
+    /// Equivalent to `get(key).unwrap()`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the map has no value for `key`, with a panic message including the key formatted
+    /// using `Debug`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::collections::HashMap;
+    /// use better_unwrap::BUMap;
+    ///
+    /// let ports = HashMap::from([("https".to_string(), 443)]);
+    /// assert_eq!(*ports.get_or_panic("https"), 443);
+    /// ```
+    ///
+    /// ```should_panic
+    /// use std::collections::BTreeMap;
+    /// use better_unwrap::BUMap;
+    ///
+    /// let ports = BTreeMap::from([("https", 443)]);
+    /// ports.get_or_panic("http"); // panics with `"key \"http\" not found in map with 1 entries"`
+    /// ```
+    #[track_caller]
+    fn get_or_panic(&self, key: &Q) -> &V;
+
+    /// Returns a mutable reference to the value for `key`.
+    ///
+    /// Equivalent to `get_mut(key).unwrap()`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the map has no value for `key`, with a panic message including the key formatted
+    /// using `Debug`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::collections::HashMap;
+    /// use better_unwrap::BUMap;
+    ///
+    /// let mut hits = HashMap::from([("/", 1)]);
+    /// *hits.get_mut_or_panic("/") += 1;
+    /// assert_eq!(hits["/"], 2);
+    /// ```
+    #[track_caller]
+    fn get_mut_or_panic(&mut self, key: &Q) -> &mut V;
+}
+
+impl<K, V, Q, S> BUMap<Q, V> for HashMap<K, V, S>
+where
+    K: Borrow<Q> + Hash + Eq,
+    Q: Hash + Eq + Debug + ?Sized,
+    S: BuildHasher,
+{
+    #[track_caller]
+    fn get_or_panic(&self, key: &Q) -> &V {
+        match self.get(key) {
+            Some(value) => value,
+            None => fail_missing(key, self.len()),
+        }
+    }
+
+    #[track_caller]
+    fn get_mut_or_panic(&mut self, key: &Q) -> &mut V {
+        let len = self.len();
+        match self.get_mut(key) {
+            Some(value) => value,
+            None => fail_missing(key, len),
+        }
+    }
+}
+
+impl<K, V, Q> BUMap<Q, V> for BTreeMap<K, V>
+where
+    K: Borrow<Q> + Ord,
+    Q: Ord + Debug + ?Sized,
+{
+    #[track_caller]
+    fn get_or_panic(&self, key: &Q) -> &V {
+        match self.get(key) {
+            Some(value) => value,
+            None => fail_missing(key, self.len()),
+        }
+    }
+
+    #[track_caller]
+    fn get_mut_or_panic(&mut self, key: &Q) -> &mut V {
+        let len = self.len();
+        match self.get_mut(key) {
+            Some(value) => value,
+            None => fail_missing(key, len),
+        }
+    }
+}
+
+#[track_caller]
+fn fail_missing<Q: Debug + ?Sized>(key: &Q, len: usize) -> ! {
+    policy::fail(format_args!(
+        "key {key:?} not found in map with {len} entries",
+        key = Truncated(key)
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_get_or_panic_with_present_key() {
+        let mut hash = HashMap::from([("a".to_string(), 1)]);
+        *hash.get_mut_or_panic("a") += 1;
+        assert_eq!(*hash.get_or_panic("a"), 2);
+
+        let mut btree = BTreeMap::from([(1u32, "one")]);
+        *btree.get_mut_or_panic(&1) = "uno";
+        assert_eq!(*btree.get_or_panic(&1), "uno");
+    }
+
+    #[test]
+    #[should_panic(expected = "key \"region\" not found in map with 1 entries")]
+    fn test_hash_map_get_or_panic_panics_on_missing_key() {
+        let config = HashMap::from([("zone".to_string(), "a")]);
+        let _ = config.get_or_panic("region");
+    }
+
+    #[test]
+    #[should_panic(expected = "key 7 not found in map with 0 entries")]
+    fn test_btree_map_get_mut_or_panic_panics_on_missing_key() {
+        let mut map: BTreeMap<u32, u32> = BTreeMap::new();
+        let _ = map.get_mut_or_panic(&7);
+    }
+}
@@ -0,0 +1,192 @@
+use std::any::type_name;
+use std::fmt;
+use std::mem::{ManuallyDrop, MaybeUninit};
+
+use crate::policy;
+
+/// A value that is initialized once, some time after construction.
+///
+/// `LateInit<T>` stores its value in a [`MaybeUninit<T>`] and records whether [`write`](Self::write)
+/// happened, so reading it is safe: a forgotten write panics instead of being undefined behaviour.
+///
+/// # Examples
+///
+/// ```
+/// use better_unwrap::mem::LateInit;
+///
+/// let mut buffer: LateInit<Vec<u8>> = LateInit::new();
+/// buffer.write(vec![1, 2, 3]);
+/// assert_eq!(buffer.get_or_panic().len(), 3);
+/// ```
+pub struct LateInit<T> {
+    value: MaybeUninit<T>,
+    initialized: bool,
+}
+
+impl<T> LateInit<T> {
+    /// Creates an uninitialized `LateInit`.
+    pub const fn new() -> Self {
+        LateInit {
+            value: MaybeUninit::uninit(),
+            initialized: false,
+        }
+    }
+
+    /// Returns `true` if a value has been written.
+    pub fn is_initialized(&self) -> bool {
+        self.initialized
+    }
+
+    /// Writes `value`, dropping any previously written value, and returns a mutable reference to it.
+    pub fn write(&mut self, value: T) -> &mut T {
+        if self.initialized {
+            // Cleared first so a panicking destructor cannot lead to a double drop.
+            self.initialized = false;
+            // SAFETY: `initialized` was set, so the value has been written.
+            unsafe { self.value.assume_init_drop() };
+        }
+        self.initialized = true;
+        self.value.write(value)
+    }
+
+    /// Returns a reference to the written value.
+    ///
+    /// # Panics
+    ///
+    /// Panics if no value has been written, with a panic message including the type of the value.
+    ///
+    /// # Examples
+    ///
+    /// ```should_panic
+    /// use better_unwrap::mem::LateInit;
+    ///
+    /// let config: LateInit<String> = LateInit::new();
+    /// config.get_or_panic(); // panics with `"called `get_or_panic()` on a `LateInit<alloc::string::String>` that was never written"`
+    /// ```
+    #[track_caller]
+    pub fn get_or_panic(&self) -> &T {
+        if !self.initialized {
+            fail_unwritten::<T>("get_or_panic");
+        }
+        // SAFETY: `initialized` is only set after the value has been written.
+        unsafe { self.value.assume_init_ref() }
+    }
+
+    /// Extracts the written value.
+    ///
+    /// The safe counterpart of `MaybeUninit::assume_init()`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if no value has been written, with a panic message including the type of the value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use better_unwrap::mem::LateInit;
+    ///
+    /// let mut id = LateInit::new();
+    /// id.write(7u32);
+    /// assert_eq!(id.assume_init_or_panic(), 7);
+    /// ```
+    #[track_caller]
+    pub fn assume_init_or_panic(self) -> T {
+        if !self.initialized {
+            fail_unwritten::<T>("assume_init_or_panic");
+        }
+        let this = ManuallyDrop::new(self);
+        // SAFETY: `initialized` is only set after the value has been written, and `this` is never
+        // dropped, so the value is moved out exactly once.
+        unsafe { this.value.assume_init_read() }
+    }
+}
+
+#[track_caller]
+fn fail_unwritten<T>(method: &str) -> ! {
+    policy::fail(format_args!(
+        "called `{method}()` on a `LateInit<{}>` that was never written",
+        type_name::<T>()
+    ))
+}
+
+impl<T> Default for LateInit<T> {
+    fn default() -> Self {
+        LateInit::new()
+    }
+}
+
+impl<T> Drop for LateInit<T> {
+    fn drop(&mut self) {
+        if self.initialized {
+            // SAFETY: `initialized` is only set after the value has been written.
+            unsafe { self.value.assume_init_drop() };
+        }
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for LateInit<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.initialized {
+            f.debug_tuple("LateInit")
+                .field(self.get_or_panic())
+                .finish()
+        } else {
+            f.write_str("LateInit(<uninit>)")
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::rc::Rc;
+
+    #[test]
+    fn test_get_or_panic_after_write() {
+        let mut value = LateInit::new();
+        assert!(!value.is_initialized());
+        value.write(42);
+        assert!(value.is_initialized());
+        assert_eq!(*value.get_or_panic(), 42);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "called `get_or_panic()` on a `LateInit<u32>` that was never written"
+    )]
+    fn test_get_or_panic_panics_without_write() {
+        let value: LateInit<u32> = LateInit::new();
+        let _ = value.get_or_panic();
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "called `assume_init_or_panic()` on a `LateInit<u32>` that was never written"
+    )]
+    fn test_assume_init_or_panic_panics_without_write() {
+        let value: LateInit<u32> = LateInit::new();
+        let _ = value.assume_init_or_panic();
+    }
+
+    #[test]
+    fn test_write_and_drop_release_values() {
+        let shared = Rc::new(());
+        let mut value = LateInit::new();
+        value.write(Rc::clone(&shared));
+        value.write(Rc::clone(&shared));
+        assert_eq!(Rc::strong_count(&shared), 2);
+        drop(value);
+        assert_eq!(Rc::strong_count(&shared), 1);
+    }
+
+    #[test]
+    fn test_assume_init_or_panic_moves_value_out_once() {
+        let shared = Rc::new(());
+        let mut value = LateInit::new();
+        value.write(Rc::clone(&shared));
+        let extracted = value.assume_init_or_panic();
+        assert_eq!(Rc::strong_count(&shared), 2);
+        drop(extracted);
+        assert_eq!(Rc::strong_count(&shared), 1);
+    }
+}
@@ -0,0 +1,235 @@
+//! Custom rendering of errors in panic messages.
+
+use core::error::Error;
+use core::fmt::{self, Debug, Write};
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+static MAX_ERROR_LEN: AtomicUsize = AtomicUsize::new(usize::MAX);
+
+/// Sets the maximum length, in bytes, of the `Debug` rendering of an error or unexpected value in
+/// the crate's failure messages.
+///
+/// Longer renderings are cut at `len` bytes and end with an ellipsis and the original length, so that
+/// a failed unwrap of a value holding a large response body cannot flood the logs. Renderings are not
+/// truncated by default; pass `usize::MAX` to stop truncating again.
+///
+/// # Examples
+///
+/// ```should_panic
+/// use better_unwrap::BUResult;
+///
+/// better_unwrap::set_max_error_len(16);
+///
+/// let body = "x".repeat(1_000_000);
+/// let response: Result<(), String> = Err(body);
+/// response.or_panic(); // panics with `"called `or_panic()` on an `Err` value: \"xxxxxxxxxxxxxxx… (1000002 bytes)"`
+/// ```
+pub fn set_max_error_len(len: usize) {
+    MAX_ERROR_LEN.store(len, Ordering::Relaxed);
+}
+
+/// How a value is rendered in the message of [`or_panic_message()`](crate::BUResult::or_panic_message).
+///
+/// Implement it, or derive it with the `macros` feature, for errors whose `Debug` output is too large
+/// or too noisy to read in a panic message.
+///
+/// # Examples
+///
+/// ```
+/// use std::fmt;
+/// use better_unwrap::PanicMessage;
+///
+/// struct UploadError {
+///     path: String,
+///     body: Vec<u8>,
+/// }
+///
+/// impl PanicMessage for UploadError {
+///     fn fmt_panic_message(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+///         write!(f, "upload of {} ({} bytes) failed", self.path, self.body.len())
+///     }
+/// }
+/// ```
+pub trait PanicMessage {
+    /// Formats the value for a panic message.
+    fn fmt_panic_message(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result;
+}
+
+/// Displays a value using its [`PanicMessage`] rendering.
+pub(crate) struct Rendered<'a, E>(pub(crate) &'a E);
+
+impl<E: PanicMessage> fmt::Display for Rendered<'_, E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt_panic_message(f)
+    }
+}
+
+/// Displays an error followed by its chain of sources, one `caused by:` line per level.
+pub(crate) struct Chain<'a>(pub(crate) &'a dyn Error);
+
+impl fmt::Display for Chain<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)?;
+        let mut source = self.0.source();
+        while let Some(error) = source {
+            write!(f, "\ncaused by: {error}")?;
+            source = error.source();
+        }
+        Ok(())
+    }
+}
+
+/// Formats a value using `Debug`, truncated to the length set with [`set_max_error_len`].
+pub(crate) struct Truncated<'a, T: ?Sized>(pub(crate) &'a T);
+
+impl<T: Debug + ?Sized> Debug for Truncated<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match MAX_ERROR_LEN.load(Ordering::Relaxed) {
+            usize::MAX => self.0.fmt(f),
+            max => fmt_truncated(self.0, max, f),
+        }
+    }
+}
+
+/// Formats `value` using `Debug`, keeping `{:#?}` if requested, cut at `max` bytes.
+fn fmt_truncated<T: Debug + ?Sized>(
+    value: &T,
+    max: usize,
+    f: &mut fmt::Formatter<'_>,
+) -> fmt::Result {
+    let alternate = f.alternate();
+    let mut limited = Limited {
+        inner: f,
+        remaining: max,
+        total: 0,
+    };
+    if alternate {
+        write!(limited, "{value:#?}")?;
+    } else {
+        write!(limited, "{value:?}")?;
+    }
+    let total = limited.total;
+    if total > max {
+        write!(f, "… ({total} bytes)")?;
+    }
+    Ok(())
+}
+
+/// Writes through to `inner` until `remaining` bytes have been written, then only counts.
+struct Limited<'a, 'f> {
+    inner: &'a mut fmt::Formatter<'f>,
+    remaining: usize,
+    total: usize,
+}
+
+impl Write for Limited<'_, '_> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.total += s.len();
+        if self.remaining > 0 {
+            let mut end = s.len().min(self.remaining);
+            while !s.is_char_boundary(end) {
+                end -= 1;
+            }
+            self.inner.write_str(&s[..end])?;
+            // A character straddling the limit ends the output.
+            self.remaining = if end < s.len() {
+                0
+            } else {
+                self.remaining - end
+            };
+        }
+        Ok(())
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use crate::payload::{self, BUPanicInfo};
+    use crate::policy::tests::{in_child, run_in_child};
+    use crate::{BUArray, BUIterator, BUOption, BUResult};
+    use std::any::Any;
+    use std::panic;
+
+    fn panic_message(payload: Box<dyn Any + Send>) -> String {
+        payload
+            .downcast::<String>()
+            .map(|message| *message)
+            .or_panic()
+    }
+
+    struct Limit<'a>(&'a str, usize);
+
+    impl fmt::Display for Limit<'_> {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            fmt_truncated(self.0, self.1, f)
+        }
+    }
+
+    #[test]
+    fn test_truncation_reports_original_length() {
+        assert_eq!(Limit("abcdef", 4).to_string(), "\"abc… (8 bytes)");
+        assert_eq!(Limit("abcdef", 8).to_string(), "\"abcdef\"");
+    }
+
+    #[test]
+    fn test_truncation_keeps_char_boundaries() {
+        assert_eq!(Limit("héllo", 3).to_string(), "\"h… (8 bytes)");
+    }
+
+    #[test]
+    fn test_truncated_is_unlimited_by_default() {
+        assert_eq!(format!("{:?}", Truncated("abcdef")), "\"abcdef\"");
+    }
+
+    #[test]
+    fn child_max_error_len() {
+        if !in_child() {
+            return;
+        }
+        set_max_error_len(5);
+        let payload = panic::catch_unwind(|| {
+            payload::with_structured(|| Err::<u32, _>("0123456789").or_panic())
+        })
+        .or_panic_err();
+        let info = payload.downcast_ref::<BUPanicInfo>().or_panic();
+        assert_eq!(info.error_debug(), Some("\"0123… (12 bytes)"));
+
+        let single =
+            panic::catch_unwind(|| ["0123456789", "abcdefghij"].into_iter().single_or_panic());
+        assert_eq!(
+            panic_message(single.or_panic_err()),
+            "expected exactly one element, found at least 2 (first two: \"0123… (12 bytes), \"abcd… (12 bytes))"
+        );
+
+        let retried =
+            panic::catch_unwind(|| crate::retry_or_panic(1, || Err::<u32, _>("0123456789")));
+        assert!(panic_message(retried.or_panic_err()).ends_with(": \"0123… (12 bytes)"));
+
+        let array = panic::catch_unwind(|| [Ok(1), Err("0123456789")].unwrap_all_or_panic());
+        assert_eq!(
+            panic_message(array.or_panic_err()),
+            "called `unwrap_all_or_panic()` with `Err` at positions [1: \"0123… (12 bytes)]"
+        );
+
+        let (_, report) = [Ok(1), Err("0123456789")].into_iter().partition_report();
+        let reported = panic::catch_unwind(|| report.or_panic());
+        assert!(panic_message(reported.or_panic_err()).ends_with("[1] \"0123… (12 bytes)"));
+
+        let asserted = panic::catch_unwind(|| crate::assert_ok!(Err::<u32, _>("0123456789")));
+        assert!(panic_message(asserted.or_panic_err()).ends_with("Err(\"0123… (12 bytes))"));
+
+        let parsed = panic::catch_unwind(|| crate::BUStr::parse_or_panic::<u32>("0123456789a"));
+        assert!(
+            panic_message(parsed.or_panic_err()).starts_with("failed to parse \"0123… (13 bytes)")
+        );
+    }
+
+    #[test]
+    fn test_max_error_len_applies_everywhere() {
+        // The limit is process-wide, so it is set in a child process where it cannot truncate the
+        // messages other tests check.
+        let output = run_in_child("message::tests::child_max_error_len");
+        assert!(output.status.success(), "{output:?}");
+    }
+}
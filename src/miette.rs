@@ -0,0 +1,123 @@
+//! Panicking with [`miette`](https://docs.rs/miette) diagnostics.
+//!
+//! Available with the `miette` feature. [`BUDiagnostic`] renders a diagnostic's labels, source
+//! snippets, and help text into the panic message, through the installed miette report handler
+//! (the graphical handler unless another one was installed with `miette::set_hook`). `or_panic()` on
+//! a `miette::Result` already renders its report through the same handler.
+
+use ::miette::{Diagnostic, Report};
+
+use crate::policy;
+
+/// Trait that provides an alternative to `unwrap()` for results whose error is a miette
+/// [`Diagnostic`].
+pub trait BUDiagnostic<T> {
+    /// Unwraps a result, yielding the content of an `Ok`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is an `Err`, with a panic message including the error rendered as a miette
+    /// diagnostic, with its labels, source snippets, and help text.
+    ///
+    /// # Examples
+    ///
+    /// ```should_panic
+    /// use better_unwrap::miette::BUDiagnostic;
+    /// use miette::{Diagnostic, NamedSource, SourceSpan};
+    ///
+    /// #[derive(Debug, Diagnostic)]
+    /// #[diagnostic(help("declare the variable with `let`"))]
+    /// struct UndefinedVariable {
+    ///     #[source_code]
+    ///     src: NamedSource<&'static str>,
+    ///     #[label("not found in this scope")]
+    ///     span: SourceSpan,
+    /// }
+    ///
+    /// impl std::fmt::Display for UndefinedVariable {
+    ///     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    ///         f.write_str("undefined variable")
+    ///     }
+    /// }
+    ///
+    /// impl std::error::Error for UndefinedVariable {}
+    ///
+    /// let checked: Result<(), UndefinedVariable> = Err(UndefinedVariable {
+    ///     src: NamedSource::new("main.lang", "print(total)"),
+    ///     span: (6, 5).into(),
+    /// });
+    /// checked.or_panic_diagnostic(); // panics with the rendered diagnostic, pointing at `total`
+    /// ```
+    #[track_caller]
+    fn or_panic_diagnostic(self) -> T;
+}
+
+impl<T, E> BUDiagnostic<T> for Result<T, E>
+where
+    E: Diagnostic + Send + Sync + 'static,
+{
+    #[track_caller]
+    fn or_panic_diagnostic(self) -> T {
+        match self {
+            Ok(value) => value,
+            Err(error) => {
+                let report = Report::new(error);
+                policy::fail(format_args!(
+                    "called `or_panic_diagnostic()` on an `Err` value:\n{report:?}"
+                ))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::BUResult;
+    use ::miette::{NamedSource, SourceSpan};
+    use std::fmt;
+    use std::panic;
+
+    #[derive(Debug, Diagnostic)]
+    #[diagnostic(code(lang::undefined), help("declare the variable with `let`"))]
+    struct UndefinedVariable {
+        #[source_code]
+        src: NamedSource<&'static str>,
+        #[label("not found in this scope")]
+        span: SourceSpan,
+    }
+
+    impl fmt::Display for UndefinedVariable {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str("undefined variable")
+        }
+    }
+
+    impl std::error::Error for UndefinedVariable {}
+
+    #[test]
+    fn test_or_panic_diagnostic_renders_labels_and_help() {
+        let payload = panic::catch_unwind(|| {
+            let checked: Result<(), UndefinedVariable> = Err(UndefinedVariable {
+                src: NamedSource::new("main.lang", "print(total)"),
+                span: (6, 5).into(),
+            });
+            checked.or_panic_diagnostic();
+        })
+        .or_panic_err();
+        let message = payload.downcast::<String>().or_panic();
+        assert!(message.starts_with("called `or_panic_diagnostic()` on an `Err` value:\n"));
+        assert!(message.contains("lang::undefined"));
+        assert!(message.contains("undefined variable"));
+        assert!(message.contains("main.lang"));
+        assert!(message.contains("print(total)"));
+        assert!(message.contains("not found in this scope"));
+        assert!(message.contains("declare the variable with `let`"));
+    }
+
+    #[test]
+    fn test_or_panic_diagnostic_returns_ok_value() {
+        let checked: Result<u32, UndefinedVariable> = Ok(3);
+        assert_eq!(checked.or_panic_diagnostic(), 3);
+    }
+}
@@ -0,0 +1,375 @@
+//! Explicit overflow handling for integer arithmetic.
+//!
+//! [`BUChecked`] offers `checked_*_or_panic` methods that name the operands and type on overflow, and
+//! `try_*` methods returning a [`Checked`] result whose fallback is chosen per call site: panic,
+//! saturate, or wrap. [`BUNonZero`] converts integers into their `NonZero` counterparts.
+
+use std::any;
+use std::fmt::Display;
+use std::num::NonZero;
+use std::panic::Location;
+
+use crate::{emit, policy};
+
+/// The result of an integer operation that may have overflowed, with the fallbacks it supports.
+///
+/// Returned by the `try_*` methods of [`BUChecked`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[must_use = "a `Checked` result does nothing until a fallback such as `or_saturate()` is chosen"]
+pub struct Checked<T> {
+    op: &'static str,
+    lhs: T,
+    rhs: T,
+    checked: Option<T>,
+    saturating: T,
+    wrapping: T,
+}
+
+impl<T: Copy + Display> Checked<T> {
+    /// Returns the result, or `None` if the operation overflowed.
+    ///
+    /// Equivalent to the `checked_*` method of the operation.
+    pub fn checked(self) -> Option<T> {
+        self.checked
+    }
+
+    /// Returns the result of the operation.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the operation overflowed, with a panic message including the operands and type.
+    ///
+    /// # Examples
+    ///
+    /// ```should_panic
+    /// use better_unwrap::BUChecked;
+    ///
+    /// 200u8.try_add(100).or_panic(); // panics with `"arithmetic overflow: `200 + 100` does not fit in `u8`"`
+    /// ```
+    #[track_caller]
+    pub fn or_panic(self) -> T {
+        match self.checked {
+            Some(value) => value,
+            None => policy::fail(format_args!(
+                "arithmetic overflow: `{} {} {}` does not fit in `{}`",
+                self.lhs,
+                self.op,
+                self.rhs,
+                any::type_name::<T>()
+            )),
+        }
+    }
+
+    /// Returns the result of the operation, or the bound of the type it overflowed past.
+    ///
+    /// Equivalent to the `saturating_*` method of the operation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use better_unwrap::BUChecked;
+    ///
+    /// assert_eq!(200u8.try_add(100).or_saturate(), 255);
+    /// assert_eq!(5u8.try_sub(10).or_saturate(), 0);
+    /// ```
+    pub fn or_saturate(self) -> T {
+        self.saturating
+    }
+
+    /// Returns the result of the operation, or the bound of the type it overflowed past after logging
+    /// that the fallback was taken.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use better_unwrap::BUChecked;
+    ///
+    /// assert_eq!(200u8.try_add(100).or_saturate_logged(), 255); // logs the overflow to stderr
+    /// ```
+    #[track_caller]
+    pub fn or_saturate_logged(self) -> T {
+        if self.checked.is_none() {
+            self.log("saturated", self.saturating);
+        }
+        self.saturating
+    }
+
+    /// Returns the result of the operation, wrapping around at the bounds of the type.
+    ///
+    /// Equivalent to the `wrapping_*` method of the operation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use better_unwrap::BUChecked;
+    ///
+    /// assert_eq!(200u8.try_add(100).or_wrap(), 44);
+    /// ```
+    pub fn or_wrap(self) -> T {
+        self.wrapping
+    }
+
+    /// Returns the result of the operation, wrapping around at the bounds of the type after logging
+    /// that the fallback was taken.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use better_unwrap::BUChecked;
+    ///
+    /// assert_eq!(200u8.try_add(100).or_wrap_logged(), 44); // logs the overflow to stderr
+    /// ```
+    #[track_caller]
+    pub fn or_wrap_logged(self) -> T {
+        if self.checked.is_none() {
+            self.log("wrapped", self.wrapping);
+        }
+        self.wrapping
+    }
+
+    #[track_caller]
+    fn log(&self, fallback: &str, value: T) {
+        emit::log(
+            Location::caller(),
+            format_args!(
+                "arithmetic overflow: `{} {} {}` does not fit in `{}`, {fallback} to {value}",
+                self.lhs,
+                self.op,
+                self.rhs,
+                any::type_name::<T>()
+            ),
+        );
+    }
+}
+
+/// Trait that provides alternatives to `checked_*().unwrap()` and explicit overflow fallbacks for
+/// integer arithmetic.
+pub trait BUChecked: Copy + Display {
+    /// Starts an addition whose overflow fallback is chosen on the returned [`Checked`].
+    fn try_add(self, rhs: Self) -> Checked<Self>;
+
+    /// Starts a subtraction whose overflow fallback is chosen on the returned [`Checked`].
+    fn try_sub(self, rhs: Self) -> Checked<Self>;
+
+    /// Starts a multiplication whose overflow fallback is chosen on the returned [`Checked`].
+    fn try_mul(self, rhs: Self) -> Checked<Self>;
+
+    /// Adds `rhs`.
+    ///
+    /// Equivalent to `checked_add(rhs).unwrap()`.
+    ///
+    /// # Panics
+    ///
+    /// Panics on overflow, with a panic message including the operands and type.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use better_unwrap::BUChecked;
+    ///
+    /// assert_eq!(200u8.checked_add_or_panic(55), 255);
+    /// ```
+    #[track_caller]
+    fn checked_add_or_panic(self, rhs: Self) -> Self {
+        self.try_add(rhs).or_panic()
+    }
+
+    /// Subtracts `rhs`.
+    ///
+    /// Equivalent to `checked_sub(rhs).unwrap()`.
+    ///
+    /// # Panics
+    ///
+    /// Panics on overflow, with a panic message including the operands and type.
+    ///
+    /// # Examples
+    ///
+    /// ```should_panic
+    /// use better_unwrap::BUChecked;
+    ///
+    /// 5u32.checked_sub_or_panic(10); // panics with `"arithmetic overflow: `5 - 10` does not fit in `u32`"`
+    /// ```
+    #[track_caller]
+    fn checked_sub_or_panic(self, rhs: Self) -> Self {
+        self.try_sub(rhs).or_panic()
+    }
+
+    /// Multiplies by `rhs`.
+    ///
+    /// Equivalent to `checked_mul(rhs).unwrap()`.
+    ///
+    /// # Panics
+    ///
+    /// Panics on overflow, with a panic message including the operands and type.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use better_unwrap::BUChecked;
+    ///
+    /// assert_eq!(12i64.checked_mul_or_panic(-3), -36);
+    /// ```
+    #[track_caller]
+    fn checked_mul_or_panic(self, rhs: Self) -> Self {
+        self.try_mul(rhs).or_panic()
+    }
+}
+
+macro_rules! impl_bu_checked {
+    ($($ty:ty),*) => {
+        $(
+            impl BUChecked for $ty {
+                fn try_add(self, rhs: Self) -> Checked<Self> {
+                    Checked {
+                        op: "+",
+                        lhs: self,
+                        rhs,
+                        checked: self.checked_add(rhs),
+                        saturating: self.saturating_add(rhs),
+                        wrapping: self.wrapping_add(rhs),
+                    }
+                }
+
+                fn try_sub(self, rhs: Self) -> Checked<Self> {
+                    Checked {
+                        op: "-",
+                        lhs: self,
+                        rhs,
+                        checked: self.checked_sub(rhs),
+                        saturating: self.saturating_sub(rhs),
+                        wrapping: self.wrapping_sub(rhs),
+                    }
+                }
+
+                fn try_mul(self, rhs: Self) -> Checked<Self> {
+                    Checked {
+                        op: "*",
+                        lhs: self,
+                        rhs,
+                        checked: self.checked_mul(rhs),
+                        saturating: self.saturating_mul(rhs),
+                        wrapping: self.wrapping_mul(rhs),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_bu_checked!(
+    u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize
+);
+
+/// Trait that provides an alternative to `NonZero::new(n).unwrap()` for integers.
+pub trait BUNonZero: Copy {
+    /// The `NonZero` counterpart of the integer type.
+    type NonZero;
+
+    /// Returns the integer as its `NonZero` counterpart.
+    ///
+    /// Equivalent to `NonZero::new(self).unwrap()`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the integer is zero, with a panic message including the type name.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::num::NonZeroUsize;
+    /// use better_unwrap::BUNonZero;
+    ///
+    /// let capacity: NonZeroUsize = 64usize.non_zero_or_panic();
+    /// assert_eq!(capacity.get(), 64);
+    /// ```
+    ///
+    /// ```should_panic
+    /// use better_unwrap::BUNonZero;
+    ///
+    /// 0u32.non_zero_or_panic(); // panics with `"expected a non-zero `u32`, found 0"`
+    /// ```
+    #[track_caller]
+    fn non_zero_or_panic(self) -> Self::NonZero;
+}
+
+macro_rules! impl_bu_non_zero {
+    ($($ty:ty),*) => {
+        $(
+            impl BUNonZero for $ty {
+                type NonZero = NonZero<$ty>;
+
+                #[track_caller]
+                fn non_zero_or_panic(self) -> NonZero<$ty> {
+                    match NonZero::new(self) {
+                        Some(value) => value,
+                        None => policy::fail(format_args!(
+                            "expected a non-zero `{}`, found 0",
+                            any::type_name::<$ty>()
+                        )),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_bu_non_zero!(
+    u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_checked_or_panic_without_overflow() {
+        assert_eq!(200u8.checked_add_or_panic(55), 255);
+        assert_eq!(10u32.checked_sub_or_panic(4), 6);
+        assert_eq!(12i64.checked_mul_or_panic(-3), -36);
+    }
+
+    #[test]
+    #[should_panic(expected = "arithmetic overflow: `200 + 100` does not fit in `u8`")]
+    fn test_checked_add_or_panic_panics_on_overflow() {
+        let _ = 200u8.checked_add_or_panic(100);
+    }
+
+    #[test]
+    #[should_panic(expected = "arithmetic overflow: `-128 * -1` does not fit in `i8`")]
+    fn test_try_mul_or_panic_panics_on_overflow() {
+        let _ = (-128i8).try_mul(-1).or_panic();
+    }
+
+    #[test]
+    fn test_or_saturate() {
+        assert_eq!(200u8.try_add(100).or_saturate(), 255);
+        assert_eq!(5u8.try_sub(10).or_saturate(), 0);
+        assert_eq!(i32::MIN.try_mul(2).or_saturate(), i32::MIN);
+        assert_eq!(2u8.try_add(3).or_saturate_logged(), 5);
+    }
+
+    #[test]
+    fn test_or_wrap() {
+        assert_eq!(200u8.try_add(100).or_wrap(), 44);
+        assert_eq!(5u8.try_sub(10).or_wrap(), 251);
+        assert_eq!(200u8.try_add(100).or_wrap_logged(), 44);
+    }
+
+    #[test]
+    fn test_non_zero_or_panic() {
+        assert_eq!(64usize.non_zero_or_panic().get(), 64);
+        assert_eq!((-3i8).non_zero_or_panic().get(), -3);
+    }
+
+    #[test]
+    #[should_panic(expected = "expected a non-zero `u32`, found 0")]
+    fn test_non_zero_or_panic_panics_on_zero() {
+        let _ = 0u32.non_zero_or_panic();
+    }
+
+    #[test]
+    fn test_checked() {
+        assert_eq!(200u8.try_add(55).checked(), Some(255));
+        assert_eq!(200u8.try_add(100).checked(), None);
+    }
+}
@@ -0,0 +1,145 @@
+//! Alternatives to `get().unwrap()` and `set(value).unwrap()` for cells that are initialized once.
+
+use std::any;
+use std::cell::OnceCell;
+use std::fmt::Debug;
+use std::sync::OnceLock;
+
+use crate::message::Truncated;
+use crate::policy;
+
+/// Trait that provides alternatives to `get().unwrap()` and `set(value).unwrap()` for `OnceLock<T>`
+/// and `OnceCell<T>`.
+///
+/// `set(value).unwrap()` on an initialized cell panics with only `Err(..)` showing the rejected value;
+/// these methods name the cell and show both the existing and the rejected value.
+pub trait BUOnce<T> {
+    /// Returns the value of an initialized cell.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the cell was never initialized, with a panic message including the cell's type.
+    ///
+    /// # Examples
+    ///
+    /// ```should_panic
+    /// use std::sync::OnceLock;
+    /// use better_unwrap::BUOnce;
+    ///
+    /// let config: OnceLock<u16> = OnceLock::new();
+    /// config.get_or_panic(); // panics with `"`OnceLock<u16>` was never initialized"`
+    /// ```
+    #[track_caller]
+    fn get_or_panic(&self) -> &T;
+
+    /// Initializes the cell with `value`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the cell is already initialized, with a panic message including the existing and the
+    /// rejected value formatted using `Debug`.
+    ///
+    /// # Examples
+    ///
+    /// ```should_panic
+    /// use std::cell::OnceCell;
+    /// use better_unwrap::BUOnce;
+    ///
+    /// let port = OnceCell::new();
+    /// port.set_or_panic(8080);
+    /// port.set_or_panic(8443); // panics with `"`OnceCell<i32>` was already initialized; existing value: 8080, rejected value: 8443"`
+    /// ```
+    #[track_caller]
+    fn set_or_panic(&self, value: T)
+    where
+        T: Debug;
+}
+
+impl<T> BUOnce<T> for OnceLock<T> {
+    #[track_caller]
+    fn get_or_panic(&self) -> &T {
+        match self.get() {
+            Some(value) => value,
+            None => uninitialized::<T>("OnceLock"),
+        }
+    }
+
+    #[track_caller]
+    fn set_or_panic(&self, value: T)
+    where
+        T: Debug,
+    {
+        if let Err(rejected) = self.set(value) {
+            already_initialized("OnceLock", self.get_or_panic(), &rejected)
+        }
+    }
+}
+
+impl<T> BUOnce<T> for OnceCell<T> {
+    #[track_caller]
+    fn get_or_panic(&self) -> &T {
+        match self.get() {
+            Some(value) => value,
+            None => uninitialized::<T>("OnceCell"),
+        }
+    }
+
+    #[track_caller]
+    fn set_or_panic(&self, value: T)
+    where
+        T: Debug,
+    {
+        if let Err(rejected) = self.set(value) {
+            already_initialized("OnceCell", self.get_or_panic(), &rejected)
+        }
+    }
+}
+
+#[track_caller]
+fn uninitialized<T>(cell: &str) -> ! {
+    policy::fail(format_args!(
+        "`{cell}<{}>` was never initialized",
+        any::type_name::<T>()
+    ))
+}
+
+#[track_caller]
+fn already_initialized<T: Debug>(cell: &str, existing: &T, rejected: &T) -> ! {
+    policy::fail(format_args!(
+        "`{cell}<{}>` was already initialized; existing value: {:?}, rejected value: {:?}",
+        any::type_name::<T>(),
+        Truncated(existing),
+        Truncated(rejected)
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_set_then_get() {
+        let lock = OnceLock::new();
+        lock.set_or_panic("ada");
+        assert_eq!(*lock.get_or_panic(), "ada");
+        let cell = OnceCell::new();
+        cell.set_or_panic(3);
+        assert_eq!(*cell.get_or_panic(), 3);
+    }
+
+    #[test]
+    #[should_panic(expected = "`OnceLock<alloc::string::String>` was never initialized")]
+    fn test_get_or_panic_names_type() {
+        OnceLock::<String>::new().get_or_panic();
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "`OnceLock<&str>` was already initialized; existing value: \"ada\", rejected value: \"grace\""
+    )]
+    fn test_set_or_panic_shows_both_values() {
+        let lock = OnceLock::new();
+        lock.set_or_panic("ada");
+        lock.set_or_panic("grace");
+    }
+}
@@ -0,0 +1,127 @@
+use std::fmt::Debug;
+use std::ops::ControlFlow;
+
+use crate::message::Truncated;
+use crate::policy;
+
+/// Trait that provides alternatives to `break_value().unwrap()` and `continue_value().unwrap()` for
+/// `ControlFlow<B, C>`.
+///
+/// Unlike unwrapping the `Option` returned by `break_value()`, the panic message includes the value
+/// of the other variant.
+pub trait BUControlFlow<B, C> {
+    /// Returns the value of a `ControlFlow::Break`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is `ControlFlow::Continue`, with a panic message including the continue
+    /// value formatted using `Debug`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::ops::ControlFlow;
+    /// use better_unwrap::BUControlFlow;
+    ///
+    /// let found = [3, 8, 11].iter().try_for_each(|&n| {
+    ///     if n > 5 { ControlFlow::Break(n) } else { ControlFlow::Continue(()) }
+    /// });
+    /// assert_eq!(found.break_or_panic(), 8);
+    /// ```
+    ///
+    /// ```should_panic
+    /// use std::ops::ControlFlow;
+    /// use better_unwrap::BUControlFlow;
+    ///
+    /// let x: ControlFlow<u32, &str> = ControlFlow::Continue("still scanning");
+    /// x.break_or_panic(); // panics with `"called `break_or_panic()` on a `Continue` value: \"still scanning\""`
+    /// ```
+    #[track_caller]
+    fn break_or_panic(self) -> B
+    where
+        C: Debug;
+
+    /// Returns the value of a `ControlFlow::Continue`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is `ControlFlow::Break`, with a panic message including the break value
+    /// formatted using `Debug`.
+    ///
+    /// # Examples
+    ///
+    /// ```should_panic
+    /// use std::ops::ControlFlow;
+    /// use better_unwrap::BUControlFlow;
+    ///
+    /// let x: ControlFlow<&str, u32> = ControlFlow::Break("limit reached");
+    /// x.continue_or_panic(); // panics with `"called `continue_or_panic()` on a `Break` value: \"limit reached\""`
+    /// ```
+    #[track_caller]
+    fn continue_or_panic(self) -> C
+    where
+        B: Debug;
+}
+
+impl<B, C> BUControlFlow<B, C> for ControlFlow<B, C> {
+    #[track_caller]
+    fn break_or_panic(self) -> B
+    where
+        C: Debug,
+    {
+        match self {
+            ControlFlow::Break(value) => value,
+            ControlFlow::Continue(value) => policy::fail(format_args!(
+                "called `break_or_panic()` on a `Continue` value: {value:?}",
+                value = Truncated(&value)
+            )),
+        }
+    }
+
+    #[track_caller]
+    fn continue_or_panic(self) -> C
+    where
+        B: Debug,
+    {
+        match self {
+            ControlFlow::Continue(value) => value,
+            ControlFlow::Break(value) => policy::fail(format_args!(
+                "called `continue_or_panic()` on a `Break` value: {value:?}",
+                value = Truncated(&value)
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_break_or_panic_with_break() {
+        let flow: ControlFlow<u32, ()> = ControlFlow::Break(8);
+        assert_eq!(flow.break_or_panic(), 8);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "called `break_or_panic()` on a `Continue` value: \"still scanning\""
+    )]
+    fn test_break_or_panic_panics_on_continue() {
+        let flow: ControlFlow<u32, &str> = ControlFlow::Continue("still scanning");
+        let _ = flow.break_or_panic();
+    }
+
+    #[test]
+    fn test_continue_or_panic_with_continue() {
+        let flow: ControlFlow<(), u32> = ControlFlow::Continue(3);
+        assert_eq!(flow.continue_or_panic(), 3);
+    }
+
+    #[test]
+    #[should_panic(expected = "called `continue_or_panic()` on a `Break` value: \"limit reached\"")]
+    fn test_continue_or_panic_panics_on_break() {
+        let flow: ControlFlow<&str, u32> = ControlFlow::Break("limit reached");
+        let _ = flow.continue_or_panic();
+    }
+}
@@ -1,4 +1,12 @@
-use std::default::Default;
+use core::default::Default;
+#[cfg(feature = "alloc")]
+use core::fmt::Debug;
+use core::fmt::Display;
+
+#[cfg(feature = "alloc")]
+use crate::diff::Diff;
+use crate::located::BULocatedError;
+use crate::policy;
 
 /// Trait that provides methods as alternatives to `unwrap()` and related methods for `Option<T>`.
 ///
@@ -18,8 +26,102 @@ pub trait BUOption<T> {
     /// let x: Option<u32> = None;
     /// x.or_panic(); // panics with `"called or_panic() on a None value"`
     /// ```
+    #[track_caller]
     fn or_panic(self) -> T;
 
+    /// Returns a reference to the content of a `Some` without consuming the option.
+    ///
+    /// Equivalent to `as_ref().unwrap()`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is `None`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use better_unwrap::BUOption;
+    ///
+    /// let x: Option<String> = Some("config.toml".to_string());
+    /// assert_eq!(x.or_panic_ref(), "config.toml");
+    /// ```
+    #[track_caller]
+    fn or_panic_ref(&self) -> &T;
+
+    /// Returns a mutable reference to the content of a `Some` without consuming the option.
+    ///
+    /// Equivalent to `as_mut().unwrap()`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is `None`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use better_unwrap::BUOption;
+    ///
+    /// let mut x: Option<Vec<u32>> = Some(vec![1]);
+    /// x.or_panic_mut().push(2);
+    /// assert_eq!(x, Some(vec![1, 2]));
+    /// ```
+    #[track_caller]
+    fn or_panic_mut(&mut self) -> &mut T;
+
+    /// Unwraps an option, yielding the content of a `Some`, with a backtrace in the panic message.
+    ///
+    /// The backtrace is captured even when `RUST_BACKTRACE` is unset, so rarely failing call sites
+    /// report where they were reached from without enabling backtraces for the whole process.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is `None`, with a panic message followed by the backtrace.
+    ///
+    /// # Examples
+    ///
+    /// ```should_panic
+    /// use better_unwrap::BUOption;
+    ///
+    /// let x: Option<u32> = None;
+    /// x.or_panic_with_backtrace(); // panics with `"called `or_panic_with_backtrace()` on a `None` value\n\nbacktrace:\n   0: ..."`
+    /// ```
+    #[cfg(feature = "std")]
+    #[track_caller]
+    fn or_panic_with_backtrace(self) -> T;
+
+    /// Unwraps an option and asserts that the content of the `Some` equals `expected`.
+    ///
+    /// Suited to tests comparing large values: on a mismatch the panic message shows a line diff of
+    /// the pretty `Debug` renderings, so only the fields that differ stand out.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is `None`, with a panic message including the expected value, or if the
+    /// content differs from `expected`, with a panic message including the diff, where lines only in
+    /// `expected` are prefixed with `-` and lines only in the actual value with `+`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use better_unwrap::BUOption;
+    ///
+    /// let ports = vec![8080, 8443];
+    /// ports.first().copied().expect_some_eq(8080);
+    /// ```
+    ///
+    /// ```should_panic
+    /// use better_unwrap::BUOption;
+    ///
+    /// let x: Option<[u16; 2]> = Some([8080, 8443]);
+    /// x.expect_some_eq([8080, 9443]); // panics with `"called `expect_some_eq()` on a `Some` value that differs from the expected one:\n- expected\n+ actual\n\n  [\n      8080,\n-     9443,\n+     8443,\n  ]"`
+    /// ```
+    #[cfg(feature = "alloc")]
+    #[track_caller]
+    fn expect_some_eq<U>(self, expected: U)
+    where
+        T: PartialEq<U> + Debug,
+        U: Debug;
+
     /// Returns the contained value or a provided default.
     ///
     /// Equivalent to `unwrap_or()`.
@@ -32,6 +134,7 @@ pub trait BUOption<T> {
     /// let y: Option<u32> = None;
     /// assert_eq!(y.panic_or(100), 100);
     /// ```
+    #[track_caller]
     fn panic_or(self, default: T) -> T;
 
     /// Returns the contained value or computes it from a closure.
@@ -46,6 +149,7 @@ pub trait BUOption<T> {
     /// let y: Option<u32> = None;
     /// assert_eq!(y.panic_or_else(|| 100), 100);
     /// ```
+    #[track_caller]
     fn panic_or_else<F>(self, f: F) -> T
     where
         F: FnOnce() -> T;
@@ -62,14 +166,99 @@ pub trait BUOption<T> {
     /// let y: Option<String> = None;
     /// assert_eq!(y.panic_or_default(), String::new());
     /// ```
+    #[track_caller]
     fn panic_or_default(self) -> T
     where
         T: Default;
 
+    /// Returns the contained value or a provided default, logging that the default was used.
+    ///
+    /// Like `panic_or()`, but a `None` is logged with the caller location instead of silently
+    /// disappearing.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use better_unwrap::BUOption;
+    ///
+    /// let y: Option<u32> = None;
+    /// assert_eq!(y.panic_or_log(100), 100); // logs the `None` to stderr
+    /// ```
+    #[cfg(feature = "std")]
+    #[track_caller]
+    fn panic_or_log(self, default: T) -> T;
+
+    /// Returns the contained value or computes it from a closure, logging that the closure was used.
+    ///
+    /// Like `panic_or_else()`, but a `None` is logged with the caller location instead of silently
+    /// disappearing.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use better_unwrap::BUOption;
+    ///
+    /// let y: Option<u32> = None;
+    /// assert_eq!(y.panic_or_log_else(|| 100), 100); // logs the `None` to stderr
+    /// ```
+    #[cfg(feature = "std")]
+    #[track_caller]
+    fn panic_or_log_else<F>(self, f: F) -> T
+    where
+        F: FnOnce() -> T;
+
+    /// Unwraps an option in debug builds, and returns the contained value or a provided default in
+    /// release builds.
+    ///
+    /// The `debug_assertions` equivalent of unwrapping: behaves like `or_panic()` when
+    /// `debug_assertions` are enabled, and like `panic_or(default)` otherwise, so a missing value
+    /// fails loudly during development without taking down a release build.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is `None` and `debug_assertions` are enabled.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use better_unwrap::BUOption;
+    ///
+    /// let x: Option<u32> = Some(2);
+    /// assert_eq!(x.debug_panic_or(100), 2);
+    /// ```
+    #[track_caller]
+    fn debug_panic_or(self, default: T) -> T;
+
+    /// Unwraps an option in debug builds, and returns the contained value or computes it from a
+    /// closure in release builds.
+    ///
+    /// Behaves like `or_panic()` when `debug_assertions` are enabled, and like `panic_or_else(f)`
+    /// otherwise.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is `None` and `debug_assertions` are enabled.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use better_unwrap::BUOption;
+    ///
+    /// let x: Option<u32> = Some(2);
+    /// assert_eq!(x.debug_panic_or_else(|| 100), 2);
+    /// ```
+    #[track_caller]
+    fn debug_panic_or_else<F>(self, f: F) -> T
+    where
+        F: FnOnce() -> T;
+
     /// Unwraps an option, yielding the content of a `Some`.
     ///
     /// Equivalent to `expect()`, but with a clearer name.
     ///
+    /// The message can be anything that implements `Display`, such as `format_args!(...)`, which is
+    /// only rendered if the value is `None`.
+    ///
     /// # Panics
     ///
     /// Panics if the value is `None`, with a panic message including the provided message.
@@ -82,37 +271,608 @@ pub trait BUOption<T> {
     /// let x: Option<u32> = None;
     /// x.panic_with("Expected a value"); // panics with `"Expected a value"`
     /// ```
-    fn panic_with(self, msg: &str) -> T;
+    ///
+    /// ```should_panic
+    /// use better_unwrap::BUOption;
+    ///
+    /// let key = "region";
+    /// let x: Option<&str> = None;
+    /// x.panic_with(format_args!("missing config key {key:?}")); // panics with `"missing config key \"region\""`
+    /// ```
+    #[track_caller]
+    fn panic_with<M: Display>(self, msg: M) -> T;
+
+    /// Unwraps an option, yielding the content of a `Some`.
+    ///
+    /// Like `panic_with()`, but the message is computed by a closure that only runs if the value is
+    /// `None`, so no formatting happens on the happy path.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is `None`, with the message returned by `f`.
+    ///
+    /// # Examples
+    ///
+    /// ```should_panic
+    /// use better_unwrap::BUOption;
+    ///
+    /// let key = "region";
+    /// let x: Option<&str> = None;
+    /// x.panic_with_else(|| format!("missing config key {key:?}")); // panics with `"missing config key \"region\""`
+    /// ```
+    #[track_caller]
+    fn panic_with_else<F, M>(self, f: F) -> T
+    where
+        F: FnOnce() -> M,
+        M: Display;
+
+    /// Transforms the option into a `Result`, mapping `None` to a [`BULocatedError`] that records the
+    /// caller location.
+    ///
+    /// Equivalent to `ok_or(...)`, for a `None` that should propagate to the caller rather than panic
+    /// while still saying where it came from.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use better_unwrap::BUOption;
+    ///
+    /// let x: Option<u32> = None;
+    /// let error = x.ok_or_located_err().unwrap_err();
+    /// assert_eq!(error.location().file(), file!());
+    /// ```
+    #[track_caller]
+    fn ok_or_located_err(self) -> Result<T, BULocatedError>;
+
+    /// Transforms the option into a `Result`, mapping `None` to a [`BULocatedError`] that records the
+    /// caller location and `message`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use better_unwrap::BUOption;
+    ///
+    /// let x: Option<u32> = None;
+    /// let error = x.ok_or_located_err_with("no user id in the request").unwrap_err();
+    /// assert_eq!(error.message(), Some("no user id in the request"));
+    /// ```
+    #[track_caller]
+    fn ok_or_located_err_with(self, message: &'static str) -> Result<T, BULocatedError>;
+
+    /// Unwraps an option, yielding the content of a `Some`, or exits the process if the value is
+    /// `None`.
+    ///
+    /// Instead of panicking with a backtrace, prints `"error: called `or_exit()` on a `None` value"`
+    /// to stderr and calls [`std::process::exit`] with `code`, which suits command-line tools. Like
+    /// any call to `process::exit`, destructors on the stack do not run.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use better_unwrap::BUOption;
+    ///
+    /// let config_path = Some("app.toml");
+    /// assert_eq!(config_path.or_exit(1), "app.toml");
+    /// ```
+    #[cfg(feature = "std")]
+    #[track_caller]
+    fn or_exit(self, code: i32) -> T;
+
+    /// Unwraps an option, yielding the content of a `Some`, or exits the process with `code` and a
+    /// custom message if the value is `None`.
+    ///
+    /// Like `or_exit()`, but prints `"error: {msg}"` to stderr, for a user-facing line.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use better_unwrap::BUOption;
+    ///
+    /// let config_path = Some("app.toml");
+    /// assert_eq!(config_path.or_exit_with(64, "no configuration file given"), "app.toml");
+    /// ```
+    #[cfg(feature = "std")]
+    #[track_caller]
+    fn or_exit_with<M: Display>(self, code: i32, msg: M) -> T;
+
+    /// Unwraps an option, yielding the content of a `Some`, or exits the process with the code and
+    /// message returned by `f` if the value is `None`.
+    ///
+    /// `f` is only called on failure. The message is printed to stderr as `"error: {message}"`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use better_unwrap::BUOption;
+    ///
+    /// let user = Some("ada");
+    /// let name = user.or_exit_with_else(|| (67, format!("unknown user, try `{} --list`", "app")));
+    /// assert_eq!(name, "ada");
+    /// ```
+    #[cfg(feature = "std")]
+    #[track_caller]
+    fn or_exit_with_else<F, M>(self, f: F) -> T
+    where
+        F: FnOnce() -> (i32, M),
+        M: Display;
+
+    /// Unwraps an option, yielding the content of a `Some`, or aborts the process if the value is
+    /// `None`.
+    ///
+    /// Prints the same message as `or_panic()`, with the caller location, to stderr and calls
+    /// [`std::process::abort`], which never unwinds even under `panic = "unwind"`. Use it where
+    /// unwinding is not allowed, such as inside FFI callbacks.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use better_unwrap::BUOption;
+    ///
+    /// let handle = Some(3);
+    /// assert_eq!(handle.or_abort(), 3);
+    /// ```
+    #[cfg(feature = "std")]
+    #[track_caller]
+    fn or_abort(self) -> T;
 }
 
 impl<T> BUOption<T> for Option<T> {
+    #[track_caller]
     fn or_panic(self) -> T {
         match self {
             Some(value) => value,
-            None => panic!("called `or_panic()` on a `None` value"),
+            None => policy::fail_detailed(
+                "or_panic",
+                None,
+                format_args!("called `or_panic()` on a `None` value"),
+            ),
         }
     }
 
+    #[track_caller]
+    fn or_panic_ref(&self) -> &T {
+        match self {
+            Some(value) => value,
+            None => policy::fail_detailed(
+                "or_panic_ref",
+                None,
+                format_args!("called `or_panic_ref()` on a `None` value"),
+            ),
+        }
+    }
+
+    #[track_caller]
+    fn or_panic_mut(&mut self) -> &mut T {
+        match self {
+            Some(value) => value,
+            None => policy::fail_detailed(
+                "or_panic_mut",
+                None,
+                format_args!("called `or_panic_mut()` on a `None` value"),
+            ),
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[track_caller]
+    fn or_panic_with_backtrace(self) -> T {
+        match self {
+            Some(value) => value,
+            None => policy::fail_detailed(
+                "or_panic_with_backtrace",
+                None,
+                format_args!(
+                    "called `or_panic_with_backtrace()` on a `None` value\n\nbacktrace:\n{}",
+                    std::backtrace::Backtrace::force_capture()
+                ),
+            ),
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    #[track_caller]
+    fn expect_some_eq<U>(self, expected: U)
+    where
+        T: PartialEq<U> + Debug,
+        U: Debug,
+    {
+        match self {
+            Some(value) if value == expected => {}
+            Some(value) => policy::fail_detailed(
+                "expect_some_eq",
+                None,
+                format_args!(
+                    "called `expect_some_eq()` on a `Some` value that differs from the expected one:\n{}",
+                    Diff {
+                        expected: &expected,
+                        actual: &value,
+                    }
+                ),
+            ),
+            None => policy::fail_detailed(
+                "expect_some_eq",
+                None,
+                format_args!(
+                    "called `expect_some_eq()` on a `None` value\nexpected: Some({expected:?})",
+                    expected = crate::message::Truncated(&expected)
+                ),
+            ),
+        }
+    }
+
+    #[cfg_attr(
+        all(
+            feature = "no-panic",
+            not(feature = "test-strict"),
+            not(feature = "metrics"),
+            not(feature = "tracing"),
+            not(debug_assertions)
+        ),
+        no_panic::no_panic
+    )]
+    #[track_caller]
     fn panic_or(self, default: T) -> T {
-        self.unwrap_or(default)
+        match self {
+            Some(value) => value,
+            None => {
+                crate::strict::check("panic_or", "a `None` value");
+                #[cfg(any(feature = "metrics", feature = "tracing"))]
+                crate::emit::fallback("panic_or");
+                default
+            }
+        }
     }
 
+    #[cfg_attr(
+        all(
+            feature = "no-panic",
+            not(feature = "test-strict"),
+            not(feature = "metrics"),
+            not(feature = "tracing"),
+            not(debug_assertions)
+        ),
+        no_panic::no_panic
+    )]
+    #[track_caller]
     fn panic_or_else<F>(self, f: F) -> T
     where
         F: FnOnce() -> T,
     {
-        self.unwrap_or_else(f)
+        match self {
+            Some(value) => value,
+            None => {
+                crate::strict::check("panic_or_else", "a `None` value");
+                #[cfg(any(feature = "metrics", feature = "tracing"))]
+                crate::emit::fallback("panic_or_else");
+                f()
+            }
+        }
     }
 
+    // Not `unwrap_or_default()`, so that optional features can instrument the fallback branch.
+    #[allow(clippy::manual_unwrap_or_default)]
+    #[cfg_attr(
+        all(
+            feature = "no-panic",
+            not(feature = "test-strict"),
+            not(feature = "metrics"),
+            not(feature = "tracing"),
+            not(debug_assertions)
+        ),
+        no_panic::no_panic
+    )]
+    #[track_caller]
     fn panic_or_default(self) -> T
     where
         T: Default,
     {
-        self.unwrap_or_default()
+        match self {
+            Some(value) => value,
+            None => {
+                crate::strict::check("panic_or_default", "a `None` value");
+                #[cfg(any(feature = "metrics", feature = "tracing"))]
+                crate::emit::fallback("panic_or_default");
+                T::default()
+            }
+        }
     }
 
-    fn panic_with(self, msg: &str) -> T {
-        self.expect(msg)
+    #[cfg(feature = "std")]
+    #[track_caller]
+    fn panic_or_log(self, default: T) -> T {
+        match self {
+            Some(value) => value,
+            None => {
+                crate::emit::log_fallback("panic_or_log", format_args!("a `None` value"));
+                default
+            }
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[track_caller]
+    fn panic_or_log_else<F>(self, f: F) -> T
+    where
+        F: FnOnce() -> T,
+    {
+        match self {
+            Some(value) => value,
+            None => {
+                crate::emit::log_fallback("panic_or_log_else", format_args!("a `None` value"));
+                f()
+            }
+        }
+    }
+
+    #[cfg_attr(
+        all(
+            feature = "no-panic",
+            not(feature = "test-strict"),
+            not(feature = "metrics"),
+            not(feature = "tracing"),
+            not(debug_assertions)
+        ),
+        no_panic::no_panic
+    )]
+    #[track_caller]
+    fn debug_panic_or(self, default: T) -> T {
+        match self {
+            Some(value) => value,
+            None if cfg!(debug_assertions) => policy::fail_detailed(
+                "debug_panic_or",
+                None,
+                format_args!("called `debug_panic_or()` on a `None` value"),
+            ),
+            None => {
+                #[cfg(any(feature = "metrics", feature = "tracing"))]
+                crate::emit::fallback("debug_panic_or");
+                default
+            }
+        }
+    }
+
+    #[cfg_attr(
+        all(
+            feature = "no-panic",
+            not(feature = "test-strict"),
+            not(feature = "metrics"),
+            not(feature = "tracing"),
+            not(debug_assertions)
+        ),
+        no_panic::no_panic
+    )]
+    #[track_caller]
+    fn debug_panic_or_else<F>(self, f: F) -> T
+    where
+        F: FnOnce() -> T,
+    {
+        match self {
+            Some(value) => value,
+            None if cfg!(debug_assertions) => policy::fail_detailed(
+                "debug_panic_or_else",
+                None,
+                format_args!("called `debug_panic_or_else()` on a `None` value"),
+            ),
+            None => {
+                #[cfg(any(feature = "metrics", feature = "tracing"))]
+                crate::emit::fallback("debug_panic_or_else");
+                f()
+            }
+        }
+    }
+
+    #[track_caller]
+    fn panic_with<M: Display>(self, msg: M) -> T {
+        match self {
+            Some(value) => value,
+            None => policy::fail_detailed("panic_with", None, format_args!("{msg}")),
+        }
+    }
+
+    #[track_caller]
+    fn panic_with_else<F, M>(self, f: F) -> T
+    where
+        F: FnOnce() -> M,
+        M: Display,
+    {
+        match self {
+            Some(value) => value,
+            None => policy::fail_detailed("panic_with_else", None, format_args!("{}", f())),
+        }
+    }
+
+    #[track_caller]
+    fn ok_or_located_err(self) -> Result<T, BULocatedError> {
+        match self {
+            Some(value) => Ok(value),
+            None => Err(BULocatedError::new(None)),
+        }
+    }
+
+    #[track_caller]
+    fn ok_or_located_err_with(self, message: &'static str) -> Result<T, BULocatedError> {
+        match self {
+            Some(value) => Ok(value),
+            None => Err(BULocatedError::new(Some(message))),
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[track_caller]
+    fn or_exit(self, code: i32) -> T {
+        match self {
+            Some(value) => value,
+            None => crate::emit::exit(code, format_args!("called `or_exit()` on a `None` value")),
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[track_caller]
+    fn or_exit_with<M: Display>(self, code: i32, msg: M) -> T {
+        match self {
+            Some(value) => value,
+            None => crate::emit::exit(code, format_args!("{msg}")),
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[track_caller]
+    fn or_exit_with_else<F, M>(self, f: F) -> T
+    where
+        F: FnOnce() -> (i32, M),
+        M: Display,
+    {
+        match self {
+            Some(value) => value,
+            None => {
+                let (code, msg) = f();
+                crate::emit::exit(code, format_args!("{msg}"))
+            }
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[track_caller]
+    fn or_abort(self) -> T {
+        match self {
+            Some(value) => value,
+            None => crate::emit::abort(&crate::Report::capture(format_args!(
+                "called `or_abort()` on a `None` value"
+            ))),
+        }
+    }
+}
+
+/// Trait that provides alternatives to `copied().unwrap()` and `cloned().unwrap()` for `Option<&T>`,
+/// such as the results of map lookups.
+pub trait BUOptionRef<T> {
+    /// Copies the referenced content of a `Some`.
+    ///
+    /// Equivalent to `copied().unwrap()`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is `None`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::collections::HashMap;
+    /// use better_unwrap::BUOptionRef;
+    ///
+    /// let ports = HashMap::from([("https", 443)]);
+    /// assert_eq!(ports.get("https").copied_or_panic(), 443);
+    /// ```
+    #[track_caller]
+    fn copied_or_panic(self) -> T
+    where
+        T: Copy;
+
+    /// Clones the referenced content of a `Some`.
+    ///
+    /// Equivalent to `cloned().unwrap()`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is `None`.
+    ///
+    /// # Examples
+    ///
+    /// ```should_panic
+    /// use std::collections::HashMap;
+    /// use better_unwrap::BUOptionRef;
+    ///
+    /// let hosts: HashMap<&str, String> = HashMap::new();
+    /// hosts.get("primary").cloned_or_panic(); // panics with `"called `cloned_or_panic()` on a `None` value"`
+    /// ```
+    #[track_caller]
+    fn cloned_or_panic(self) -> T
+    where
+        T: Clone;
+}
+
+impl<T> BUOptionRef<T> for Option<&T> {
+    #[track_caller]
+    fn copied_or_panic(self) -> T
+    where
+        T: Copy,
+    {
+        match self {
+            Some(value) => *value,
+            None => policy::fail_detailed(
+                "copied_or_panic",
+                None,
+                format_args!("called `copied_or_panic()` on a `None` value"),
+            ),
+        }
+    }
+
+    #[track_caller]
+    fn cloned_or_panic(self) -> T
+    where
+        T: Clone,
+    {
+        match self {
+            Some(value) => value.clone(),
+            None => policy::fail_detailed(
+                "cloned_or_panic",
+                None,
+                format_args!("called `cloned_or_panic()` on a `None` value"),
+            ),
+        }
     }
 }
 
+/// Trait that provides an alternative to `flatten().unwrap()` for nested options, such as a cache
+/// lookup whose outer layer says whether the key was cached and whose inner layer holds the value.
+pub trait BUNestedOption<T> {
+    /// Unwraps both layers of a nested option, yielding the content of the inner `Some`.
+    ///
+    /// Unlike `flatten().or_panic()`, the panic message says which layer was `None`. For deeper
+    /// nesting, each call unwraps the two outermost layers.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the outer or the inner option is `None`, with a panic message naming the layer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use better_unwrap::BUNestedOption;
+    ///
+    /// let cached: Option<Option<u16>> = Some(Some(8080));
+    /// assert_eq!(cached.flatten_or_panic(), 8080);
+    /// ```
+    ///
+    /// ```should_panic
+    /// use better_unwrap::BUNestedOption;
+    ///
+    /// let cached: Option<Option<u16>> = Some(None);
+    /// cached.flatten_or_panic(); // panics with `"called `flatten_or_panic()` on a `Some(None)` value: the inner option is `None`"`
+    /// ```
+    #[track_caller]
+    fn flatten_or_panic(self) -> T;
+}
+
+impl<T> BUNestedOption<T> for Option<Option<T>> {
+    #[track_caller]
+    fn flatten_or_panic(self) -> T {
+        match self {
+            Some(Some(value)) => value,
+            Some(None) => policy::fail_detailed(
+                "flatten_or_panic",
+                None,
+                format_args!(
+                    "called `flatten_or_panic()` on a `Some(None)` value: the inner option is `None`"
+                ),
+            ),
+            None => policy::fail_detailed(
+                "flatten_or_panic",
+                None,
+                format_args!(
+                    "called `flatten_or_panic()` on a `None` value: the outer option is `None`"
+                ),
+            ),
+        }
+    }
+}
@@ -0,0 +1,220 @@
+//! Structured panic payloads, for panic hooks and `catch_unwind` callers that handle failures
+//! programmatically.
+//!
+//! By default the crate's failures panic with a `String` payload, like `unwrap()` does, so
+//! `#[should_panic(expected = "...")]` and existing `downcast_ref::<String>()` calls keep working.
+//! With structured payloads enabled, failures panic with a [`BUPanicInfo`] instead, which records the
+//! method that failed, the rendered message, the error, and the caller location as separate fields.
+//!
+//! The default panic hook prints `Box<dyn Any>` for payloads that are not strings, so enable
+//! structured payloads together with [`install_panic_hook`](crate::install_panic_hook), which renders
+//! them like any other failure.
+//!
+//! # Examples
+//!
+//! ```
+//! use std::panic;
+//! use better_unwrap::payload::{self, BUPanicInfo};
+//! use better_unwrap::prelude::*;
+//!
+//! let payload = panic::catch_unwind(|| {
+//!     payload::with_structured(|| {
+//!         let port: Result<u16, &str> = Err("not a number");
+//!         port.or_panic()
+//!     })
+//! })
+//! .or_panic_err();
+//! let info = payload.downcast_ref::<BUPanicInfo>().or_panic();
+//! assert_eq!(info.method(), Some("or_panic"));
+//! assert_eq!(info.error_debug(), Some("\"not a number\""));
+//! ```
+
+use std::cell::Cell;
+use std::fmt;
+use std::panic::Location;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use crate::report::Report;
+
+static STRUCTURED: AtomicBool = AtomicBool::new(false);
+
+thread_local! {
+    static SCOPED: Cell<bool> = const { Cell::new(false) };
+}
+
+/// A failure of the crate's panicking methods, raised as the panic payload when structured payloads
+/// are enabled.
+///
+/// Displays as the rendered failure message, the same text an unstructured failure panics with.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BUPanicInfo {
+    method: Option<&'static str>,
+    message: String,
+    error_debug: Option<String>,
+    location: &'static Location<'static>,
+}
+
+impl BUPanicInfo {
+    /// Captures the payload of a failure reported at `location`.
+    // Under `fuzzing`, failures abort instead of panicking.
+    #[cfg_attr(all(feature = "fuzzing", not(test)), allow(dead_code))]
+    pub(crate) fn capture(report: &Report<'_>, location: &'static Location<'static>) -> Self {
+        BUPanicInfo {
+            method: report.method(),
+            message: report.to_string(),
+            error_debug: report.error_debug(),
+            location,
+        }
+    }
+
+    /// Returns the name of the method that failed, such as `"or_panic"`, when it is known.
+    pub fn method(&self) -> Option<&'static str> {
+        self.method
+    }
+
+    /// Returns the rendered failure message, including any report sections.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// Returns the `Debug` rendering of the error the method was called on, when it failed on an
+    /// `Err` value.
+    pub fn error_debug(&self) -> Option<&str> {
+        self.error_debug.as_deref()
+    }
+
+    /// Returns the location of the failing call.
+    pub fn location(&self) -> &'static Location<'static> {
+        self.location
+    }
+}
+
+impl fmt::Display for BUPanicInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+/// Enables or disables structured payloads for failures on every thread.
+///
+/// # Examples
+///
+/// ```
+/// use better_unwrap::payload;
+///
+/// better_unwrap::install_panic_hook();
+/// payload::set_structured(true);
+/// ```
+pub fn set_structured(enabled: bool) {
+    STRUCTURED.store(enabled, Ordering::Relaxed);
+}
+
+/// Restores the previous scoped setting when a scope ends, including on unwind.
+struct ScopedGuard(bool);
+
+impl Drop for ScopedGuard {
+    fn drop(&mut self) {
+        SCOPED.set(self.0);
+    }
+}
+
+/// Runs `f` with structured payloads enabled for failures on the current thread.
+///
+/// # Examples
+///
+/// ```
+/// use std::panic;
+/// use better_unwrap::payload::{self, BUPanicInfo};
+/// use better_unwrap::prelude::*;
+///
+/// let payload = panic::catch_unwind(|| {
+///     payload::with_structured(|| {
+///         let session: Option<u64> = None;
+///         session.panic_with("no active session")
+///     })
+/// })
+/// .or_panic_err();
+/// assert_eq!(payload.downcast_ref::<BUPanicInfo>().or_panic().to_string(), "no active session");
+/// ```
+pub fn with_structured<R>(f: impl FnOnce() -> R) -> R {
+    let _guard = ScopedGuard(SCOPED.replace(true));
+    f()
+}
+
+/// Returns whether failures on the current thread panic with a [`BUPanicInfo`].
+#[cfg_attr(all(feature = "fuzzing", not(test)), allow(dead_code))]
+pub(crate) fn is_structured() -> bool {
+    SCOPED.get() || STRUCTURED.load(Ordering::Relaxed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{BUIndex, BUOption, BUOptionRef, BUResult};
+    use std::panic;
+
+    fn capture(f: impl FnOnce() + panic::UnwindSafe) -> BUPanicInfo {
+        let payload = panic::catch_unwind(|| with_structured(f)).or_panic_err();
+        *payload.downcast::<BUPanicInfo>().or_panic()
+    }
+
+    #[test]
+    fn test_option_payload_records_method_and_location() {
+        let line = line!() + 1;
+        let info = capture(|| _ = None::<u32>.or_panic_ref());
+        assert_eq!(info.method(), Some("or_panic_ref"));
+        assert_eq!(info.message(), "called `or_panic_ref()` on a `None` value");
+        assert_eq!(info.error_debug(), None);
+        assert_eq!(info.location().file(), file!());
+        assert_eq!(info.location().line(), line);
+    }
+
+    #[test]
+    fn test_result_payload_records_error() {
+        let info = capture(|| {
+            let result: Result<u32, &str> = Err("refused");
+            result.panic_with("connecting to the broker");
+        });
+        assert_eq!(info.method(), Some("panic_with"));
+        assert_eq!(info.message(), "connecting to the broker: \"refused\"");
+        assert_eq!(info.error_debug(), Some("\"refused\""));
+        assert_eq!(info.to_string(), info.message());
+    }
+
+    #[test]
+    fn test_payload_records_method_of_every_entry_point() {
+        let info = capture(|| {
+            None::<&u32>.copied_or_panic();
+        });
+        assert_eq!(info.method(), Some("copied_or_panic"));
+        let info = capture(|| {
+            "4x".parse::<u32>().or_panic_display();
+        });
+        assert_eq!(info.method(), Some("or_panic_display"));
+        assert_eq!(info.error_debug(), None);
+        let info = capture(|| {
+            "4x".parse::<u32>().or_panic_chained();
+        });
+        assert_eq!(info.method(), Some("or_panic_chained"));
+        assert_eq!(
+            info.error_debug(),
+            Some("ParseIntError { kind: InvalidDigit }")
+        );
+    }
+
+    #[test]
+    fn test_payload_without_method() {
+        let info = capture(|| {
+            [1, 2].index_or_panic(5);
+        });
+        assert_eq!(info.method(), None);
+        assert_eq!(info.error_debug(), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "called `or_panic()` on a `None` value")]
+    fn test_payload_is_string_outside_scope() {
+        with_structured(|| {});
+        None::<u32>.or_panic();
+    }
+}
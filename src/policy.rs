@@ -0,0 +1,477 @@
+//! Policies controlling how the crate's panicking methods handle a failure.
+//!
+//! By default every failure panics. An application can choose a different process-wide policy with
+//! [`set`], such as [`Policy::Abort`] in software that must never unwind. A policy can also be
+//! overridden for the duration of a closure on the current thread with [`with`], or with
+//! [`with_default`] when the closure should be abandoned and return `Default::default()` instead of
+//! bringing the thread down.
+//!
+//! # Examples
+//!
+//! ```
+//! use better_unwrap::prelude::*;
+//! use better_unwrap::policy::{self, Policy};
+//!
+//! let flushed: u32 = policy::with_default(Policy::LogAndDefault, || {
+//!     let pending: Option<u32> = None;
+//!     pending.or_panic() // logged instead of panicking
+//! });
+//! assert_eq!(flushed, 0);
+//! ```
+
+use std::cell::Cell;
+use std::error::Error;
+use std::fmt::{self, Debug};
+use std::panic::{self, AssertUnwindSafe, Location};
+use std::sync::atomic::{AtomicU8, Ordering};
+
+use crate::emit;
+use crate::report::Report;
+
+/// How the crate's panicking methods handle a failure.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Policy {
+    /// Panic with the failure message. This is the default.
+    Panic,
+    /// Log the failure with its caller location and return `Default::default()` from the nearest
+    /// enclosing [`with_default`] scope.
+    ///
+    /// Only a [`with_default`] scope has a default to return, so this policy cannot be set
+    /// process-wide: [`set`] returns a [`SetPolicyError`] for it. Under [`with`] alone, failures
+    /// panic as under [`Policy::Panic`].
+    LogAndDefault,
+    /// Print the failure message with its caller location to stderr and abort the process, without
+    /// unwinding.
+    Abort,
+}
+
+impl Policy {
+    fn from_bits(bits: u8) -> Policy {
+        match bits {
+            1 => Policy::LogAndDefault,
+            2 => Policy::Abort,
+            _ => Policy::Panic,
+        }
+    }
+}
+
+/// The error returned by [`set`] for a policy that cannot be in effect process-wide.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SetPolicyError(Policy);
+
+impl SetPolicyError {
+    /// Returns the policy that was rejected.
+    pub fn policy(&self) -> Policy {
+        self.0
+    }
+}
+
+impl fmt::Display for SetPolicyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "`Policy::{:?}` cannot be set process-wide; run the work in `policy::with_default()` instead",
+            self.0
+        )
+    }
+}
+
+impl Error for SetPolicyError {}
+
+/// The process-wide policy, as set with [`set`].
+static GLOBAL: AtomicU8 = AtomicU8::new(Policy::Panic as u8);
+
+thread_local! {
+    static OVERRIDE: Cell<Option<Policy>> = const { Cell::new(None) };
+    static DEFAULT_SCOPES: Cell<usize> = const { Cell::new(0) };
+}
+
+/// Unwind payload used to abandon a [`with_default`] scope under [`Policy::LogAndDefault`].
+struct Defaulted;
+
+/// Restores the previous thread-local override when a scope ends, including on unwind.
+struct OverrideGuard(Option<Policy>);
+
+impl Drop for OverrideGuard {
+    fn drop(&mut self) {
+        OVERRIDE.set(self.0);
+    }
+}
+
+/// Tracks how many [`with_default`] scopes are active on the current thread.
+struct DefaultScope;
+
+impl DefaultScope {
+    fn enter() -> Self {
+        DEFAULT_SCOPES.set(DEFAULT_SCOPES.get() + 1);
+        DefaultScope
+    }
+}
+
+impl Drop for DefaultScope {
+    fn drop(&mut self) {
+        DEFAULT_SCOPES.set(DEFAULT_SCOPES.get() - 1);
+    }
+}
+
+/// Sets the process-wide policy, in effect on every thread that has not overridden it with [`with`]
+/// or [`with_default`].
+///
+/// # Errors
+///
+/// Returns an error, leaving the policy unchanged, if `policy` is [`Policy::LogAndDefault`], which
+/// needs a [`with_default`] scope to return a default from. For a best-effort batch job, run each
+/// unit of work in `with_default(Policy::LogAndDefault, ..)` instead.
+///
+/// # Examples
+///
+/// ```no_run
+/// use better_unwrap::prelude::*;
+/// use better_unwrap::policy::{self, Policy};
+///
+/// // Flight software: never unwind through the control loop.
+/// policy::set(Policy::Abort).or_panic();
+/// ```
+///
+/// ```
+/// use better_unwrap::policy::{self, Policy};
+///
+/// let error = policy::set(Policy::LogAndDefault).unwrap_err();
+/// assert_eq!(error.policy(), Policy::LogAndDefault);
+/// assert_eq!(policy::current(), Policy::Panic);
+/// ```
+pub fn set(policy: Policy) -> Result<(), SetPolicyError> {
+    if policy == Policy::LogAndDefault {
+        return Err(SetPolicyError(policy));
+    }
+    GLOBAL.store(policy as u8, Ordering::Relaxed);
+    Ok(())
+}
+
+/// Returns the policy in effect on the current thread: its override, if any, or the process-wide
+/// policy.
+///
+/// # Examples
+///
+/// ```
+/// use better_unwrap::policy::{self, Policy};
+///
+/// assert_eq!(policy::current(), Policy::Panic);
+/// policy::with(Policy::LogAndDefault, || {
+///     assert_eq!(policy::current(), Policy::LogAndDefault);
+/// });
+/// ```
+pub fn current() -> Policy {
+    OVERRIDE
+        .get()
+        .unwrap_or_else(|| Policy::from_bits(GLOBAL.load(Ordering::Relaxed)))
+}
+
+/// Runs `f` with `policy` in effect on the current thread.
+///
+/// The override takes precedence over the process-wide policy set with [`set`], and does not apply
+/// to other threads, including threads spawned by `f`. The previous policy is restored when `f`
+/// returns or unwinds.
+///
+/// # Examples
+///
+/// ```
+/// use std::thread;
+/// use better_unwrap::policy::{self, Policy};
+///
+/// policy::with(Policy::LogAndDefault, || {
+///     assert_eq!(policy::current(), Policy::LogAndDefault);
+///     let elsewhere = thread::spawn(policy::current).join();
+///     assert_eq!(elsewhere.ok(), Some(Policy::Panic));
+/// });
+/// ```
+pub fn with<R>(policy: Policy, f: impl FnOnce() -> R) -> R {
+    let _guard = OverrideGuard(OVERRIDE.replace(Some(policy)));
+    f()
+}
+
+/// Runs `f` with `policy` in effect on the current thread, returning `R::default()` if a failure is
+/// handled under [`Policy::LogAndDefault`].
+///
+/// When a failure is defaulted, the rest of `f` is abandoned. Panics that did not originate from a
+/// defaulted failure are propagated unchanged.
+///
+/// Abandoning `f` unwinds the stack, so this does not work in binaries built with
+/// `panic = "abort"`: there, a defaulted failure aborts the process instead.
+///
+/// # Examples
+///
+/// ```
+/// use better_unwrap::prelude::*;
+/// use better_unwrap::policy::{self, Policy};
+///
+/// let name: String = policy::with_default(Policy::LogAndDefault, || {
+///     let name: Result<String, &str> = Err("not configured");
+///     name.or_panic()
+/// });
+/// assert_eq!(name, String::new());
+/// ```
+pub fn with_default<R: Default>(policy: Policy, f: impl FnOnce() -> R) -> R {
+    let _scope = DefaultScope::enter();
+    match panic::catch_unwind(AssertUnwindSafe(|| with(policy, f))) {
+        Ok(value) => value,
+        Err(payload) if payload.is::<Defaulted>() => R::default(),
+        Err(payload) => panic::resume_unwind(payload),
+    }
+}
+
+/// Handles a failure according to the policy in effect on the current thread.
+#[track_caller]
+pub(crate) fn fail(message: fmt::Arguments<'_>) -> ! {
+    handle(Report::capture(message))
+}
+
+/// Handles a failure of `method`, called on `error` if it failed on an error value, according to
+/// the policy in effect on the current thread.
+#[track_caller]
+pub(crate) fn fail_detailed(
+    method: &'static str,
+    error: Option<&dyn Debug>,
+    message: fmt::Arguments<'_>,
+) -> ! {
+    handle(Report::capture(message).detail(method, error))
+}
+
+/// Handles a soft failure that exceeded the [budget](crate::budget) in effect, according to the
+/// policy in effect on the current thread.
+///
+/// The escalation is never defaulted, even under [`Policy::LogAndDefault`], since that would turn it
+/// back into the soft failure it escalates.
+#[track_caller]
+pub(crate) fn escalate(message: fmt::Arguments<'_>) -> ! {
+    let report = Report::capture(message);
+    #[cfg(feature = "tracing")]
+    emit::trace_failure(Location::caller(), &report);
+    terminate(current(), &report)
+}
+
+/// Handles a captured failure according to the policy in effect on the current thread.
+#[track_caller]
+fn handle(report: Report<'_>) -> ! {
+    #[cfg(feature = "tracing")]
+    emit::trace_failure(Location::caller(), &report);
+    match current() {
+        Policy::LogAndDefault if DEFAULT_SCOPES.get() > 0 => {
+            emit::log(Location::caller(), format_args!("{report}"));
+            panic::resume_unwind(Box::new(Defaulted))
+        }
+        policy => terminate(policy, &report),
+    }
+}
+
+/// Panics or aborts with a failure that is not defaulted, according to `policy`.
+#[track_caller]
+fn terminate(policy: Policy, report: &Report<'_>) -> ! {
+    match policy {
+        Policy::Panic | Policy::LogAndDefault => emit::panic(report),
+        Policy::Abort => emit::abort(report),
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::{BUOption, BUResult};
+    use std::env;
+    use std::process::{Command, Output};
+
+    /// Set in the child processes started by [`run_in_child`].
+    const CHILD_VAR: &str = "BETTER_UNWRAP_POLICY_TEST_CHILD";
+
+    /// Runs the test `name` alone in a child process, where it can change process-wide state, such as
+    /// the policy, or abort without affecting the other tests.
+    pub(crate) fn run_in_child(name: &str) -> Output {
+        Command::new(env::current_exe().or_panic())
+            .args([name, "--exact", "--nocapture", "--test-threads", "1"])
+            .env(CHILD_VAR, "1")
+            .output()
+            .or_panic()
+    }
+
+    pub(crate) fn in_child() -> bool {
+        env::var_os(CHILD_VAR).is_some()
+    }
+
+    #[test]
+    fn child_set_abort() {
+        if in_child() {
+            set(Policy::Abort).or_panic();
+            None::<u32>.or_panic();
+        }
+    }
+
+    #[test]
+    fn test_set_abort_aborts_the_process() {
+        let output = run_in_child("policy::tests::child_set_abort");
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        assert!(!output.status.success());
+        assert!(
+            stderr.contains("[better_unwrap] src/policy.rs:"),
+            "unexpected stderr: {stderr}"
+        );
+        assert!(stderr.contains("called `or_panic()` on a `None` value"));
+        assert!(!stderr.contains("panicked at"));
+    }
+
+    #[test]
+    fn test_set_rejects_log_and_default() {
+        let error = set(Policy::LogAndDefault).or_panic_err();
+        assert_eq!(
+            error.to_string(),
+            "`Policy::LogAndDefault` cannot be set process-wide; run the work in `policy::with_default()` instead"
+        );
+        assert_eq!(current(), Policy::Panic);
+    }
+
+    #[test]
+    fn child_set_panic() {
+        if in_child() {
+            set(Policy::Abort).or_panic();
+            set(Policy::Panic).or_panic();
+            assert_eq!(current(), Policy::Panic);
+            assert!(panic::catch_unwind(|| None::<u32>.or_panic()).is_err());
+        }
+    }
+
+    #[test]
+    fn test_set_panic() {
+        let output = run_in_child("policy::tests::child_set_panic");
+        assert!(output.status.success(), "{output:?}");
+    }
+
+    #[test]
+    fn child_with_abort() {
+        if in_child() {
+            with(Policy::Abort, || Err::<u32, &str>("disk full").or_panic());
+        }
+    }
+
+    #[test]
+    fn test_with_abort_aborts_the_process() {
+        let output = run_in_child("policy::tests::child_with_abort");
+        assert!(!output.status.success());
+        assert!(String::from_utf8_lossy(&output.stderr).contains("\"disk full\""));
+    }
+
+    #[test]
+    fn test_current_defaults_to_panic() {
+        assert_eq!(current(), Policy::Panic);
+    }
+
+    #[test]
+    fn test_with_restores_previous_policy() {
+        with(Policy::LogAndDefault, || {
+            with(Policy::Panic, || assert_eq!(current(), Policy::Panic));
+            assert_eq!(current(), Policy::LogAndDefault);
+        });
+        assert_eq!(current(), Policy::Panic);
+    }
+
+    #[test]
+    fn test_with_applies_only_to_current_thread() {
+        with(Policy::Abort, || {
+            let other = std::thread::spawn(current).join().or_panic();
+            assert_eq!(other, Policy::Panic);
+            assert_eq!(current(), Policy::Abort);
+        });
+    }
+
+    #[test]
+    fn child_with_overrides_set() {
+        if in_child() {
+            set(Policy::Abort).or_panic();
+            let value = with_default(Policy::LogAndDefault, || None::<u32>.or_panic());
+            assert_eq!(value, 0);
+            with(Policy::Panic, || {
+                assert!(panic::catch_unwind(|| None::<u32>.or_panic()).is_err());
+            });
+            assert_eq!(current(), Policy::Abort);
+        }
+    }
+
+    #[test]
+    fn test_with_overrides_set() {
+        let output = run_in_child("policy::tests::child_with_overrides_set");
+        assert!(output.status.success(), "{output:?}");
+    }
+
+    #[test]
+    fn test_with_restores_policy_on_unwind() {
+        let result = panic::catch_unwind(|| {
+            with(Policy::LogAndDefault, || panic!("boom"));
+        });
+        assert!(result.is_err());
+        assert_eq!(current(), Policy::Panic);
+    }
+
+    #[test]
+    fn test_with_default_returns_default_on_failure() {
+        let value = with_default(Policy::LogAndDefault, || {
+            let result: Result<u32, &str> = Err("error");
+            result.or_panic() + 1
+        });
+        assert_eq!(value, 0);
+        assert_eq!(current(), Policy::Panic);
+    }
+
+    #[test]
+    fn test_with_default_returns_value_on_success() {
+        let value = with_default(Policy::LogAndDefault, || Some(42).or_panic());
+        assert_eq!(value, 42);
+    }
+
+    #[test]
+    #[should_panic(expected = "called `or_panic()` on a `None` value")]
+    fn test_with_default_under_panic_policy_panics() {
+        with_default(Policy::Panic, || {
+            let option: Option<u32> = None;
+            option.or_panic()
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "unrelated")]
+    fn test_with_default_propagates_other_panics() {
+        with_default(Policy::LogAndDefault, || -> u32 { panic!("unrelated") });
+    }
+
+    #[test]
+    #[should_panic(expected = "called `or_panic()` on a `None` value")]
+    fn test_log_and_default_outside_scope_panics() {
+        with(Policy::LogAndDefault, || {
+            let option: Option<u32> = None;
+            option.or_panic()
+        });
+    }
+
+    #[cfg(feature = "macros")]
+    #[test]
+    fn test_panic_policy_attribute_log_and_default() {
+        #[crate::panic_policy(log_and_default)]
+        fn flush(pending: Option<u32>) -> u32 {
+            pending.or_panic()
+        }
+
+        assert_eq!(flush(Some(3)), 3);
+        assert_eq!(flush(None), 0);
+    }
+
+    #[cfg(feature = "macros")]
+    #[test]
+    #[should_panic(
+        expected = "called `or_panic()` on a `None` value\n\nmodule: better_unwrap::policy::tests"
+    )]
+    fn test_panic_policy_attribute_panic() {
+        #[crate::panic_policy(panic)]
+        fn load(value: Option<u32>) -> u32 {
+            value.or_panic()
+        }
+
+        with_default(Policy::LogAndDefault, || load(None));
+    }
+}
@@ -0,0 +1,84 @@
+use std::process::ExitStatus;
+
+use crate::policy;
+
+/// Trait that provides an alternative to `code().unwrap()` for `ExitStatus`.
+///
+/// `code()` returns `None` exactly when a child process was killed by a signal, which is when the
+/// signal number is most needed; this trait puts it in the panic message.
+pub trait BUExitStatus {
+    /// Returns the exit code of the process.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the process did not exit with a code. On Unix, the panic message includes the number
+    /// of the signal that terminated the process.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[cfg(unix)] {
+    /// use std::process::Command;
+    /// use better_unwrap::{BUExitStatus, BUResult};
+    ///
+    /// let status = Command::new("true").status().or_panic();
+    /// assert_eq!(status.code_or_panic(), 0);
+    /// # }
+    /// ```
+    #[track_caller]
+    fn code_or_panic(&self) -> i32;
+}
+
+impl BUExitStatus for ExitStatus {
+    #[track_caller]
+    fn code_or_panic(&self) -> i32 {
+        match self.code() {
+            Some(code) => code,
+            None => fail_without_code(self),
+        }
+    }
+}
+
+#[cfg(unix)]
+#[track_caller]
+fn fail_without_code(status: &ExitStatus) -> ! {
+    use std::os::unix::process::ExitStatusExt;
+
+    match status.signal() {
+        Some(signal) if status.core_dumped() => policy::fail(format_args!(
+            "called `code_or_panic()` on a process terminated by signal {signal} (core dumped)"
+        )),
+        Some(signal) => policy::fail(format_args!(
+            "called `code_or_panic()` on a process terminated by signal {signal}"
+        )),
+        None => policy::fail(format_args!(
+            "called `code_or_panic()` on a process without an exit code: {status}"
+        )),
+    }
+}
+
+#[cfg(not(unix))]
+#[track_caller]
+fn fail_without_code(status: &ExitStatus) -> ! {
+    policy::fail(format_args!(
+        "called `code_or_panic()` on a process without an exit code: {status}"
+    ))
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use std::os::unix::process::ExitStatusExt;
+
+    #[test]
+    fn test_code_or_panic_with_exit_code() {
+        assert_eq!(ExitStatus::from_raw(0).code_or_panic(), 0);
+        assert_eq!(ExitStatus::from_raw(3 << 8).code_or_panic(), 3);
+    }
+
+    #[test]
+    #[should_panic(expected = "called `code_or_panic()` on a process terminated by signal 9")]
+    fn test_code_or_panic_panics_on_signal() {
+        let _ = ExitStatus::from_raw(9).code_or_panic();
+    }
+}
@@ -0,0 +1,107 @@
+use std::any;
+use std::rc::{self, Rc};
+use std::sync::{self, Arc};
+
+use crate::policy;
+
+/// Trait that provides an alternative to `upgrade().unwrap()` for `rc::Weak` and `sync::Weak`.
+///
+/// Unlike unwrapping the `Option` returned by `upgrade()`, the panic message says that it was a weak
+/// reference whose referent is gone.
+pub trait BUWeak {
+    /// The strong pointer a weak reference upgrades to: `Rc<T>` or `Arc<T>`.
+    type Strong;
+
+    /// Upgrades the weak reference to a strong pointer.
+    ///
+    /// Equivalent to `upgrade().unwrap()`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the referent has already been dropped, with a panic message including the type of
+    /// the referent.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::rc::Rc;
+    /// use better_unwrap::BUWeak;
+    ///
+    /// let parent = Rc::new("root");
+    /// let weak = Rc::downgrade(&parent);
+    /// assert_eq!(*weak.upgrade_or_panic(), "root");
+    /// ```
+    ///
+    /// ```should_panic
+    /// use std::sync::Arc;
+    /// use better_unwrap::BUWeak;
+    ///
+    /// let weak = Arc::downgrade(&Arc::new(42u32));
+    /// weak.upgrade_or_panic(); // panics with `"called `upgrade_or_panic()` on a `Weak<u32>` whose referent was already dropped"`
+    /// ```
+    #[track_caller]
+    fn upgrade_or_panic(&self) -> Self::Strong;
+}
+
+impl<T> BUWeak for rc::Weak<T> {
+    type Strong = Rc<T>;
+
+    #[track_caller]
+    fn upgrade_or_panic(&self) -> Rc<T> {
+        match self.upgrade() {
+            Some(strong) => strong,
+            None => fail_dropped::<T>(),
+        }
+    }
+}
+
+impl<T> BUWeak for sync::Weak<T> {
+    type Strong = Arc<T>;
+
+    #[track_caller]
+    fn upgrade_or_panic(&self) -> Arc<T> {
+        match self.upgrade() {
+            Some(strong) => strong,
+            None => fail_dropped::<T>(),
+        }
+    }
+}
+
+#[track_caller]
+fn fail_dropped<T>() -> ! {
+    policy::fail(format_args!(
+        "called `upgrade_or_panic()` on a `Weak<{}>` whose referent was already dropped",
+        any::type_name::<T>()
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_upgrade_or_panic_with_live_referent() {
+        let rc = Rc::new(1);
+        assert_eq!(*Rc::downgrade(&rc).upgrade_or_panic(), 1);
+        let arc = Arc::new(2);
+        assert_eq!(*Arc::downgrade(&arc).upgrade_or_panic(), 2);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "called `upgrade_or_panic()` on a `Weak<alloc::string::String>` whose referent was already dropped"
+    )]
+    fn test_upgrade_or_panic_panics_on_dropped_rc() {
+        let weak = Rc::downgrade(&Rc::new(String::from("parent")));
+        let _ = weak.upgrade_or_panic();
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "called `upgrade_or_panic()` on a `Weak<u32>` whose referent was already dropped"
+    )]
+    fn test_upgrade_or_panic_panics_on_empty_arc_weak() {
+        let weak = sync::Weak::<u32>::new();
+        let _ = weak.upgrade_or_panic();
+    }
+}
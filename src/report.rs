@@ -0,0 +1,608 @@
+//! Rendering of the failures reported by the crate's panicking methods.
+
+use std::cell::Cell;
+use std::fmt::{self, Debug};
+use std::sync::RwLock;
+
+use crate::message::Truncated;
+
+static APP_INFO: RwLock<Option<AppInfo>> = RwLock::new(None);
+
+static REDACTION_HOOK: RwLock<Option<RedactionHook>> = RwLock::new(None);
+
+/// A function that rewrites a rendered failure, set with [`Report::set_redaction_hook`].
+pub type RedactionHook = fn(&str) -> String;
+
+thread_local! {
+    static MODULE_PATH: Cell<Option<&'static str>> = const { Cell::new(None) };
+    static DETERMINISTIC: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Restores the previously recorded module path when a scope ends, including on unwind.
+struct ModulePathGuard(Option<&'static str>);
+
+impl Drop for ModulePathGuard {
+    fn drop(&mut self) {
+        MODULE_PATH.set(self.0);
+    }
+}
+
+/// Runs `f` with `module` recorded as the calling module on the current thread.
+///
+/// Failures inside `f` include the module path in their message. This is usually called through the
+/// [`in_module!`](crate::in_module) macro, which passes `module_path!()` of the calling code.
+///
+/// # Examples
+///
+/// ```should_panic
+/// use better_unwrap::prelude::*;
+/// use better_unwrap::report;
+///
+/// report::with_module_path("app::telemetry", || {
+///     let endpoint: Option<&str> = None;
+///     endpoint.or_panic(); // panics with `"called `or_panic()` on a `None` value\n\nmodule: app::telemetry"`
+/// });
+/// ```
+pub fn with_module_path<R>(module: &'static str, f: impl FnOnce() -> R) -> R {
+    let _guard = ModulePathGuard(MODULE_PATH.replace(Some(module)));
+    f()
+}
+
+/// Restores the previous deterministic mode when a scope ends, including on unwind.
+struct DeterministicGuard(bool);
+
+impl Drop for DeterministicGuard {
+    fn drop(&mut self) {
+        DETERMINISTIC.set(self.0);
+    }
+}
+
+/// Runs `f` with failures on the current thread rendered deterministically, for snapshot tests.
+///
+/// Failure messages inside `f` are passed through [`normalize`], so they no longer depend on the
+/// machine, the run, or the time they were produced.
+///
+/// # Examples
+///
+/// ```
+/// use std::panic;
+/// use better_unwrap::prelude::*;
+/// use better_unwrap::report;
+///
+/// let payload = panic::catch_unwind(|| {
+///     report::with_deterministic(|| {
+///         let config: Result<u32, String> = Err("no config at /home/ci/app/config.toml".into());
+///         config.or_panic()
+///     })
+/// })
+/// .unwrap_err();
+/// assert_eq!(
+///     payload.downcast_ref::<String>().or_panic(),
+///     "called `or_panic()` on an `Err` value: \"no config at [path]/config.toml\""
+/// );
+/// ```
+pub fn with_deterministic<R>(f: impl FnOnce() -> R) -> R {
+    let _guard = DeterministicGuard(DETERMINISTIC.replace(true));
+    f()
+}
+
+/// Replaces the parts of a rendered failure that vary between runs with fixed placeholders.
+///
+/// - pointer addresses such as `0x7ffd5e8c1a40` become `0x[addr]`
+/// - thread IDs such as `ThreadId(7)` become `ThreadId([id])`
+/// - RFC 3339 timestamps become `[timestamp]`, and `SystemTime { .. }` debug output becomes
+///   `SystemTime { [timestamp] }`
+/// - measured durations with a fractional part, such as `1.372ms`, become `[duration]`; whole
+///   durations such as `30s` are usually configured values and are kept
+/// - the directories of absolute paths become `[path]`, keeping the file name
+///
+/// # Examples
+///
+/// ```
+/// use better_unwrap::report;
+///
+/// assert_eq!(
+///     report::normalize("worker ThreadId(12) gave up after 3.2ms reading /var/lib/app/state.db"),
+///     "worker ThreadId([id]) gave up after [duration] reading [path]/state.db"
+/// );
+/// ```
+pub fn normalize(text: &str) -> String {
+    let mut normalized = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(c) = rest.chars().next() {
+        let at_boundary = normalized
+            .chars()
+            .next_back()
+            .is_none_or(|prev| !prev.is_alphanumeric() && !matches!(prev, '_' | '.' | ':' | '/'));
+        match normalize_token(rest, at_boundary) {
+            Some((len, replacement)) => {
+                normalized.push_str(&replacement);
+                rest = &rest[len..];
+            }
+            None => {
+                normalized.push(c);
+                rest = &rest[c.len_utf8()..];
+            }
+        }
+    }
+    normalized
+}
+
+/// Matches a nondeterministic token at the start of `text`, returning its length and replacement.
+fn normalize_token(text: &str, at_boundary: bool) -> Option<(usize, String)> {
+    if let Some(rest) = text.strip_prefix("ThreadId(") {
+        let digits = count_while(rest, |c| c.is_ascii_digit());
+        if digits > 0 && rest[digits..].starts_with(')') {
+            return Some(("ThreadId(".len() + digits + 1, "ThreadId([id])".to_owned()));
+        }
+    }
+    if text.starts_with("SystemTime {") {
+        let len = text.find('}')? + 1;
+        return Some((len, "SystemTime { [timestamp] }".to_owned()));
+    }
+    if !at_boundary {
+        return None;
+    }
+    if let Some(rest) = text.strip_prefix("0x") {
+        let digits = count_while(rest, |c| c.is_ascii_hexdigit());
+        if digits >= 4 && !continues_word(&rest[digits..]) {
+            return Some((2 + digits, "0x[addr]".to_owned()));
+        }
+    }
+    if let Some(len) = timestamp_len(text) {
+        return Some((len, "[timestamp]".to_owned()));
+    }
+    if let Some(len) = fractional_duration_len(text) {
+        return Some((len, "[duration]".to_owned()));
+    }
+    let is_windows_absolute =
+        text.len() >= 3 && text.as_bytes()[0].is_ascii_alphabetic() && text[1..].starts_with(":\\");
+    if text.starts_with('/') || is_windows_absolute {
+        // Skip the drive prefix so its colon does not end the path.
+        let start = if is_windows_absolute { 2 } else { 0 };
+        let len = start
+            + count_while(&text[start..], |c| {
+                !c.is_whitespace() && !matches!(c, '"' | '\'' | '`' | ')' | ',' | ':' | ';')
+            });
+        let path = &text[..len];
+        let (dir, file) = path.rsplit_once(['/', '\\'])?;
+        if !dir.is_empty() && !file.is_empty() {
+            return Some((len, format!("[path]/{file}")));
+        }
+    }
+    None
+}
+
+/// Returns the length of an RFC 3339 timestamp such as `2024-05-01T12:30:00.123Z` at the start of
+/// `text`.
+fn timestamp_len(text: &str) -> Option<usize> {
+    let bytes = text.as_bytes();
+    let digits_at = |start: usize, n: usize| {
+        bytes.len() >= start + n && bytes[start..start + n].iter().all(u8::is_ascii_digit)
+    };
+    let shape = digits_at(0, 4)
+        && bytes.get(4) == Some(&b'-')
+        && digits_at(5, 2)
+        && bytes.get(7) == Some(&b'-')
+        && digits_at(8, 2)
+        && matches!(bytes.get(10), Some(b'T' | b' '))
+        && digits_at(11, 2)
+        && bytes.get(13) == Some(&b':')
+        && digits_at(14, 2)
+        && bytes.get(16) == Some(&b':')
+        && digits_at(17, 2);
+    if !shape {
+        return None;
+    }
+    let mut len = 19;
+    if bytes.get(len) == Some(&b'.') {
+        len += 1 + count_while(&text[len + 1..], |c| c.is_ascii_digit());
+    }
+    match bytes.get(len) {
+        Some(b'Z') => len += 1,
+        Some(b'+' | b'-')
+            if digits_at(len + 1, 2)
+                && bytes.get(len + 3) == Some(&b':')
+                && digits_at(len + 4, 2) =>
+        {
+            len += 6
+        }
+        _ => {}
+    }
+    Some(len)
+}
+
+/// Returns the length of a duration with a fractional part such as `1.372ms` at the start of `text`.
+fn fractional_duration_len(text: &str) -> Option<usize> {
+    let whole = count_while(text, |c| c.is_ascii_digit());
+    if whole == 0 || !text[whole..].starts_with('.') {
+        return None;
+    }
+    let fraction = count_while(&text[whole + 1..], |c| c.is_ascii_digit());
+    if fraction == 0 {
+        return None;
+    }
+    let number = whole + 1 + fraction;
+    ["ns", "µs", "ms", "s"].iter().find_map(|unit| {
+        let rest = text[number..].strip_prefix(unit)?;
+        (!continues_word(rest)).then_some(number + unit.len())
+    })
+}
+
+fn count_while(text: &str, f: impl Fn(char) -> bool) -> usize {
+    text.find(|c| !f(c)).unwrap_or(text.len())
+}
+
+fn continues_word(text: &str) -> bool {
+    text.chars()
+        .next()
+        .is_some_and(|c| c.is_alphanumeric() || c == '_')
+}
+
+/// Evaluates the given statements with the calling module recorded for failure messages.
+///
+/// Expands to [`report::with_module_path`](crate::report::with_module_path) called with
+/// `module_path!()`, so failures inside the block include the module path of the calling code.
+///
+/// # Examples
+///
+/// ```
+/// use better_unwrap::prelude::*;
+///
+/// let port = better_unwrap::in_module! {
+///     let port: Option<u16> = Some(8080);
+///     port.or_panic()
+/// };
+/// assert_eq!(port, 8080);
+/// ```
+#[macro_export]
+macro_rules! in_module {
+    ($($body:tt)*) => {
+        $crate::report::with_module_path(::core::module_path!(), || { $($body)* })
+    };
+}
+
+/// The application identity included in every report, set with [`Report::set_app_info`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct AppInfo {
+    name: &'static str,
+    version: &'static str,
+    git_sha: &'static str,
+}
+
+/// A failure as rendered by the crate: the failure message plus any context captured at the failing
+/// call.
+///
+/// Reports are rendered into the panic message (or log line) of every failure handled by the crate.
+pub struct Report<'a> {
+    message: fmt::Arguments<'a>,
+    method: Option<&'static str>,
+    error: Option<&'a dyn Debug>,
+    module_path: Option<&'static str>,
+    app_info: Option<AppInfo>,
+    deterministic: bool,
+    redaction_hook: Option<RedactionHook>,
+    #[cfg(feature = "tracing-error")]
+    span_trace: tracing_error::SpanTrace,
+}
+
+impl Report<'_> {
+    /// Sets the application name, version, and git commit included in every report rendered by the
+    /// crate from now on.
+    ///
+    /// # Examples
+    ///
+    /// ```should_panic
+    /// use better_unwrap::prelude::*;
+    /// use better_unwrap::Report;
+    ///
+    /// Report::set_app_info(env!("CARGO_PKG_NAME"), env!("CARGO_PKG_VERSION"), "4f2a9c1");
+    ///
+    /// let config: Option<&str> = None;
+    /// config.or_panic(); // panics with `"called `or_panic()` on a `None` value\n\napp: better_unwrap 1.0.0 (4f2a9c1)"`
+    /// ```
+    pub fn set_app_info(name: &'static str, version: &'static str, git_sha: &'static str) {
+        let info = AppInfo {
+            name,
+            version,
+            git_sha,
+        };
+        *APP_INFO
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner()) = Some(info);
+    }
+
+    /// Sets a function that every report rendered by the crate from now on is passed through before
+    /// it reaches a panic message or log line, or removes it with `None`.
+    ///
+    /// The hook also applies to the crate's other output: the diagnostics of the `*_or_log` methods,
+    /// the messages printed before exiting, and the error recorded in a
+    /// [`BUPanicInfo`](crate::payload::BUPanicInfo).
+    ///
+    /// Use it to scrub tokens and personal data that errors may carry out of crash logs. It applies
+    /// after [`with_deterministic`] normalization. For call sites whose errors must never be
+    /// rendered at all, use [`or_panic_redacted()`](crate::BUResult::or_panic_redacted).
+    ///
+    /// # Examples
+    ///
+    /// ```should_panic
+    /// use better_unwrap::prelude::*;
+    /// use better_unwrap::Report;
+    ///
+    /// fn scrub(report: &str) -> String {
+    ///     match report.find("Bearer ") {
+    ///         Some(start) => format!("{}Bearer [redacted]\"", &report[..start]),
+    ///         None => report.to_owned(),
+    ///     }
+    /// }
+    ///
+    /// Report::set_redaction_hook(Some(scrub));
+    ///
+    /// let response: Result<u32, String> = Err("unauthorized: Bearer eyJhbGciOi".into());
+    /// response.or_panic(); // panics with `"called `or_panic()` on an `Err` value: \"unauthorized: Bearer [redacted]\""`
+    /// ```
+    pub fn set_redaction_hook(hook: Option<RedactionHook>) {
+        *REDACTION_HOOK
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner()) = hook;
+    }
+}
+
+impl<'a> Report<'a> {
+    /// Captures the context of a failure at the current call.
+    pub(crate) fn capture(message: fmt::Arguments<'a>) -> Self {
+        Report {
+            message,
+            method: None,
+            error: None,
+            module_path: MODULE_PATH.get(),
+            app_info: *APP_INFO
+                .read()
+                .unwrap_or_else(|poisoned| poisoned.into_inner()),
+            deterministic: DETERMINISTIC.get(),
+            redaction_hook: *REDACTION_HOOK
+                .read()
+                .unwrap_or_else(|poisoned| poisoned.into_inner()),
+            #[cfg(feature = "tracing-error")]
+            span_trace: tracing_error::SpanTrace::capture(),
+        }
+    }
+
+    /// Records the name of the method that failed and the error it was called on, if any.
+    pub(crate) fn detail(mut self, method: &'static str, error: Option<&'a dyn Debug>) -> Self {
+        self.method = Some(method);
+        self.error = error;
+        self
+    }
+
+    /// Returns the name of the method that failed, when it was recorded.
+    #[cfg_attr(all(feature = "fuzzing", not(test)), allow(dead_code))]
+    pub(crate) fn method(&self) -> Option<&'static str> {
+        self.method
+    }
+
+    /// Renders the error the failing method was called on using `Debug`, when it was recorded, and
+    /// passes it through the redaction hook in effect when the report was captured.
+    #[cfg_attr(all(feature = "fuzzing", not(test)), allow(dead_code))]
+    pub(crate) fn error_debug(&self) -> Option<String> {
+        let error = format!("{:?}", Truncated(self.error?));
+        Some(match self.redaction_hook {
+            Some(hook) => hook(&error),
+            None => error,
+        })
+    }
+}
+
+/// Passes a line the crate emits outside of a report, such as a log line, through the
+/// [redaction hook](Report::set_redaction_hook), if one is set.
+pub(crate) fn redact(line: fmt::Arguments<'_>) -> String {
+    let hook = *REDACTION_HOOK
+        .read()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    match hook {
+        Some(hook) => hook(&line.to_string()),
+        None => line.to_string(),
+    }
+}
+
+impl fmt::Display for Report<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (self.deterministic, self.redaction_hook) {
+            (false, None) => Sections(self).fmt(f),
+            (true, None) => f.write_str(&normalize(&Sections(self).to_string())),
+            (false, Some(hook)) => f.write_str(&hook(&Sections(self).to_string())),
+            (true, Some(hook)) => f.write_str(&hook(&normalize(&Sections(self).to_string()))),
+        }
+    }
+}
+
+/// The sections of a report, rendered as captured.
+struct Sections<'r, 'a>(&'r Report<'a>);
+
+impl fmt::Display for Sections<'_, '_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Sections(report) = self;
+        write!(f, "{}", report.message)?;
+        if let Some(module) = report.module_path {
+            write!(f, "\n\nmodule: {module}")?;
+        }
+        #[cfg(feature = "tracing-error")]
+        if report.span_trace.status() == tracing_error::SpanTraceStatus::CAPTURED {
+            write!(f, "\n\nspan trace:\n{}", report.span_trace)?;
+        }
+        if let Some(info) = report.app_info {
+            write!(
+                f,
+                "\n\napp: {} {} ({})",
+                info.name, info.version, info.git_sha
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::policy::tests::{in_child, run_in_child};
+    use crate::{BUOption, BUResult};
+
+    #[test]
+    fn test_report_renders_module_path() {
+        let rendered = with_module_path("app::telemetry", || {
+            Report::capture(format_args!("failed")).to_string()
+        });
+        assert_eq!(rendered, "failed\n\nmodule: app::telemetry");
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "called `or_panic()` on a `None` value\n\nmodule: better_unwrap::report::tests"
+    )]
+    fn test_in_module_records_calling_module() {
+        crate::in_module! {
+            let value: Option<u32> = None;
+            value.or_panic()
+        };
+    }
+
+    #[test]
+    fn test_report_renders_app_info() {
+        let mut report =
+            with_module_path("app::telemetry", || Report::capture(format_args!("failed")));
+        report.app_info = Some(AppInfo {
+            name: "collector",
+            version: "2.1.0",
+            git_sha: "4f2a9c1",
+        });
+        assert_eq!(
+            report.to_string(),
+            "failed\n\nmodule: app::telemetry\n\napp: collector 2.1.0 (4f2a9c1)"
+        );
+    }
+
+    #[test]
+    fn test_report_applies_redaction_hook() {
+        let mut report = Report::capture(format_args!("token sk-42 rejected"));
+        report.redaction_hook = Some(|text| text.replace("sk-42", "[token]"));
+        assert_eq!(report.to_string(), "token [token] rejected");
+    }
+
+    #[test]
+    fn child_redaction_hook() {
+        if !in_child() {
+            return;
+        }
+        Report::set_redaction_hook(Some(|text| text.replace("SECRET", "[redacted]")));
+        let payload = std::panic::catch_unwind(|| {
+            crate::payload::with_structured(|| Err::<u32, _>("token SECRET").or_panic())
+        })
+        .or_panic_err();
+        let info = payload
+            .downcast_ref::<crate::payload::BUPanicInfo>()
+            .or_panic();
+        assert_eq!(
+            info.message(),
+            "called `or_panic()` on an `Err` value: \"token [redacted]\""
+        );
+        assert_eq!(info.error_debug(), Some("\"token [redacted]\""));
+        assert_eq!(
+            redact(format_args!("discarded `Err` value: \"token SECRET\"")),
+            "discarded `Err` value: \"token [redacted]\""
+        );
+    }
+
+    #[test]
+    fn test_redaction_hook_applies_to_payload() {
+        // The hook is process-wide, so it is installed in a child process where it cannot rewrite the
+        // messages other tests check.
+        let output = run_in_child("report::tests::child_redaction_hook");
+        assert!(output.status.success(), "{output:?}");
+    }
+
+    #[test]
+    fn test_report_renders_deterministically() {
+        let rendered = with_deterministic(|| {
+            Report::capture(format_args!("lock held by ThreadId(3) at 0x7ffd5e8c1a40")).to_string()
+        });
+        assert_eq!(rendered, "lock held by ThreadId([id]) at 0x[addr]");
+        assert!(!DETERMINISTIC.get());
+    }
+
+    #[test]
+    fn test_normalize_timestamps() {
+        assert_eq!(
+            normalize("expired at 2024-05-01T12:30:00.123Z, checked 2024-05-01 12:31:00+02:00"),
+            "expired at [timestamp], checked [timestamp]"
+        );
+        assert_eq!(
+            normalize("mtime SystemTime { tv_sec: 1714566600, tv_nsec: 0 }"),
+            "mtime SystemTime { [timestamp] }"
+        );
+    }
+
+    #[test]
+    fn test_normalize_durations() {
+        assert_eq!(
+            normalize("attempt 1 failed after 1.372ms, timed out after 30s"),
+            "attempt 1 failed after [duration], timed out after 30s"
+        );
+        assert_eq!(normalize("version 1.2.3"), "version 1.2.3");
+    }
+
+    #[test]
+    fn test_normalize_paths() {
+        assert_eq!(
+            normalize("failed to read \"/home/ci/app/config.toml\": not found"),
+            "failed to read \"[path]/config.toml\": not found"
+        );
+        assert_eq!(
+            normalize("panicked at /build/src/main.rs:4:9"),
+            "panicked at [path]/main.rs:4:9"
+        );
+        assert_eq!(normalize(r"C:\Users\ci\app.log"), "[path]/app.log");
+        assert_eq!(normalize("src/lib.rs and a/b"), "src/lib.rs and a/b");
+    }
+
+    #[test]
+    fn test_normalize_keeps_short_hex_and_words() {
+        assert_eq!(
+            normalize("flags 0x1f, id 0xdeadbeef"),
+            "flags 0x1f, id 0x[addr]"
+        );
+        assert_eq!(normalize("ThreadIdx(3)"), "ThreadIdx(3)");
+    }
+
+    #[test]
+    fn test_with_module_path_restores_on_exit() {
+        with_module_path("outer", || {
+            with_module_path("inner", || {});
+            assert_eq!(MODULE_PATH.get(), Some("outer"));
+        });
+        assert_eq!(MODULE_PATH.get(), None);
+    }
+
+    #[cfg(feature = "tracing-error")]
+    #[test]
+    fn test_report_renders_span_trace() {
+        use tracing_subscriber::layer::SubscriberExt;
+
+        let subscriber = tracing_subscriber::registry().with(tracing_error::ErrorLayer::default());
+        let rendered = tracing::subscriber::with_default(subscriber, || {
+            let span = tracing::info_span!("flush_telemetry");
+            let _entered = span.enter();
+            Report::capture(format_args!("failed")).to_string()
+        });
+        assert!(rendered.starts_with("failed\n\nspan trace:\n"));
+        assert!(rendered.contains("flush_telemetry"));
+    }
+
+    #[cfg(feature = "tracing-error")]
+    #[test]
+    fn test_report_omits_empty_span_trace() {
+        assert_eq!(
+            Report::capture(format_args!("failed")).to_string(),
+            "failed"
+        );
+    }
+}
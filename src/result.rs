@@ -1,5 +1,21 @@
-use std::fmt::Debug;
-use std::default::Default;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use core::default::Default;
+use core::error::Error;
+#[cfg(feature = "alloc")]
+use core::fmt;
+use core::fmt::{Debug, Display};
+#[cfg(feature = "std")]
+use core::panic::Location;
+#[cfg(feature = "std")]
+use std::backtrace::Backtrace;
+
+#[cfg(feature = "alloc")]
+use crate::diff::Diff;
+use crate::message::{Chain, PanicMessage, Rendered, Truncated};
+use crate::policy;
+#[cfg(feature = "std")]
+use crate::{emit, report::Report, traced::Traced};
 
 /// Trait that provides methods as alternatives to `unwrap()` and related methods for `Result<T, E>`.
 ///
@@ -19,7 +35,261 @@ pub trait BUResult<T, E> {
     /// let x: Result<u32, &str> = Err("emergency failure");
     /// x.or_panic(); // panics with `"emergency failure"`
     /// ```
-    fn or_panic(self) -> T;
+    #[track_caller]
+    fn or_panic(self) -> T
+    where
+        E: Debug;
+
+    /// Returns a reference to the content of an `Ok` without consuming the result.
+    ///
+    /// Equivalent to `as_ref().unwrap()`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is an `Err`, with a panic message including the error value formatted using `Debug`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use better_unwrap::BUResult;
+    ///
+    /// let x: Result<String, &str> = Ok("config.toml".to_string());
+    /// assert_eq!(x.or_panic_ref(), "config.toml");
+    /// ```
+    #[track_caller]
+    fn or_panic_ref(&self) -> &T
+    where
+        E: Debug;
+
+    /// Returns a mutable reference to the content of an `Ok` without consuming the result.
+    ///
+    /// Equivalent to `as_mut().unwrap()`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is an `Err`, with a panic message including the error value formatted using `Debug`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use better_unwrap::BUResult;
+    ///
+    /// let mut x: Result<Vec<u32>, &str> = Ok(vec![1]);
+    /// x.or_panic_mut().push(2);
+    /// assert_eq!(x, Ok(vec![1, 2]));
+    /// ```
+    #[track_caller]
+    fn or_panic_mut(&mut self) -> &mut T
+    where
+        E: Debug;
+
+    /// Unwraps a result, yielding the content of an `Ok`, without rendering the error.
+    ///
+    /// For errors that may carry tokens or personal data: the panic message names the error's type
+    /// and the caller location, but not its contents. The error is not recorded in the
+    /// [`BUPanicInfo`](crate::BUPanicInfo) payload either.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is an `Err`, with a panic message including the error's type name and the
+    /// caller location.
+    ///
+    /// # Examples
+    ///
+    /// ```should_panic
+    /// use better_unwrap::BUResult;
+    ///
+    /// #[derive(Debug)]
+    /// struct AuthError {
+    ///     token: String,
+    /// }
+    ///
+    /// let x: Result<u32, AuthError> = Err(AuthError { token: "sk-live-42".into() });
+    /// x.or_panic_redacted(); // panics with `"called `or_panic_redacted()` at src/main.rs:12:3 on an `Err` value of type `rust_out::AuthError` (contents redacted)"`
+    /// ```
+    #[track_caller]
+    fn or_panic_redacted(self) -> T;
+
+    /// Unwraps a result, yielding the content of an `Ok`.
+    ///
+    /// Like `or_panic()`, but the error is pretty-printed using `{:#?}`, one field per line, which
+    /// keeps deeply nested errors readable.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is an `Err`, with a panic message including the error value pretty-printed
+    /// using `Debug`.
+    ///
+    /// # Examples
+    ///
+    /// ```should_panic
+    /// use better_unwrap::BUResult;
+    ///
+    /// #[derive(Debug)]
+    /// struct QueryError {
+    ///     table: &'static str,
+    ///     code: u32,
+    /// }
+    ///
+    /// let x: Result<u32, QueryError> = Err(QueryError { table: "users", code: 1205 });
+    /// x.or_panic_pretty(); // panics with `"called `or_panic_pretty()` on an `Err` value: QueryError {\n    table: \"users\",\n    code: 1205,\n}"`
+    /// ```
+    #[track_caller]
+    fn or_panic_pretty(self) -> T
+    where
+        E: Debug;
+
+    /// Unwraps a result, yielding the content of an `Ok`.
+    ///
+    /// Like `or_panic()`, but the error is formatted using `Display`, so domain errors read as their
+    /// human-readable message instead of a struct dump.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is an `Err`, with a panic message including the error value formatted using `Display`.
+    ///
+    /// # Examples
+    ///
+    /// ```should_panic
+    /// use better_unwrap::BUResult;
+    ///
+    /// let x: Result<u32, std::num::ParseIntError> = "4x".parse::<u32>();
+    /// x.or_panic_display(); // panics with `"called `or_panic_display()` on an `Err` value: invalid digit found in string"`
+    /// ```
+    #[track_caller]
+    fn or_panic_display(self) -> T
+    where
+        E: Display;
+
+    /// Unwraps a result, yielding the content of an `Ok`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is an `Err`, with a panic message including the error rendered by its
+    /// [`PanicMessage`] implementation, which can be derived with the `macros` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```should_panic
+    /// use std::fmt;
+    /// use better_unwrap::{BUResult, PanicMessage};
+    ///
+    /// struct UploadError {
+    ///     path: &'static str,
+    ///     body: Vec<u8>,
+    /// }
+    ///
+    /// impl PanicMessage for UploadError {
+    ///     fn fmt_panic_message(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    ///         write!(f, "upload of {} ({} bytes) failed", self.path, self.body.len())
+    ///     }
+    /// }
+    ///
+    /// let x: Result<(), UploadError> = Err(UploadError { path: "logs.tar", body: vec![0; 4096] });
+    /// x.or_panic_message(); // panics with `"called `or_panic_message()` on an `Err` value: upload of logs.tar (4096 bytes) failed"`
+    /// ```
+    #[track_caller]
+    fn or_panic_message(self) -> T
+    where
+        E: PanicMessage;
+
+    /// Unwraps a result, yielding the content of an `Ok`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is an `Err`, with a panic message including the error rendered with
+    /// `Display` followed by its whole chain of [`source()`](core::error::Error::source)s, one
+    /// `caused by:` line per level, so the root cause is not lost.
+    ///
+    /// # Examples
+    ///
+    /// ```should_panic
+    /// use std::fs;
+    /// use std::io;
+    /// use better_unwrap::BUResult;
+    ///
+    /// #[derive(Debug)]
+    /// struct ConfigError(io::Error);
+    ///
+    /// impl std::fmt::Display for ConfigError {
+    ///     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    ///         f.write_str("failed to load the configuration")
+    ///     }
+    /// }
+    ///
+    /// impl std::error::Error for ConfigError {
+    ///     fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+    ///         Some(&self.0)
+    ///     }
+    /// }
+    ///
+    /// let x = fs::read_to_string("/nonexistent/app.toml").map_err(ConfigError);
+    /// x.or_panic_chained(); // panics with `"called `or_panic_chained()` on an `Err` value: failed to load the configuration\ncaused by: No such file or directory (os error 2)"`
+    /// ```
+    #[track_caller]
+    fn or_panic_chained(self) -> T
+    where
+        E: Error;
+
+    /// Unwraps a result, yielding the content of an `Ok`, with a backtrace in the panic message.
+    ///
+    /// The backtrace is captured even when `RUST_BACKTRACE` is unset, so rarely failing call sites
+    /// report where they were reached from without enabling backtraces for the whole process. It is
+    /// part of the message, and so of the [`BUPanicInfo`](crate::BUPanicInfo) payload too.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is an `Err`, with a panic message including the error value formatted
+    /// using `Debug`, followed by the backtrace.
+    ///
+    /// # Examples
+    ///
+    /// ```should_panic
+    /// use better_unwrap::BUResult;
+    ///
+    /// let x: Result<u32, &str> = Err("lease expired");
+    /// x.or_panic_with_backtrace(); // panics with `"called `or_panic_with_backtrace()` on an `Err` value: \"lease expired\"\n\nbacktrace:\n   0: ..."`
+    /// ```
+    #[cfg(feature = "std")]
+    #[track_caller]
+    fn or_panic_with_backtrace(self) -> T
+    where
+        E: Debug;
+
+    /// Unwraps a result and asserts that the content of the `Ok` equals `expected`.
+    ///
+    /// Suited to tests comparing large values: on a mismatch the panic message shows a line diff of
+    /// the pretty `Debug` renderings, so only the fields that differ stand out.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is an `Err`, with a panic message including the error value formatted
+    /// using `Debug` and the expected value, or if the content differs from `expected`, with a panic
+    /// message including the diff, where lines only in `expected` are prefixed with `-` and lines
+    /// only in the actual value with `+`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use better_unwrap::BUResult;
+    ///
+    /// let x: Result<u16, std::num::ParseIntError> = "8080".parse();
+    /// x.expect_ok_eq(8080);
+    /// ```
+    ///
+    /// ```should_panic
+    /// use better_unwrap::BUResult;
+    ///
+    /// let x: Result<(u16, &str), &str> = Ok((8080, "localhost"));
+    /// x.expect_ok_eq((8080, "example.com")); // panics with `"called `expect_ok_eq()` on an `Ok` value that differs from the expected one:\n- expected\n+ actual\n\n  (\n      8080,\n-     \"example.com\",\n+     \"localhost\",\n  )"`
+    /// ```
+    #[cfg(feature = "alloc")]
+    #[track_caller]
+    fn expect_ok_eq<U>(self, expected: U)
+    where
+        T: PartialEq<U> + Debug,
+        U: Debug,
+        E: Debug;
 
     /// Returns the contained value or a provided default.
     ///
@@ -33,6 +303,7 @@ pub trait BUResult<T, E> {
     /// let x: Result<u32, &str> = Err("error");
     /// assert_eq!(x.panic_or(42), 42);
     /// ```
+    #[track_caller]
     fn panic_or(self, default: T) -> T;
 
     /// Returns the contained value or computes it from a closure that receives the error.
@@ -50,6 +321,7 @@ pub trait BUResult<T, E> {
     ///     42
     /// }), 42);
     /// ```
+    #[track_caller]
     fn panic_or_else<F>(self, f: F) -> T
     where
         F: FnOnce(E) -> T;
@@ -66,14 +338,107 @@ pub trait BUResult<T, E> {
     /// let x: Result<u32, &str> = Err("error");
     /// assert_eq!(x.panic_or_default(), 0);
     /// ```
+    #[track_caller]
     fn panic_or_default(self) -> T
     where
         T: Default;
 
+    /// Returns the contained `Ok` value or a provided default, logging the error.
+    ///
+    /// Like `panic_or()`, but the error is logged, formatted using `Debug`, with the caller location
+    /// instead of silently disappearing.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use better_unwrap::BUResult;
+    ///
+    /// let x: Result<u32, &str> = Err("timeout");
+    /// assert_eq!(x.panic_or_log(100), 100); // logs the error to stderr
+    /// ```
+    #[cfg(feature = "std")]
+    #[track_caller]
+    fn panic_or_log(self, default: T) -> T
+    where
+        E: Debug;
+
+    /// Returns the contained `Ok` value or computes it from the error, logging the error first.
+    ///
+    /// Like `panic_or_else()`, but the error is logged, formatted using `Debug`, with the caller
+    /// location instead of silently disappearing.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use better_unwrap::BUResult;
+    ///
+    /// let x: Result<u32, &str> = Err("timeout");
+    /// assert_eq!(x.panic_or_log_else(|err| err.len() as u32), 7); // logs the error to stderr
+    /// ```
+    #[cfg(feature = "std")]
+    #[track_caller]
+    fn panic_or_log_else<F>(self, f: F) -> T
+    where
+        E: Debug,
+        F: FnOnce(E) -> T;
+
+    /// Unwraps a result in debug builds, and returns the contained `Ok` value or a provided default
+    /// in release builds.
+    ///
+    /// The `debug_assertions` equivalent of unwrapping: behaves like `or_panic()` when
+    /// `debug_assertions` are enabled, and like `panic_or(default)` otherwise, so an error fails
+    /// loudly during development without taking down a release build.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is an `Err` and `debug_assertions` are enabled, with a panic message
+    /// including the error formatted using `Debug`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use better_unwrap::BUResult;
+    ///
+    /// let x: Result<u32, &str> = Ok(2);
+    /// assert_eq!(x.debug_panic_or(100), 2);
+    /// ```
+    #[track_caller]
+    fn debug_panic_or(self, default: T) -> T
+    where
+        E: Debug;
+
+    /// Unwraps a result in debug builds, and returns the contained `Ok` value or computes it from the
+    /// error in release builds.
+    ///
+    /// Behaves like `or_panic()` when `debug_assertions` are enabled, and like `panic_or_else(f)`
+    /// otherwise.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is an `Err` and `debug_assertions` are enabled, with a panic message
+    /// including the error formatted using `Debug`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use better_unwrap::BUResult;
+    ///
+    /// let x: Result<u32, &str> = Ok(2);
+    /// assert_eq!(x.debug_panic_or_else(|err| err.len() as u32), 2);
+    /// ```
+    #[track_caller]
+    fn debug_panic_or_else<F>(self, f: F) -> T
+    where
+        E: Debug,
+        F: FnOnce(E) -> T;
+
     /// Unwraps a result, yielding the content of an `Ok`.
     ///
     /// Equivalent to `expect()`, but with a clearer name.
     ///
+    /// The message can be anything that implements `Display`, such as `format_args!(...)`, which is
+    /// only rendered if the value is an `Err`.
+    ///
     /// # Panics
     ///
     /// Panics if the value is an `Err`, with a panic message including the provided message.
@@ -86,7 +451,41 @@ pub trait BUResult<T, E> {
     /// let x: Result<u32, &str> = Err("emergency failure");
     /// x.panic_with("Testing error handling"); // panics with `"Testing error handling"`
     /// ```
-    fn panic_with(self, msg: &str) -> T;
+    ///
+    /// ```should_panic
+    /// use better_unwrap::BUResult;
+    ///
+    /// let path = "config.toml";
+    /// let x: Result<u32, &str> = Err("permission denied");
+    /// x.panic_with(format_args!("loading {path} failed")); // panics with `"loading config.toml failed: \"permission denied\""`
+    /// ```
+    #[track_caller]
+    fn panic_with<M: Display>(self, msg: M) -> T
+    where
+        E: Debug;
+
+    /// Unwraps a result, yielding the content of an `Ok`.
+    ///
+    /// Like `panic_with()`, but the message is computed from the error by a closure that only runs if
+    /// the value is an `Err`, so no formatting happens on the happy path.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is an `Err`, with the message returned by `f`.
+    ///
+    /// # Examples
+    ///
+    /// ```should_panic
+    /// use better_unwrap::BUResult;
+    ///
+    /// let x: Result<u32, &str> = Err("permission denied");
+    /// x.panic_with_else(|err| format!("failed to load config: {err}")); // panics with `"failed to load config: permission denied"`
+    /// ```
+    #[track_caller]
+    fn panic_with_else<F, M>(self, f: F) -> T
+    where
+        F: FnOnce(E) -> M,
+        M: Display;
 
     /// Unwraps a result, yielding the content of an `Err`.
     ///
@@ -112,14 +511,38 @@ pub trait BUResult<T, E> {
     /// let error = x.or_panic_err(); // returns "error message"
     /// assert_eq!(error, "error message");
     /// ```
+    #[track_caller]
     fn or_panic_err(self) -> E
     where
         T: Debug;
 
+    /// Unwraps a result, yielding the content of an `Err`.
+    ///
+    /// Like `or_panic_err()`, but the `Ok` value is formatted using `Display`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is an `Ok`, with a panic message including the Ok value formatted using `Display`.
+    ///
+    /// # Examples
+    ///
+    /// ```should_panic
+    /// use better_unwrap::BUResult;
+    ///
+    /// let x: Result<&str, u32> = Ok("connected");
+    /// x.or_panic_err_display(); // panics with `"called `or_panic_err_display()` on an `Ok` value: connected"`
+    /// ```
+    #[track_caller]
+    fn or_panic_err_display(self) -> E
+    where
+        T: Display;
+
     /// Unwraps a result, yielding the content of an `Err`.
     ///
     /// Equivalent to `expect_err()`, but with a clearer name.
     ///
+    /// The message can be anything that implements `Display`, such as `format_args!(...)`.
+    ///
     /// # Panics
     ///
     /// Panics if the value is an `Ok`, with a panic message including the provided message.
@@ -140,54 +563,889 @@ pub trait BUResult<T, E> {
     /// let error = x.panic_err_with("Should not panic"); // returns "error message"
     /// assert_eq!(error, "error message");
     /// ```
-    fn panic_err_with(self, msg: &str) -> E;
-}
-
-impl<T, E: Debug> BUResult<T, E> for Result<T, E> {
-    fn or_panic(self) -> T {
-        match self {
-            Ok(value) => value,
-            Err(error) => panic!("called `or_panic()` on an `Err` value: {error:?}"),
-        }
-    }
-
-    fn panic_or(self, default: T) -> T {
-        self.unwrap_or(default)
-    }
+    #[track_caller]
+    fn panic_err_with<M: Display>(self, msg: M) -> E;
 
-    fn panic_or_else<F>(self, f: F) -> T
+    /// Unwraps a result, yielding the content of an `Ok`, or exits the process if the value is an
+    /// `Err`.
+    ///
+    /// Instead of panicking with a backtrace, prints the error formatted using `Debug` as
+    /// `"error: {error:?}"` to stderr and calls [`std::process::exit`] with `code`, which suits
+    /// command-line tools. Like any call to `process::exit`, destructors on the stack do not run. Use
+    /// `or_exit_display()` to print an error that implements `Display` with it instead.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use better_unwrap::BUResult;
+    ///
+    /// let port: Result<u16, std::num::ParseIntError> = "8080".parse();
+    /// assert_eq!(port.or_exit(2), 8080);
+    /// ```
+    #[cfg(feature = "std")]
+    #[track_caller]
+    fn or_exit(self, code: i32) -> T
     where
-        F: FnOnce(E) -> T,
-    {
-        self.unwrap_or_else(f)
-    }
+        E: Debug;
 
-    fn panic_or_default(self) -> T
+    /// Unwraps a result, yielding the content of an `Ok`, or exits the process if the value is an
+    /// `Err`.
+    ///
+    /// Like `or_exit()`, but prints the error formatted using `Display` as `"error: {error}"`, so
+    /// errors with a human-readable message are not shown as a struct dump or a quoted string.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use better_unwrap::BUResult;
+    ///
+    /// let port: Result<u16, std::num::ParseIntError> = "8080".parse();
+    /// assert_eq!(port.or_exit_display(2), 8080);
+    /// ```
+    #[cfg(feature = "std")]
+    #[track_caller]
+    fn or_exit_display(self, code: i32) -> T
     where
-        T: Default,
-    {
-        self.unwrap_or_default()
-    }
-
-    fn panic_with(self, msg: &str) -> T {
-        self.expect(msg)
-    }
+        E: Display;
 
-    fn or_panic_err(self) -> E
-    where
-        T: Debug,
-    {
+    /// Unwraps a result, yielding the content of an `Ok`, or exits the process with `code` and a
+    /// custom message if the value is an `Err`.
+    ///
+    /// Like `or_exit()`, but prints `"error: {msg}"` to stderr, for a user-facing line instead of the
+    /// error's `Debug` rendering.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use better_unwrap::BUResult;
+    ///
+    /// let port: Result<u16, std::num::ParseIntError> = "8080".parse();
+    /// assert_eq!(port.or_exit_with(2, "the port must be a number"), 8080);
+    /// ```
+    #[cfg(feature = "std")]
+    #[track_caller]
+    fn or_exit_with<M: Display>(self, code: i32, msg: M) -> T;
+
+    /// Unwraps a result, yielding the content of an `Ok`, or exits the process with the code and
+    /// message returned by `f` if the value is an `Err`.
+    ///
+    /// `f` receives the error, so that each class of failure can map to its own exit code. It is only
+    /// called on failure. The message is printed to stderr as `"error: {message}"`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::num::IntErrorKind;
+    /// use better_unwrap::BUResult;
+    ///
+    /// let port = "8080".parse::<u16>().or_exit_with_else(|error| match error.kind() {
+    ///     IntErrorKind::Empty => (64, "no port given".to_string()),
+    ///     _ => (65, format!("invalid port: {error}")),
+    /// });
+    /// assert_eq!(port, 8080);
+    /// ```
+    #[cfg(feature = "std")]
+    #[track_caller]
+    fn or_exit_with_else<F, M>(self, f: F) -> T
+    where
+        F: FnOnce(E) -> (i32, M),
+        M: Display;
+
+    /// Unwraps a result, yielding the content of an `Ok`, or aborts the process if the value is an
+    /// `Err`.
+    ///
+    /// Prints the same message as `or_panic()`, with the caller location, to stderr and calls
+    /// [`std::process::abort`], which never unwinds even under `panic = "unwind"`. Use it where
+    /// unwinding is not allowed, such as inside FFI callbacks.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use better_unwrap::BUResult;
+    ///
+    /// let written: Result<usize, std::io::Error> = Ok(512);
+    /// assert_eq!(written.or_abort(), 512);
+    /// ```
+    #[cfg(feature = "std")]
+    #[track_caller]
+    fn or_abort(self) -> T
+    where
+        E: Debug;
+
+    /// Converts a result into an `Option<T>`, logging the discarded error.
+    ///
+    /// Equivalent to `ok()`, but the `Err` value is written to stderr together with the caller location
+    /// instead of being silently dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use better_unwrap::BUResult;
+    ///
+    /// let x: Result<u32, &str> = Err("error");
+    /// assert_eq!(x.ok_or_log(), None); // logs the discarded error to stderr
+    /// ```
+    #[cfg(feature = "std")]
+    #[track_caller]
+    fn ok_or_log(self) -> Option<T>
+    where
+        E: Debug;
+
+    /// Converts a result into an `Option<E>`, logging the discarded `Ok` value.
+    ///
+    /// Equivalent to `err()`, but the `Ok` value is written to stderr together with the caller location
+    /// instead of being silently dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use better_unwrap::BUResult;
+    ///
+    /// let x: Result<u32, &str> = Ok(42);
+    /// assert_eq!(x.err_or_log(), None); // logs the discarded `Ok` value to stderr
+    /// ```
+    #[cfg(feature = "std")]
+    #[track_caller]
+    fn err_or_log(self) -> Option<E>
+    where
+        T: Debug;
+
+    /// Wraps the error in a [`Traced`], capturing a backtrace of where the `Err` was first observed.
+    ///
+    /// A later `or_panic()` on the traced result reports the captured backtrace along with the error,
+    /// so failures unwrapped far from their origin still show where they came from.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use better_unwrap::BUResult;
+    ///
+    /// let x: Result<u32, &str> = Err("error");
+    /// let traced = x.traced();
+    /// assert_eq!(*traced.or_panic_err().error(), "error");
+    /// ```
+    #[cfg(feature = "std")]
+    #[track_caller]
+    fn traced(self) -> Result<T, Traced<E>>;
+}
+
+impl<T, E> BUResult<T, E> for Result<T, E> {
+    #[track_caller]
+    fn or_panic(self) -> T
+    where
+        E: Debug,
+    {
+        match self {
+            Ok(value) => value,
+            Err(error) => policy::fail_detailed(
+                "or_panic",
+                Some(&error),
+                format_args!(
+                    "called `or_panic()` on an `Err` value: {error:?}",
+                    error = Truncated(&error)
+                ),
+            ),
+        }
+    }
+
+    #[track_caller]
+    fn or_panic_ref(&self) -> &T
+    where
+        E: Debug,
+    {
+        match self {
+            Ok(value) => value,
+            Err(error) => policy::fail_detailed(
+                "or_panic_ref",
+                Some(&error),
+                format_args!(
+                    "called `or_panic_ref()` on an `Err` value: {error:?}",
+                    error = Truncated(&error)
+                ),
+            ),
+        }
+    }
+
+    #[track_caller]
+    fn or_panic_mut(&mut self) -> &mut T
+    where
+        E: Debug,
+    {
+        match self {
+            Ok(value) => value,
+            Err(error) => policy::fail_detailed(
+                "or_panic_mut",
+                Some(&error),
+                format_args!(
+                    "called `or_panic_mut()` on an `Err` value: {error:?}",
+                    error = Truncated(&error)
+                ),
+            ),
+        }
+    }
+
+    #[track_caller]
+    fn or_panic_redacted(self) -> T {
+        match self {
+            Ok(value) => value,
+            Err(_) => policy::fail_detailed(
+                "or_panic_redacted",
+                None,
+                format_args!(
+                    "called `or_panic_redacted()` at {} on an `Err` value of type `{}` (contents redacted)",
+                    core::panic::Location::caller(),
+                    core::any::type_name::<E>()
+                ),
+            ),
+        }
+    }
+
+    #[track_caller]
+    fn or_panic_pretty(self) -> T
+    where
+        E: Debug,
+    {
+        match self {
+            Ok(value) => value,
+            Err(error) => policy::fail_detailed(
+                "or_panic_pretty",
+                Some(&error),
+                format_args!(
+                    "called `or_panic_pretty()` on an `Err` value: {error:#?}",
+                    error = Truncated(&error)
+                ),
+            ),
+        }
+    }
+
+    #[track_caller]
+    fn or_panic_display(self) -> T
+    where
+        E: Display,
+    {
+        match self {
+            Ok(value) => value,
+            Err(error) => policy::fail_detailed(
+                "or_panic_display",
+                None,
+                format_args!("called `or_panic_display()` on an `Err` value: {error}"),
+            ),
+        }
+    }
+
+    #[track_caller]
+    fn or_panic_message(self) -> T
+    where
+        E: PanicMessage,
+    {
         match self {
-            Ok(value) => panic!("called `or_panic_err()` on an `Ok` value: {value:?}"),
+            Ok(value) => value,
+            Err(error) => policy::fail_detailed(
+                "or_panic_message",
+                None,
+                format_args!(
+                    "called `or_panic_message()` on an `Err` value: {}",
+                    Rendered(&error)
+                ),
+            ),
+        }
+    }
+
+    #[track_caller]
+    fn or_panic_chained(self) -> T
+    where
+        E: Error,
+    {
+        match self {
+            Ok(value) => value,
+            Err(error) => policy::fail_detailed(
+                "or_panic_chained",
+                Some(&error),
+                format_args!(
+                    "called `or_panic_chained()` on an `Err` value: {}",
+                    Chain(&error)
+                ),
+            ),
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[track_caller]
+    fn or_panic_with_backtrace(self) -> T
+    where
+        E: Debug,
+    {
+        match self {
+            Ok(value) => value,
+            Err(error) => policy::fail_detailed(
+                "or_panic_with_backtrace",
+                Some(&error),
+                format_args!(
+                    "called `or_panic_with_backtrace()` on an `Err` value: {error:?}\n\nbacktrace:\n{}",
+                    Backtrace::force_capture(),
+                    error = Truncated(&error)
+                ),
+            ),
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    #[track_caller]
+    fn expect_ok_eq<U>(self, expected: U)
+    where
+        T: PartialEq<U> + Debug,
+        U: Debug,
+        E: Debug,
+    {
+        match self {
+            Ok(value) if value == expected => {}
+            Ok(value) => policy::fail_detailed(
+                "expect_ok_eq",
+                None,
+                format_args!(
+                    "called `expect_ok_eq()` on an `Ok` value that differs from the expected one:\n{}",
+                    Diff {
+                        expected: &expected,
+                        actual: &value,
+                    }
+                ),
+            ),
+            Err(error) => policy::fail_detailed(
+                "expect_ok_eq",
+                Some(&error),
+                format_args!(
+                    "called `expect_ok_eq()` on an `Err` value: {error:?}\nexpected: Ok({expected:?})",
+                    error = Truncated(&error),
+                    expected = Truncated(&expected)
+                ),
+            ),
+        }
+    }
+
+    #[cfg_attr(
+        all(
+            feature = "no-panic",
+            not(feature = "test-strict"),
+            not(feature = "metrics"),
+            not(feature = "tracing"),
+            not(debug_assertions)
+        ),
+        no_panic::no_panic
+    )]
+    #[track_caller]
+    fn panic_or(self, default: T) -> T {
+        match self {
+            Ok(value) => value,
+            Err(_) => {
+                crate::strict::check("panic_or", "an `Err` value");
+                #[cfg(any(feature = "metrics", feature = "tracing"))]
+                crate::emit::fallback("panic_or");
+                default
+            }
+        }
+    }
+
+    #[cfg_attr(
+        all(
+            feature = "no-panic",
+            not(feature = "test-strict"),
+            not(feature = "metrics"),
+            not(feature = "tracing"),
+            not(debug_assertions)
+        ),
+        no_panic::no_panic
+    )]
+    #[track_caller]
+    fn panic_or_else<F>(self, f: F) -> T
+    where
+        F: FnOnce(E) -> T,
+    {
+        match self {
+            Ok(value) => value,
+            Err(error) => {
+                crate::strict::check("panic_or_else", "an `Err` value");
+                #[cfg(any(feature = "metrics", feature = "tracing"))]
+                crate::emit::fallback("panic_or_else");
+                f(error)
+            }
+        }
+    }
+
+    // Not `unwrap_or_default()`, so that optional features can instrument the fallback branch.
+    #[allow(clippy::manual_unwrap_or_default)]
+    #[cfg_attr(
+        all(
+            feature = "no-panic",
+            not(feature = "test-strict"),
+            not(feature = "metrics"),
+            not(feature = "tracing"),
+            not(debug_assertions)
+        ),
+        no_panic::no_panic
+    )]
+    #[track_caller]
+    fn panic_or_default(self) -> T
+    where
+        T: Default,
+    {
+        match self {
+            Ok(value) => value,
+            Err(_) => {
+                crate::strict::check("panic_or_default", "an `Err` value");
+                #[cfg(any(feature = "metrics", feature = "tracing"))]
+                crate::emit::fallback("panic_or_default");
+                T::default()
+            }
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[track_caller]
+    fn panic_or_log(self, default: T) -> T
+    where
+        E: Debug,
+    {
+        match self {
+            Ok(value) => value,
+            Err(error) => {
+                emit::log_fallback(
+                    "panic_or_log",
+                    format_args!("an `Err` value: {error:?}", error = Truncated(&error)),
+                );
+                default
+            }
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[track_caller]
+    fn panic_or_log_else<F>(self, f: F) -> T
+    where
+        E: Debug,
+        F: FnOnce(E) -> T,
+    {
+        match self {
+            Ok(value) => value,
+            Err(error) => {
+                emit::log_fallback(
+                    "panic_or_log_else",
+                    format_args!("an `Err` value: {error:?}", error = Truncated(&error)),
+                );
+                f(error)
+            }
+        }
+    }
+
+    #[cfg_attr(
+        all(
+            feature = "no-panic",
+            not(feature = "test-strict"),
+            not(feature = "metrics"),
+            not(feature = "tracing"),
+            not(debug_assertions)
+        ),
+        no_panic::no_panic
+    )]
+    #[track_caller]
+    fn debug_panic_or(self, default: T) -> T
+    where
+        E: Debug,
+    {
+        match self {
+            Ok(value) => value,
+            Err(error) if cfg!(debug_assertions) => policy::fail_detailed(
+                "debug_panic_or",
+                Some(&error),
+                format_args!(
+                    "called `debug_panic_or()` on an `Err` value: {error:?}",
+                    error = Truncated(&error)
+                ),
+            ),
+            Err(_) => {
+                #[cfg(any(feature = "metrics", feature = "tracing"))]
+                crate::emit::fallback("debug_panic_or");
+                default
+            }
+        }
+    }
+
+    #[cfg_attr(
+        all(
+            feature = "no-panic",
+            not(feature = "test-strict"),
+            not(feature = "metrics"),
+            not(feature = "tracing"),
+            not(debug_assertions)
+        ),
+        no_panic::no_panic
+    )]
+    #[track_caller]
+    fn debug_panic_or_else<F>(self, f: F) -> T
+    where
+        E: Debug,
+        F: FnOnce(E) -> T,
+    {
+        match self {
+            Ok(value) => value,
+            Err(error) if cfg!(debug_assertions) => policy::fail_detailed(
+                "debug_panic_or_else",
+                Some(&error),
+                format_args!(
+                    "called `debug_panic_or_else()` on an `Err` value: {error:?}",
+                    error = Truncated(&error)
+                ),
+            ),
+            Err(error) => {
+                #[cfg(any(feature = "metrics", feature = "tracing"))]
+                crate::emit::fallback("debug_panic_or_else");
+                f(error)
+            }
+        }
+    }
+
+    #[track_caller]
+    fn panic_with<M: Display>(self, msg: M) -> T
+    where
+        E: Debug,
+    {
+        match self {
+            Ok(value) => value,
+            Err(error) => policy::fail_detailed(
+                "panic_with",
+                Some(&error),
+                format_args!("{msg}: {error:?}", error = Truncated(&error)),
+            ),
+        }
+    }
+
+    #[track_caller]
+    fn panic_with_else<F, M>(self, f: F) -> T
+    where
+        F: FnOnce(E) -> M,
+        M: Display,
+    {
+        match self {
+            Ok(value) => value,
+            Err(error) => {
+                policy::fail_detailed("panic_with_else", None, format_args!("{}", f(error)))
+            }
+        }
+    }
+
+    #[track_caller]
+    fn or_panic_err(self) -> E
+    where
+        T: Debug,
+    {
+        match self {
+            Ok(value) => policy::fail_detailed(
+                "or_panic_err",
+                None,
+                format_args!(
+                    "called `or_panic_err()` on an `Ok` value: {value:?}",
+                    value = Truncated(&value)
+                ),
+            ),
             Err(error) => error,
         }
     }
 
-    fn panic_err_with(self, msg: &str) -> E {
+    #[track_caller]
+    fn or_panic_err_display(self) -> E
+    where
+        T: Display,
+    {
         match self {
-            Ok(_) => panic!("{}", msg),
+            Ok(value) => policy::fail_detailed(
+                "or_panic_err_display",
+                None,
+                format_args!("called `or_panic_err_display()` on an `Ok` value: {value}"),
+            ),
             Err(error) => error,
         }
     }
+
+    #[track_caller]
+    fn panic_err_with<M: Display>(self, msg: M) -> E {
+        match self {
+            Ok(_) => policy::fail_detailed("panic_err_with", None, format_args!("{msg}")),
+            Err(error) => error,
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[track_caller]
+    fn or_exit(self, code: i32) -> T
+    where
+        E: Debug,
+    {
+        match self {
+            Ok(value) => value,
+            Err(error) => emit::exit(code, format_args!("{error:?}", error = Truncated(&error))),
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[track_caller]
+    fn or_exit_display(self, code: i32) -> T
+    where
+        E: Display,
+    {
+        match self {
+            Ok(value) => value,
+            Err(error) => emit::exit(code, format_args!("{error}")),
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[track_caller]
+    fn or_exit_with<M: Display>(self, code: i32, msg: M) -> T {
+        match self {
+            Ok(value) => value,
+            Err(_) => emit::exit(code, format_args!("{msg}")),
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[track_caller]
+    fn or_exit_with_else<F, M>(self, f: F) -> T
+    where
+        F: FnOnce(E) -> (i32, M),
+        M: Display,
+    {
+        match self {
+            Ok(value) => value,
+            Err(error) => {
+                let (code, msg) = f(error);
+                emit::exit(code, format_args!("{msg}"))
+            }
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[track_caller]
+    fn or_abort(self) -> T
+    where
+        E: Debug,
+    {
+        match self {
+            Ok(value) => value,
+            Err(error) => emit::abort(&Report::capture(format_args!(
+                "called `or_abort()` on an `Err` value: {error:?}",
+                error = Truncated(&error)
+            ))),
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[track_caller]
+    fn ok_or_log(self) -> Option<T>
+    where
+        E: Debug,
+    {
+        match self {
+            Ok(value) => Some(value),
+            Err(error) => {
+                emit::log(
+                    Location::caller(),
+                    format_args!(
+                        "discarded `Err` value: {error:?}",
+                        error = Truncated(&error)
+                    ),
+                );
+                None
+            }
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[track_caller]
+    fn err_or_log(self) -> Option<E>
+    where
+        T: Debug,
+    {
+        match self {
+            Ok(value) => {
+                emit::log(
+                    Location::caller(),
+                    format_args!("discarded `Ok` value: {value:?}", value = Truncated(&value)),
+                );
+                None
+            }
+            Err(error) => Some(error),
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[track_caller]
+    fn traced(self) -> Result<T, Traced<E>> {
+        self.map_err(Traced::new)
+    }
+}
+
+/// Trait that provides methods for `Result<Option<T>, E>`, as returned by database and API clients
+/// whose lookups can both fail and find nothing.
+pub trait BUResultOption<T, E> {
+    /// Unwraps both layers, yielding the content of the inner `Some`.
+    ///
+    /// Unlike `transpose().or_panic().or_panic()`, the panic message says whether the lookup failed or
+    /// found nothing.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is an `Err`, with a panic message including the error value formatted using
+    /// `Debug`, or if it is `Ok(None)`, with a panic message saying that no value was found.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use better_unwrap::BUResultOption;
+    ///
+    /// let row: Result<Option<&str>, &str> = Ok(Some("ada"));
+    /// assert_eq!(row.some_or_panic(), "ada");
+    /// ```
+    ///
+    /// ```should_panic
+    /// use better_unwrap::BUResultOption;
+    ///
+    /// let row: Result<Option<&str>, &str> = Ok(None);
+    /// row.some_or_panic(); // panics with `"called `some_or_panic()` on an `Ok(None)` value: no value found"`
+    /// ```
+    #[track_caller]
+    fn some_or_panic(self) -> T
+    where
+        E: Debug;
+
+    /// Replaces a missing value with `default`, keeping the error if there is one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use better_unwrap::BUResultOption;
+    ///
+    /// let row: Result<Option<u32>, &str> = Ok(None);
+    /// assert_eq!(row.some_or(0), Ok(0));
+    ///
+    /// let row: Result<Option<u32>, &str> = Err("connection reset");
+    /// assert_eq!(row.some_or(0), Err("connection reset"));
+    /// ```
+    fn some_or(self, default: T) -> Result<T, E>;
+
+    /// Replaces a missing value with the result of `f`, keeping the error if there is one.
+    ///
+    /// `f` is only called if the value is `Ok(None)`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use better_unwrap::BUResultOption;
+    ///
+    /// let row: Result<Option<Vec<u32>>, &str> = Ok(None);
+    /// assert_eq!(row.some_or_else(Vec::new), Ok(vec![]));
+    /// ```
+    fn some_or_else<F>(self, f: F) -> Result<T, E>
+    where
+        F: FnOnce() -> T;
 }
 
+impl<T, E> BUResultOption<T, E> for Result<Option<T>, E> {
+    #[track_caller]
+    fn some_or_panic(self) -> T
+    where
+        E: Debug,
+    {
+        match self {
+            Ok(Some(value)) => value,
+            Ok(None) => policy::fail_detailed(
+                "some_or_panic",
+                None,
+                format_args!("called `some_or_panic()` on an `Ok(None)` value: no value found"),
+            ),
+            Err(error) => policy::fail_detailed(
+                "some_or_panic",
+                Some(&error),
+                format_args!(
+                    "called `some_or_panic()` on an `Err` value: {error:?}",
+                    error = Truncated(&error)
+                ),
+            ),
+        }
+    }
+
+    fn some_or(self, default: T) -> Result<T, E> {
+        self.map(|value| value.unwrap_or(default))
+    }
+
+    fn some_or_else<F>(self, f: F) -> Result<T, E>
+    where
+        F: FnOnce() -> T,
+    {
+        self.map(|value| value.unwrap_or_else(f))
+    }
+}
+
+/// Trait that provides alternatives to `unwrap()` for results carrying several errors, such as the
+/// `Result<T, Vec<E>>` returned by validators and compilers that accumulate errors.
+#[cfg(feature = "alloc")]
+pub trait BUMultiResult<T, E> {
+    /// Unwraps a result, yielding the content of an `Ok`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is an `Err`, with a panic message listing every error, numbered and
+    /// formatted using `Debug`, under a count header.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use better_unwrap::BUMultiResult;
+    ///
+    /// let x: Result<u32, Vec<&str>> = Ok(42);
+    /// assert_eq!(x.or_panic_multi(), 42);
+    /// ```
+    ///
+    /// ```should_panic
+    /// use better_unwrap::BUMultiResult;
+    ///
+    /// let x: Result<u32, Vec<&str>> = Err(vec!["missing field `name`", "unknown field `nmae`"]);
+    /// x.or_panic_multi(); // panics with `"called `or_panic_multi()` on an `Err` value with 2 errors:\n  1. \"missing field `name`\"\n  2. \"unknown field `nmae`\""`
+    /// ```
+    #[track_caller]
+    fn or_panic_multi(self) -> T;
+}
+
+#[cfg(feature = "alloc")]
+impl<T, E: Debug> BUMultiResult<T, E> for Result<T, Vec<E>> {
+    #[track_caller]
+    fn or_panic_multi(self) -> T {
+        match self {
+            Ok(value) => value,
+            Err(errors) => policy::fail_detailed(
+                "or_panic_multi",
+                Some(&errors),
+                format_args!(
+                    "called `or_panic_multi()` on an `Err` value with {}",
+                    MultiError(&errors)
+                ),
+            ),
+        }
+    }
+}
+
+/// Renders a list of errors as a count header followed by a numbered list.
+#[cfg(feature = "alloc")]
+struct MultiError<'a, E>(&'a [E]);
+
+#[cfg(feature = "alloc")]
+impl<E: Debug> fmt::Display for MultiError<'_, E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0.len() {
+            1 => write!(f, "1 error:")?,
+            n => write!(f, "{n} errors:")?,
+        }
+        for (i, error) in self.0.iter().enumerate() {
+            write!(f, "\n  {}. {:?}", i + 1, Truncated(error))?;
+        }
+        Ok(())
+    }
+}
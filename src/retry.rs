@@ -0,0 +1,417 @@
+//! Retrying fallible operations, panicking with every attempt's error once the attempts run out.
+
+use std::collections::hash_map::RandomState;
+use std::fmt::{self, Debug};
+use std::hash::BuildHasher;
+use std::panic::Location;
+use std::time::{Duration, Instant};
+
+use crate::message::Truncated;
+use crate::policy;
+
+/// How many times to attempt an operation and how long to wait between attempts.
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+/// use better_unwrap::retry::Backoff;
+///
+/// // 10ms, 20ms, 40ms, 80ms between five attempts, each randomized by up to half.
+/// let backoff = Backoff::exponential(Duration::from_millis(10)).jitter().max(5);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Backoff {
+    initial: Duration,
+    factor: u32,
+    attempts: usize,
+    jitter: bool,
+}
+
+impl Backoff {
+    /// The number of attempts made unless [`max`](Self::max) is called.
+    pub const DEFAULT_ATTEMPTS: usize = 3;
+
+    /// Waits `delay` between every attempt.
+    pub const fn constant(delay: Duration) -> Self {
+        Backoff {
+            initial: delay,
+            factor: 1,
+            attempts: Self::DEFAULT_ATTEMPTS,
+            jitter: false,
+        }
+    }
+
+    /// Waits `initial` after the first attempt, doubling the delay after every further attempt.
+    pub const fn exponential(initial: Duration) -> Self {
+        Backoff {
+            initial,
+            factor: 2,
+            attempts: Self::DEFAULT_ATTEMPTS,
+            jitter: false,
+        }
+    }
+
+    /// Randomizes each delay to between half and all of its nominal length, so that clients retrying
+    /// the same failure do not retry in lockstep.
+    pub const fn jitter(self) -> Self {
+        Backoff {
+            jitter: true,
+            ..self
+        }
+    }
+
+    /// Sets the maximum number of attempts, including the first. At least one attempt is always made.
+    pub const fn max(self, attempts: usize) -> Self {
+        Backoff { attempts, ..self }
+    }
+
+    /// Returns the maximum number of attempts.
+    pub const fn attempts(&self) -> usize {
+        if self.attempts == 0 { 1 } else { self.attempts }
+    }
+
+    /// Returns the delay before the attempt following attempt number `attempt` (counting from 1).
+    pub fn delay_after(&self, attempt: usize) -> Duration {
+        let exponent = u32::try_from(attempt.saturating_sub(1)).unwrap_or(u32::MAX);
+        let nominal = self
+            .initial
+            .saturating_mul(self.factor.saturating_pow(exponent));
+        if self.jitter {
+            let half = nominal / 2;
+            let random = RandomState::new().hash_one(attempt);
+            half + half.mul_f64((random % 1024) as f64 / 1023.0)
+        } else {
+            nominal
+        }
+    }
+}
+
+/// One failed attempt: the error and how long after the first attempt started it failed.
+struct Attempt<E> {
+    elapsed: Duration,
+    error: E,
+}
+
+/// Renders every failed attempt of a retried operation.
+struct Attempts<'a, E>(&'a [Attempt<E>]);
+
+impl<E: Debug> fmt::Display for Attempts<'_, E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (number, attempt) in self.0.iter().enumerate() {
+            if number > 0 {
+                f.write_str("\n")?;
+            }
+            write!(
+                f,
+                "  attempt {} failed after {:?}: {:?}",
+                number + 1,
+                attempt.elapsed,
+                Truncated(&attempt.error)
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Fails with every failed attempt, naming `location` as the call site when the failure is not
+/// reported from it, as in an asynchronous retry.
+#[track_caller]
+fn fail_after_attempts<E: Debug>(attempts: &[Attempt<E>], location: Option<&Location<'_>>) -> ! {
+    match location {
+        None => policy::fail(format_args!(
+            "operation failed after {} attempts:\n{}",
+            attempts.len(),
+            Attempts(attempts)
+        )),
+        Some(location) => policy::fail(format_args!(
+            "operation retried at {location} failed after {} attempts:\n{}",
+            attempts.len(),
+            Attempts(attempts)
+        )),
+    }
+}
+
+/// Calls `f` up to `attempts` times, without waiting, until it returns `Ok`.
+///
+/// At least one attempt is always made. Use [`retry_or_panic_with`] to wait between attempts.
+///
+/// # Errors
+///
+/// Returns the error of every attempt, in order, if every attempt fails. Unwrap it with
+/// [`or_panic_multi()`](crate::BUMultiResult::or_panic_multi) to list them all in the panic message.
+///
+/// # Examples
+///
+/// ```
+/// let mut calls = 0;
+/// let response = better_unwrap::retry(3, || {
+///     calls += 1;
+///     if calls < 2 { Err("connection refused") } else { Ok("pong") }
+/// });
+/// assert_eq!(response, Ok("pong"));
+///
+/// let errors = better_unwrap::retry(2, || Err::<(), _>("connection refused"));
+/// assert_eq!(errors, Err(vec!["connection refused", "connection refused"]));
+/// ```
+pub fn retry<T, E, F>(attempts: usize, mut f: F) -> Result<T, Vec<E>>
+where
+    F: FnMut() -> Result<T, E>,
+{
+    let mut errors = Vec::new();
+    for _ in 0..attempts.max(1) {
+        match f() {
+            Ok(value) => return Ok(value),
+            Err(error) => errors.push(error),
+        }
+    }
+    Err(errors)
+}
+
+/// Calls `f` up to `attempts` times, without waiting, until it returns `Ok`.
+///
+/// Equivalent to [`retry_or_panic_with`] with a [`Backoff`] of `attempts` attempts and no delay.
+///
+/// # Panics
+///
+/// Panics if every attempt fails, with a panic message listing each attempt's error (formatted using
+/// `Debug`) and when it failed.
+///
+/// # Examples
+///
+/// ```should_panic
+/// better_unwrap::retry_or_panic(3, || std::fs::read_to_string("/nonexistent/app.toml")); // panics with `"operation failed after 3 attempts:\n  attempt 1 failed after ..."`
+/// ```
+#[track_caller]
+pub fn retry_or_panic<T, E, F>(attempts: usize, f: F) -> T
+where
+    F: FnMut() -> Result<T, E>,
+    E: Debug,
+{
+    retry_or_panic_with(Backoff::constant(Duration::ZERO).max(attempts), f)
+}
+
+/// Calls `f` until it returns `Ok`, waiting between attempts as described by `backoff`.
+///
+/// # Panics
+///
+/// Panics if every attempt fails, with a panic message listing each attempt's error (formatted using
+/// `Debug`) and when it failed.
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+/// use better_unwrap::retry::{Backoff, retry_or_panic_with};
+///
+/// let mut calls = 0;
+/// let response = retry_or_panic_with(Backoff::exponential(Duration::from_millis(1)).max(5), || {
+///     calls += 1;
+///     if calls < 3 { Err("connection refused") } else { Ok("pong") }
+/// });
+/// assert_eq!(response, "pong");
+/// ```
+#[track_caller]
+pub fn retry_or_panic_with<T, E, F>(backoff: Backoff, mut f: F) -> T
+where
+    F: FnMut() -> Result<T, E>,
+    E: Debug,
+{
+    let start = Instant::now();
+    let mut attempts = Vec::new();
+    for attempt in 1..=backoff.attempts() {
+        match f() {
+            Ok(value) => return value,
+            Err(error) => attempts.push(Attempt {
+                elapsed: start.elapsed(),
+                error,
+            }),
+        }
+        if attempt < backoff.attempts() {
+            std::thread::sleep(backoff.delay_after(attempt));
+        }
+    }
+    fail_after_attempts(&attempts, None)
+}
+
+/// Awaits the futures returned by `f` until one resolves to `Ok`, waiting between attempts as
+/// described by `backoff`.
+///
+/// The asynchronous counterpart of [`retry_or_panic_with`], sleeping with `tokio::time::sleep`.
+/// Available with the `tokio` feature.
+///
+/// The caller location is captured when `retry_or_panic_with_async()` is called, so the panic
+/// message points at the call site rather than at the executor that polled the future.
+///
+/// # Panics
+///
+/// Panics if every attempt fails, with a panic message including the caller location and listing
+/// each attempt's error (formatted using `Debug`) and when it failed.
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+/// use better_unwrap::retry::{Backoff, retry_or_panic_with_async};
+///
+/// # tokio::runtime::Builder::new_current_thread().enable_time().build().unwrap().block_on(async {
+/// let response = retry_or_panic_with_async(Backoff::constant(Duration::from_millis(1)), || async {
+///     Ok::<_, std::io::Error>("pong")
+/// })
+/// .await;
+/// assert_eq!(response, "pong");
+/// # });
+/// ```
+#[cfg(feature = "tokio")]
+#[track_caller]
+pub fn retry_or_panic_with_async<T, E, F, Fut>(
+    backoff: Backoff,
+    mut f: F,
+) -> impl Future<Output = T>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, E>>,
+    E: Debug,
+{
+    let location = Location::caller();
+    async move {
+        let start = Instant::now();
+        let mut attempts = Vec::new();
+        for attempt in 1..=backoff.attempts() {
+            match f().await {
+                Ok(value) => return value,
+                Err(error) => attempts.push(Attempt {
+                    elapsed: start.elapsed(),
+                    error,
+                }),
+            }
+            if attempt < backoff.attempts() {
+                tokio::time::sleep(backoff.delay_after(attempt)).await;
+            }
+        }
+        fail_after_attempts(&attempts, Some(location))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FAST: Backoff = Backoff::exponential(Duration::from_millis(1));
+
+    #[test]
+    fn test_retry_returns_first_ok() {
+        let mut calls = 0;
+        let value = retry(5, || {
+            calls += 1;
+            if calls == 2 {
+                Ok(calls)
+            } else {
+                Err("refused")
+            }
+        });
+        assert_eq!(value, Ok(2));
+        assert_eq!(calls, 2);
+    }
+
+    #[test]
+    fn test_retry_returns_every_error() {
+        let mut calls = 0;
+        let errors = retry(3, || -> Result<(), _> {
+            calls += 1;
+            Err(calls)
+        });
+        assert_eq!(errors, Err(vec![1, 2, 3]));
+        assert_eq!(retry(0, || Err::<(), _>("refused")), Err(vec!["refused"]));
+    }
+
+    #[test]
+    #[should_panic(expected = "operation failed after 3 attempts:\n  attempt 1 failed after")]
+    fn test_retry_or_panic_lists_every_attempt() {
+        let _: u32 = retry_or_panic(3, || Err("refused"));
+    }
+
+    #[test]
+    fn test_delay_after_doubles_exponentially() {
+        let backoff = Backoff::exponential(Duration::from_millis(10));
+        assert_eq!(backoff.delay_after(1), Duration::from_millis(10));
+        assert_eq!(backoff.delay_after(2), Duration::from_millis(20));
+        assert_eq!(backoff.delay_after(4), Duration::from_millis(80));
+    }
+
+    #[test]
+    fn test_delay_after_with_jitter_stays_within_bounds() {
+        let backoff = Backoff::constant(Duration::from_millis(100)).jitter();
+        for attempt in 1..20 {
+            let delay = backoff.delay_after(attempt);
+            assert!(delay >= Duration::from_millis(50) && delay <= Duration::from_millis(100));
+        }
+    }
+
+    #[test]
+    fn test_retry_or_panic_with_eventual_success() {
+        let mut calls = 0;
+        let value = retry_or_panic_with(FAST.max(4), || {
+            calls += 1;
+            if calls == 3 {
+                Ok(calls)
+            } else {
+                Err("refused")
+            }
+        });
+        assert_eq!(value, 3);
+    }
+
+    #[test]
+    #[should_panic(expected = "operation failed after 2 attempts:\n  attempt 1 failed after")]
+    fn test_retry_or_panic_with_lists_every_attempt() {
+        let mut calls = 0;
+        let _: u32 = retry_or_panic_with(FAST.max(2), || {
+            calls += 1;
+            Err(format!("refused #{calls}"))
+        });
+    }
+
+    #[test]
+    fn test_retry_or_panic_with_reports_each_error() {
+        let payload = std::panic::catch_unwind(|| {
+            let mut calls = 0;
+            let _: u32 = retry_or_panic_with(FAST.max(3), || {
+                calls += 1;
+                Err(calls)
+            });
+        })
+        .err()
+        .and_then(|payload| payload.downcast::<String>().ok())
+        .map(|message| *message)
+        .unwrap_or_default();
+        assert!(payload.contains("attempt 1 failed after"));
+        assert!(payload.contains(": 1\n  attempt 2 failed after"));
+        assert!(payload.ends_with(": 3"));
+    }
+
+    #[cfg(feature = "tokio")]
+    #[tokio::test]
+    #[should_panic(expected = "failed after 3 attempts")]
+    async fn test_retry_or_panic_with_async_lists_every_attempt() {
+        let _: u32 = retry_or_panic_with_async(FAST, || async { Err("refused") }).await;
+    }
+
+    #[cfg(feature = "tokio")]
+    #[tokio::test]
+    async fn test_retry_or_panic_with_async_reports_caller_location() {
+        use crate::BUResult;
+
+        let line = line!() + 1;
+        let retried = retry_or_panic_with_async(FAST.max(1), || async { Err::<u32, _>("refused") });
+        let payload = tokio::spawn(retried).await.or_panic_err().into_panic();
+        let message = payload.downcast::<String>().or_panic();
+        assert!(
+            message.starts_with(&format!(
+                "operation retried at {}:{line}:23 failed after 1 attempts:",
+                file!()
+            )),
+            "{message}"
+        );
+    }
+}
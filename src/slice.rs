@@ -0,0 +1,99 @@
+use crate::policy;
+
+/// Trait that provides alternatives to `get(i).unwrap()` and `get_mut(i).unwrap()` for slices and
+/// `Vec`.
+///
+/// The panic message includes the requested index and the length of the slice.
+pub trait BUSlice<T> {
+    /// Returns a reference to the element at `index`.
+    ///
+    /// Equivalent to `get(index).unwrap()`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds, with a panic message including the index and the length.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use better_unwrap::BUSlice;
+    ///
+    /// let values = vec![10, 20, 30];
+    /// assert_eq!(*values.get_or_panic(1), 20);
+    /// ```
+    ///
+    /// ```should_panic
+    /// use better_unwrap::BUSlice;
+    ///
+    /// let values = [1, 2, 3, 4, 5];
+    /// values.get_or_panic(12); // panics with `"index 12 out of bounds, len is 5"`
+    /// ```
+    #[track_caller]
+    fn get_or_panic(&self, index: usize) -> &T;
+
+    /// Returns a mutable reference to the element at `index`.
+    ///
+    /// Equivalent to `get_mut(index).unwrap()`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds, with a panic message including the index and the length.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use better_unwrap::BUSlice;
+    ///
+    /// let mut values = vec![10, 20, 30];
+    /// *values.get_mut_or_panic(1) += 1;
+    /// assert_eq!(values, [10, 21, 30]);
+    /// ```
+    #[track_caller]
+    fn get_mut_or_panic(&mut self, index: usize) -> &mut T;
+}
+
+impl<T> BUSlice<T> for [T] {
+    #[track_caller]
+    fn get_or_panic(&self, index: usize) -> &T {
+        let len = self.len();
+        match self.get(index) {
+            Some(element) => element,
+            None => policy::fail(format_args!("index {index} out of bounds, len is {len}")),
+        }
+    }
+
+    #[track_caller]
+    fn get_mut_or_panic(&mut self, index: usize) -> &mut T {
+        let len = self.len();
+        match self.get_mut(index) {
+            Some(element) => element,
+            None => policy::fail(format_args!("index {index} out of bounds, len is {len}")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_get_or_panic_in_bounds() {
+        let mut values = [10, 20, 30];
+        *values.get_mut_or_panic(2) += 1;
+        assert_eq!(*values.get_or_panic(2), 31);
+    }
+
+    #[test]
+    #[should_panic(expected = "index 12 out of bounds, len is 5")]
+    fn test_get_or_panic_panics_out_of_bounds() {
+        let values = [1, 2, 3, 4, 5];
+        let _ = values.get_or_panic(12);
+    }
+
+    #[test]
+    #[should_panic(expected = "index 0 out of bounds, len is 0")]
+    fn test_get_mut_or_panic_panics_on_empty() {
+        let mut values: Vec<u32> = Vec::new();
+        let _ = values.get_mut_or_panic(0);
+    }
+}
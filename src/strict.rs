@@ -0,0 +1,70 @@
+//! Strict mode, in which the fallback methods panic instead of returning their fallback.
+//!
+//! Strict mode is enabled at compile time by the `test-strict` feature, or at runtime by setting the
+//! `BETTER_UNWRAP_STRICT` environment variable to `1` or `true`.
+
+#[cfg(all(feature = "std", not(all(feature = "no-panic", not(debug_assertions)))))]
+use std::sync::OnceLock;
+
+use crate::policy;
+
+/// The environment variable that enables strict mode at runtime. It is read once, on the first
+/// fallback.
+#[cfg(feature = "std")]
+#[cfg_attr(all(feature = "no-panic", not(debug_assertions)), allow(dead_code))]
+const STRICT_VAR: &str = "BETTER_UNWRAP_STRICT";
+
+/// Fails if strict mode is enabled, instead of letting `method`, called on `value`, return its
+/// fallback.
+#[inline]
+#[track_caller]
+pub(crate) fn check(method: &'static str, value: &str) {
+    if let Some(reason) = reason() {
+        policy::fail_detailed(
+            method,
+            None,
+            format_args!(
+                "called `{method}()` on {value}, which panics instead of falling back {reason}"
+            ),
+        )
+    }
+}
+
+/// Returns why strict mode is enabled, or `None` if it is not.
+#[inline]
+fn reason() -> Option<&'static str> {
+    if cfg!(feature = "test-strict") {
+        return Some("under the `test-strict` feature");
+    }
+    // Under `no-panic`, release builds must not have a panic path in the fallback methods.
+    #[cfg(all(feature = "std", not(all(feature = "no-panic", not(debug_assertions)))))]
+    {
+        static STRICT: OnceLock<bool> = OnceLock::new();
+        let enabled = STRICT.get_or_init(|| is_enabled(std::env::var(STRICT_VAR).ok().as_deref()));
+        if *enabled {
+            return Some("because `BETTER_UNWRAP_STRICT` is set");
+        }
+    }
+    None
+}
+
+/// Returns whether a value of [`STRICT_VAR`] enables strict mode.
+#[cfg(feature = "std")]
+#[cfg_attr(all(feature = "no-panic", not(debug_assertions)), allow(dead_code))]
+fn is_enabled(value: Option<&str>) -> bool {
+    matches!(value, Some("1" | "true"))
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_enabled() {
+        assert!(is_enabled(Some("1")));
+        assert!(is_enabled(Some("true")));
+        assert!(!is_enabled(Some("0")));
+        assert!(!is_enabled(Some("")));
+        assert!(!is_enabled(None));
+    }
+}
@@ -0,0 +1,205 @@
+use std::any;
+use std::borrow::Cow;
+use std::fmt::Display;
+use std::str::{self, FromStr, Utf8Error};
+
+use crate::message::Truncated;
+use crate::policy;
+
+/// Trait that provides an alternative to `parse().unwrap()` for string slices.
+///
+/// Unlike unwrapping the `Result` returned by `parse()`, the panic message includes the input that
+/// failed to parse and the type it was parsed as.
+pub trait BUStr {
+    /// Parses the string into a `T`.
+    ///
+    /// Equivalent to `parse::<T>().unwrap()`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the string does not parse, with a panic message including the input, the target type
+    /// name, and the parse error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use better_unwrap::BUStr;
+    ///
+    /// assert_eq!("42".parse_or_panic::<u32>(), 42);
+    /// ```
+    ///
+    /// ```should_panic
+    /// use better_unwrap::BUStr;
+    ///
+    /// "42x".parse_or_panic::<u32>(); // panics with `"failed to parse \"42x\" as `u32`: invalid digit found in string"`
+    /// ```
+    #[track_caller]
+    fn parse_or_panic<T>(&self) -> T
+    where
+        T: FromStr,
+        T::Err: Display;
+}
+
+impl BUStr for str {
+    #[track_caller]
+    fn parse_or_panic<T>(&self) -> T
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        match self.parse() {
+            Ok(value) => value,
+            Err(error) => policy::fail(format_args!(
+                "failed to parse {value:?} as `{}`: {error}",
+                any::type_name::<T>(),
+                value = Truncated(self)
+            )),
+        }
+    }
+}
+
+/// How many bytes on each side of an invalid UTF-8 sequence are shown in panic messages.
+const UTF8_PREVIEW_RADIUS: usize = 8;
+
+/// Trait that provides alternatives to `String::from_utf8(bytes).unwrap()` and
+/// `str::from_utf8(bytes).unwrap()` for byte buffers.
+///
+/// Unlike unwrapping the `Result` returned by the conversion, the panic message includes the position
+/// of the invalid bytes and a lossy preview of the bytes around them.
+pub trait BUUtf8 {
+    /// The string type the bytes convert into: `String` for `Vec<u8>`, `&str` for `&[u8]`.
+    type Str;
+
+    /// The string type of the lossy conversion: `String` for `Vec<u8>`, `Cow<str>` for `&[u8]`.
+    type Lossy;
+
+    /// Converts the bytes into a string.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the bytes are not valid UTF-8, with a panic message including the position of the
+    /// first invalid byte and a lossy preview of the bytes around it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use better_unwrap::BUUtf8;
+    ///
+    /// let body = b"hello".to_vec();
+    /// assert_eq!(body.utf8_or_panic(), "hello");
+    /// ```
+    ///
+    /// ```should_panic
+    /// use better_unwrap::BUUtf8;
+    ///
+    /// let body: &[u8] = b"caf\xC3 au lait";
+    /// body.utf8_or_panic(); // panics with `"invalid UTF-8 at byte 3 of 11 (near \"caf\u{FFFD} au lait\"): invalid utf-8 sequence of 1 bytes from index 3"`
+    /// ```
+    #[track_caller]
+    fn utf8_or_panic(self) -> Self::Str;
+
+    /// Converts the bytes into a string, replacing invalid sequences with `U+FFFD REPLACEMENT
+    /// CHARACTER`.
+    ///
+    /// Equivalent to `String::from_utf8_lossy`, without copying a `Vec<u8>` that is already valid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use better_unwrap::BUUtf8;
+    ///
+    /// let body = b"caf\xC3".to_vec();
+    /// assert_eq!(body.utf8_or_lossy(), "caf\u{FFFD}");
+    /// ```
+    fn utf8_or_lossy(self) -> Self::Lossy;
+}
+
+impl BUUtf8 for Vec<u8> {
+    type Str = String;
+    type Lossy = String;
+
+    #[track_caller]
+    fn utf8_or_panic(self) -> String {
+        match String::from_utf8(self) {
+            Ok(string) => string,
+            Err(error) => fail_utf8(error.as_bytes(), error.utf8_error()),
+        }
+    }
+
+    fn utf8_or_lossy(self) -> String {
+        match String::from_utf8(self) {
+            Ok(string) => string,
+            Err(error) => String::from_utf8_lossy(error.as_bytes()).into_owned(),
+        }
+    }
+}
+
+impl<'a> BUUtf8 for &'a [u8] {
+    type Str = &'a str;
+    type Lossy = Cow<'a, str>;
+
+    #[track_caller]
+    fn utf8_or_panic(self) -> &'a str {
+        match str::from_utf8(self) {
+            Ok(string) => string,
+            Err(error) => fail_utf8(self, error),
+        }
+    }
+
+    fn utf8_or_lossy(self) -> Cow<'a, str> {
+        String::from_utf8_lossy(self)
+    }
+}
+
+/// Fails with the position of the invalid UTF-8 in `bytes` and a lossy preview of the bytes around it.
+#[track_caller]
+fn fail_utf8(bytes: &[u8], error: Utf8Error) -> ! {
+    let position = error.valid_up_to();
+    let start = position.saturating_sub(UTF8_PREVIEW_RADIUS);
+    let end = bytes.len().min(position + UTF8_PREVIEW_RADIUS);
+    policy::fail(format_args!(
+        "invalid UTF-8 at byte {position} of {} (near {:?}): {error}",
+        bytes.len(),
+        String::from_utf8_lossy(&bytes[start..end])
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::SocketAddr;
+
+    #[test]
+    fn test_parse_or_panic_with_valid_input() {
+        assert_eq!("-7".parse_or_panic::<i64>(), -7);
+        let addr: SocketAddr = String::from("127.0.0.1:8080").parse_or_panic();
+        assert_eq!(addr.port(), 8080);
+    }
+
+    #[test]
+    #[should_panic(expected = "failed to parse \"42x\" as `u32`: invalid digit found in string")]
+    fn test_parse_or_panic_panics_with_input_and_type() {
+        let _ = "42x".parse_or_panic::<u32>();
+    }
+
+    #[test]
+    fn test_utf8_or_panic_with_valid_bytes() {
+        assert_eq!(b"hello".to_vec().utf8_or_panic(), "hello");
+        assert_eq!(b"hello".as_slice().utf8_or_panic(), "hello");
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid UTF-8 at byte 13 of 15 (near \" a long \u{FFFD}\u{FFFD}\")")]
+    fn test_utf8_or_panic_panics_with_position_and_preview() {
+        let _ = b"quite a long \xFF\xFE".to_vec().utf8_or_panic();
+    }
+
+    #[test]
+    fn test_utf8_or_lossy() {
+        assert_eq!(b"caf\xC3".to_vec().utf8_or_lossy(), "caf\u{FFFD}");
+        assert!(matches!(
+            b"cafe".as_slice().utf8_or_lossy(),
+            Cow::Borrowed("cafe")
+        ));
+    }
+}
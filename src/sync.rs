@@ -0,0 +1,285 @@
+use std::fmt::Debug;
+use std::sync::{LockResult, PoisonError, TryLockError, TryLockResult};
+
+use crate::message::Truncated;
+use crate::policy;
+
+/// Trait that provides alternatives to `lock().unwrap()` for the `LockResult` returned by locking a
+/// `Mutex` or `RwLock`.
+pub trait BULockResult<G> {
+    /// Returns the guard of a lock that was acquired without being poisoned.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the lock is poisoned, that is if another thread panicked while holding it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::sync::Mutex;
+    /// use better_unwrap::BULockResult;
+    ///
+    /// let counter = Mutex::new(0);
+    /// *counter.lock().lock_or_panic() += 1;
+    /// assert_eq!(*counter.lock().lock_or_panic(), 1);
+    /// ```
+    #[track_caller]
+    fn lock_or_panic(self) -> G;
+
+    /// Returns the guard of the lock, recovering it if the lock is poisoned.
+    ///
+    /// Equivalent to `unwrap_or_else(PoisonError::into_inner)`. Use it when the protected data stays
+    /// valid even if a thread panicked while holding the lock.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::sync::{Arc, RwLock};
+    /// use std::thread;
+    /// use better_unwrap::BULockResult;
+    ///
+    /// let hits = Arc::new(RwLock::new(0));
+    /// let writer = Arc::clone(&hits);
+    /// let _ = thread::spawn(move || {
+    ///     let _guard = writer.write().lock_or_panic();
+    ///     panic!("worker crashed");
+    /// })
+    /// .join();
+    ///
+    /// assert_eq!(*hits.read().recover_poisoned(), 0);
+    /// ```
+    fn recover_poisoned(self) -> G;
+}
+
+impl<G> BULockResult<G> for LockResult<G> {
+    #[track_caller]
+    fn lock_or_panic(self) -> G {
+        match self {
+            Ok(guard) => guard,
+            Err(_) => policy::fail(format_args!(
+                "lock poisoned by another thread that panicked while holding it"
+            )),
+        }
+    }
+
+    fn recover_poisoned(self) -> G {
+        self.unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// Trait that provides alternatives to `try_lock().unwrap()` for the `TryLockResult` returned by
+/// `Mutex::try_lock`, `RwLock::try_read`, and `RwLock::try_write`.
+pub trait BUTryLockResult<G> {
+    /// Returns the guard of a lock that was acquired without blocking and without being poisoned.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the lock is held elsewhere, or if it is poisoned, with a panic message saying which.
+    ///
+    /// # Examples
+    ///
+    /// ```should_panic
+    /// use std::sync::Mutex;
+    /// use better_unwrap::{BULockResult, BUTryLockResult};
+    ///
+    /// let queue = Mutex::new(Vec::<u32>::new());
+    /// let _held = queue.lock().lock_or_panic();
+    /// queue.try_lock().try_lock_or_panic(); // panics with `"try_lock failed: the lock is held elsewhere and acquiring it would block"`
+    /// ```
+    #[track_caller]
+    fn try_lock_or_panic(self) -> G;
+
+    /// Returns the guard of a lock that was acquired without blocking, or computes a guard with `f` if
+    /// acquiring the lock would block.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the lock is poisoned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::sync::Mutex;
+    /// use better_unwrap::{BULockResult, BUTryLockResult};
+    ///
+    /// let queue = Mutex::new(vec![1]);
+    /// let guard = queue.try_lock().try_lock_or_else(|| queue.lock().lock_or_panic());
+    /// assert_eq!(*guard, [1]);
+    /// ```
+    #[track_caller]
+    fn try_lock_or_else<F>(self, f: F) -> G
+    where
+        F: FnOnce() -> G;
+}
+
+impl<G> BUTryLockResult<G> for TryLockResult<G> {
+    #[track_caller]
+    fn try_lock_or_panic(self) -> G {
+        match self {
+            Ok(guard) => guard,
+            Err(TryLockError::WouldBlock) => policy::fail(format_args!(
+                "try_lock failed: the lock is held elsewhere and acquiring it would block"
+            )),
+            Err(TryLockError::Poisoned(_)) => policy::fail(format_args!(
+                "try_lock failed: lock poisoned by another thread that panicked while holding it"
+            )),
+        }
+    }
+
+    #[track_caller]
+    fn try_lock_or_else<F>(self, f: F) -> G
+    where
+        F: FnOnce() -> G,
+    {
+        match self {
+            Ok(guard) => guard,
+            Err(TryLockError::WouldBlock) => f(),
+            Err(TryLockError::Poisoned(_)) => policy::fail(format_args!(
+                "try_lock failed: lock poisoned by another thread that panicked while holding it"
+            )),
+        }
+    }
+}
+
+/// Trait that provides an alternative to `unwrap()` for the `Result<T, T>` returned by the
+/// `compare_exchange` methods of atomics.
+///
+/// Both variants of that result hold a value of the same type, so `unwrap()` panics with a bare
+/// `Err(3)`; this trait says that the exchange failed and shows the value that was expected.
+pub trait BUExchange<T> {
+    /// Returns the previous value of a successful `compare_exchange`, which equals `expected`.
+    ///
+    /// `expected` is the `current` argument passed to `compare_exchange`. Spurious failures of
+    /// `compare_exchange_weak` are reported like any other failure, so only use this method with
+    /// `compare_exchange`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the exchange failed, with a panic message including the expected value and the
+    /// value actually found, formatted using `Debug`.
+    ///
+    /// # Examples
+    ///
+    /// ```should_panic
+    /// use std::sync::atomic::{AtomicU32, Ordering};
+    /// use better_unwrap::BUExchange;
+    ///
+    /// let state = AtomicU32::new(2);
+    /// state
+    ///     .compare_exchange(0, 1, Ordering::AcqRel, Ordering::Acquire)
+    ///     .exchanged_or_panic(0); // panics with `"compare_exchange failed: expected 0, found 2"`
+    /// ```
+    #[track_caller]
+    fn exchanged_or_panic(self, expected: T) -> T;
+}
+
+impl<T: Debug> BUExchange<T> for Result<T, T> {
+    #[track_caller]
+    fn exchanged_or_panic(self, expected: T) -> T {
+        match self {
+            Ok(previous) => previous,
+            Err(actual) => policy::fail(format_args!(
+                "compare_exchange failed: expected {:?}, found {:?}",
+                Truncated(&expected),
+                Truncated(&actual)
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+    use std::sync::{Arc, Mutex, RwLock};
+    use std::thread;
+
+    fn poisoned_mutex() -> Arc<Mutex<u32>> {
+        let mutex = Arc::new(Mutex::new(7));
+        let holder = Arc::clone(&mutex);
+        let _ = thread::spawn(move || {
+            let _guard = holder.lock().lock_or_panic();
+            panic!("worker crashed");
+        })
+        .join();
+        mutex
+    }
+
+    #[test]
+    fn test_lock_or_panic_with_healthy_locks() {
+        let mutex = Mutex::new(1);
+        *mutex.lock().lock_or_panic() += 1;
+        assert_eq!(*mutex.lock().lock_or_panic(), 2);
+
+        let rwlock = RwLock::new(1);
+        *rwlock.write().lock_or_panic() += 1;
+        assert_eq!(*rwlock.read().lock_or_panic(), 2);
+    }
+
+    #[test]
+    #[should_panic(expected = "lock poisoned by another thread that panicked while holding it")]
+    fn test_lock_or_panic_panics_on_poisoned_lock() {
+        let mutex = poisoned_mutex();
+        let _guard = mutex.lock().lock_or_panic();
+    }
+
+    #[test]
+    fn test_try_lock_or_panic_with_free_lock() {
+        let rwlock = RwLock::new(3);
+        assert_eq!(*rwlock.try_read().try_lock_or_panic(), 3);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "try_lock failed: the lock is held elsewhere and acquiring it would block"
+    )]
+    fn test_try_lock_or_panic_panics_when_held() {
+        let rwlock = RwLock::new(3);
+        let _reader = rwlock.read().lock_or_panic();
+        let _writer = rwlock.try_write().try_lock_or_panic();
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "try_lock failed: lock poisoned by another thread that panicked while holding it"
+    )]
+    fn test_try_lock_or_panic_panics_on_poisoned_lock() {
+        let mutex = poisoned_mutex();
+        let _guard = mutex.try_lock().try_lock_or_panic();
+    }
+
+    #[test]
+    fn test_try_lock_or_else_uses_fallback_when_held() {
+        let (primary, spare) = (Mutex::new(1), Mutex::new(2));
+        let _held = primary.lock().lock_or_panic();
+        let guard = primary
+            .try_lock()
+            .try_lock_or_else(|| spare.lock().lock_or_panic());
+        assert_eq!(*guard, 2);
+    }
+
+    #[test]
+    fn test_recover_poisoned_returns_guard() {
+        let mutex = poisoned_mutex();
+        assert_eq!(*mutex.lock().recover_poisoned(), 7);
+    }
+
+    #[test]
+    fn test_exchanged_or_panic_returns_previous_value() {
+        let ready = AtomicBool::new(false);
+        let previous = ready
+            .compare_exchange(false, true, Ordering::AcqRel, Ordering::Acquire)
+            .exchanged_or_panic(false);
+        assert!(!previous);
+        assert!(ready.load(Ordering::Acquire));
+    }
+
+    #[test]
+    #[should_panic(expected = "compare_exchange failed: expected 0, found 5")]
+    fn test_exchanged_or_panic_shows_expected_and_actual() {
+        let slots = AtomicUsize::new(5);
+        slots
+            .compare_exchange(0, 1, Ordering::AcqRel, Ordering::Acquire)
+            .exchanged_or_panic(0);
+    }
+}
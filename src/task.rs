@@ -0,0 +1,129 @@
+use std::task::Poll;
+
+use crate::policy;
+
+/// Trait that provides "must be ready by now" alternatives to matching on `Poll<T>`.
+///
+/// Useful in manual `Future` and `Stream` implementations where an inner poll is known to have
+/// completed, for example after a wakeup that only fires once the inner value is ready.
+pub trait BUPoll<T> {
+    /// Returns the value of a `Poll::Ready`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is `Poll::Pending`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::task::Poll;
+    /// use better_unwrap::BUPoll;
+    ///
+    /// let x: Poll<u32> = Poll::Ready(42);
+    /// assert_eq!(x.ready_or_panic(), 42);
+    /// ```
+    ///
+    /// ```should_panic
+    /// use std::task::Poll;
+    /// use better_unwrap::BUPoll;
+    ///
+    /// let x: Poll<u32> = Poll::Pending;
+    /// x.ready_or_panic(); // panics with `"called `ready_or_panic()` on a `Pending` value"`
+    /// ```
+    #[track_caller]
+    fn ready_or_panic(self) -> T;
+}
+
+impl<T> BUPoll<T> for Poll<T> {
+    #[track_caller]
+    fn ready_or_panic(self) -> T {
+        match self {
+            Poll::Ready(value) => value,
+            Poll::Pending => policy::fail(format_args!(
+                "called `ready_or_panic()` on a `Pending` value"
+            )),
+        }
+    }
+}
+
+/// Trait that provides "must have an item ready by now" alternatives to matching on the
+/// `Poll<Option<T>>` returned by streams.
+pub trait BUPollOption<T> {
+    /// Returns the item of a `Poll::Ready(Some(_))`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is `Poll::Pending` or `Poll::Ready(None)`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::task::Poll;
+    /// use better_unwrap::BUPollOption;
+    ///
+    /// let x: Poll<Option<u32>> = Poll::Ready(Some(42));
+    /// assert_eq!(x.ready_some_or_panic(), 42);
+    /// ```
+    ///
+    /// ```should_panic
+    /// use std::task::Poll;
+    /// use better_unwrap::BUPollOption;
+    ///
+    /// let x: Poll<Option<u32>> = Poll::Ready(None);
+    /// x.ready_some_or_panic(); // panics with `"called `ready_some_or_panic()` on a `Ready(None)` value"`
+    /// ```
+    #[track_caller]
+    fn ready_some_or_panic(self) -> T;
+}
+
+impl<T> BUPollOption<T> for Poll<Option<T>> {
+    #[track_caller]
+    fn ready_some_or_panic(self) -> T {
+        match self {
+            Poll::Ready(Some(value)) => value,
+            Poll::Ready(None) => policy::fail(format_args!(
+                "called `ready_some_or_panic()` on a `Ready(None)` value"
+            )),
+            Poll::Pending => policy::fail(format_args!(
+                "called `ready_some_or_panic()` on a `Pending` value"
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ready_or_panic_with_ready() {
+        assert_eq!(Poll::Ready(42).ready_or_panic(), 42);
+        assert_eq!(Poll::Ready(Some(42)).ready_or_panic(), Some(42));
+    }
+
+    #[test]
+    #[should_panic(expected = "called `ready_or_panic()` on a `Pending` value")]
+    fn test_ready_or_panic_panics_on_pending() {
+        let poll: Poll<u32> = Poll::Pending;
+        let _ = poll.ready_or_panic();
+    }
+
+    #[test]
+    fn test_ready_some_or_panic_with_item() {
+        assert_eq!(Poll::Ready(Some(42)).ready_some_or_panic(), 42);
+    }
+
+    #[test]
+    #[should_panic(expected = "called `ready_some_or_panic()` on a `Ready(None)` value")]
+    fn test_ready_some_or_panic_panics_on_end_of_stream() {
+        let poll: Poll<Option<u32>> = Poll::Ready(None);
+        let _ = poll.ready_some_or_panic();
+    }
+
+    #[test]
+    #[should_panic(expected = "called `ready_some_or_panic()` on a `Pending` value")]
+    fn test_ready_some_or_panic_panics_on_pending() {
+        let poll: Poll<Option<u32>> = Poll::Pending;
+        let _ = poll.ready_some_or_panic();
+    }
+}
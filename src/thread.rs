@@ -0,0 +1,140 @@
+//! Joining threads, re-panicking with the message of a thread that panicked.
+
+use std::any::Any;
+use std::fmt;
+use std::thread::{JoinHandle, ScopedJoinHandle};
+
+use crate::payload::BUPanicInfo;
+use crate::policy;
+
+/// Trait that provides an alternative to `join().unwrap()` for thread handles.
+///
+/// `join().unwrap()` on a thread that panicked reports the payload as an opaque `Any { .. }`; this
+/// trait extracts the thread's panic message instead.
+pub trait BUJoinHandle<T> {
+    /// Waits for the thread to finish, yielding its result.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the thread panicked, with a panic message including the thread's name, if it has
+    /// one, and its panic message, if the payload is a `&str`, a `String`, or a [`BUPanicInfo`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::thread;
+    /// use better_unwrap::BUJoinHandle;
+    ///
+    /// let worker = thread::spawn(|| 6 * 7);
+    /// assert_eq!(worker.join_or_panic(), 42);
+    /// ```
+    ///
+    /// ```should_panic
+    /// use std::thread;
+    /// use better_unwrap::BUJoinHandle;
+    ///
+    /// let worker = thread::Builder::new()
+    ///     .name("indexer".into())
+    ///     .spawn(|| panic!("index is corrupt"))
+    ///     .unwrap();
+    /// worker.join_or_panic(); // panics with `"worker thread `indexer` panicked: index is corrupt"`
+    /// ```
+    #[track_caller]
+    fn join_or_panic(self) -> T;
+}
+
+impl<T> BUJoinHandle<T> for JoinHandle<T> {
+    #[track_caller]
+    fn join_or_panic(self) -> T {
+        let name = self.thread().name().map(str::to_owned);
+        match self.join() {
+            Ok(value) => value,
+            Err(payload) => fail(name.as_deref(), &*payload),
+        }
+    }
+}
+
+impl<T> BUJoinHandle<T> for ScopedJoinHandle<'_, T> {
+    #[track_caller]
+    fn join_or_panic(self) -> T {
+        let name = self.thread().name().map(str::to_owned);
+        match self.join() {
+            Ok(value) => value,
+            Err(payload) => fail(name.as_deref(), &*payload),
+        }
+    }
+}
+
+#[track_caller]
+fn fail(name: Option<&str>, payload: &(dyn Any + Send)) -> ! {
+    policy::fail(format_args!(
+        "worker thread {}panicked{}",
+        Name(name),
+        Message(payload)
+    ))
+}
+
+/// Renders a thread name as "`{name}` ", or nothing for an unnamed thread.
+struct Name<'a>(Option<&'a str>);
+
+impl fmt::Display for Name<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            Some(name) => write!(f, "`{name}` "),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Renders the message of a panic payload as ": {message}", or notes that it has none.
+struct Message<'a>(&'a (dyn Any + Send));
+
+impl fmt::Display for Message<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let payload = self.0;
+        if let Some(message) = payload.downcast_ref::<&str>() {
+            write!(f, ": {message}")
+        } else if let Some(message) = payload.downcast_ref::<String>() {
+            write!(f, ": {message}")
+        } else if let Some(info) = payload.downcast_ref::<BUPanicInfo>() {
+            write!(f, ": {info}")
+        } else {
+            f.write_str(" with a payload that is not a message")
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::panic;
+    use std::thread;
+
+    #[test]
+    fn test_join_or_panic_returns_result() {
+        assert_eq!(thread::spawn(|| "done").join_or_panic(), "done");
+        thread::scope(|scope| assert_eq!(scope.spawn(|| 7).join_or_panic(), 7));
+    }
+
+    #[test]
+    #[should_panic(expected = "worker thread panicked: failed on row 3")]
+    fn test_join_or_panic_extracts_string_message() {
+        thread::spawn(|| panic!("failed on row {}", 3)).join_or_panic();
+    }
+
+    #[test]
+    #[should_panic(expected = "worker thread `indexer` panicked: index is corrupt")]
+    fn test_join_or_panic_names_thread() {
+        thread::Builder::new()
+            .name("indexer".into())
+            .spawn(|| panic!("index is corrupt"))
+            .map(BUJoinHandle::join_or_panic)
+            .ok();
+    }
+
+    #[test]
+    #[should_panic(expected = "worker thread panicked with a payload that is not a message")]
+    fn test_join_or_panic_with_other_payload() {
+        thread::spawn(|| panic::panic_any(42)).join_or_panic();
+    }
+}
@@ -0,0 +1,106 @@
+use std::backtrace::{Backtrace, BacktraceStatus};
+use std::error::Error;
+use std::fmt;
+
+/// An error paired with the backtrace captured when it was first observed.
+///
+/// Created with [`BUResult::traced`](crate::BUResult::traced) or `Traced::from(error)`. Its `Debug`
+/// rendering includes the captured backtrace, so a later `or_panic()` far from where the error was
+/// created still reports where it originated.
+pub struct Traced<E> {
+    error: E,
+    backtrace: Backtrace,
+}
+
+impl<E> Traced<E> {
+    /// Wraps `error`, capturing a backtrace of the current call stack.
+    ///
+    /// The backtrace is captured even when `RUST_BACKTRACE` is unset.
+    pub fn new(error: E) -> Self {
+        Traced {
+            error,
+            backtrace: Backtrace::force_capture(),
+        }
+    }
+
+    /// Returns a reference to the wrapped error.
+    pub fn error(&self) -> &E {
+        &self.error
+    }
+
+    /// Returns the backtrace captured when the error was wrapped.
+    pub fn backtrace(&self) -> &Backtrace {
+        &self.backtrace
+    }
+
+    /// Unwraps the error, discarding the backtrace.
+    pub fn into_inner(self) -> E {
+        self.error
+    }
+}
+
+impl<E> From<E> for Traced<E> {
+    fn from(error: E) -> Self {
+        Traced::new(error)
+    }
+}
+
+impl<E: fmt::Debug> fmt::Debug for Traced<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}", self.error)?;
+        if self.backtrace.status() == BacktraceStatus::Captured {
+            write!(f, "\n\nerror originated at:\n{}", self.backtrace)?;
+        }
+        Ok(())
+    }
+}
+
+impl<E: fmt::Display> fmt::Display for Traced<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.error.fmt(f)
+    }
+}
+
+impl<E: Error> Error for Traced<E> {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        self.error.source()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::BUResult;
+
+    fn load() -> Result<u32, &'static str> {
+        Err("missing")
+    }
+
+    #[test]
+    fn test_traced_keeps_ok_value() {
+        let result: Result<u32, &str> = Ok(42);
+        assert_eq!(result.traced().or_panic(), 42);
+    }
+
+    #[test]
+    fn test_traced_wraps_error() {
+        let traced = load().traced().or_panic_err();
+        assert_eq!(*traced.error(), "missing");
+        assert_eq!(traced.backtrace().status(), BacktraceStatus::Captured);
+        assert_eq!(traced.into_inner(), "missing");
+    }
+
+    #[test]
+    fn test_traced_debug_includes_backtrace() {
+        let rendered = format!("{:?}", Traced::new("missing"));
+        assert!(rendered.starts_with("\"missing\"\n\nerror originated at:\n"));
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "called `or_panic()` on an `Err` value: \"missing\"\n\nerror originated at:"
+    )]
+    fn test_or_panic_on_traced_reports_origin() {
+        let _ = load().traced().or_panic();
+    }
+}